@@ -0,0 +1,73 @@
+//! Compares the textbook CNF with the Tseitin encoding used by `Formula::is_sat`
+//! on the preconditions and effects of the IPC domains.
+//!
+//! Run with `cargo bench --bench cnf`.
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use hddl_analyzer::{Formula, HDDLAnalyzer};
+
+fn time<T>(f: impl Fn() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+fn clause_count(cnf: &Formula) -> usize {
+    match cnf {
+        Formula::Empty => 0,
+        Formula::And(clauses) => clauses.iter().map(|clause| clause_count(clause)).sum(),
+        _ => 1,
+    }
+}
+
+fn main() {
+    let mut folders: Vec<_> = fs::read_dir("tests/ipc")
+        .unwrap()
+        .map(|folder| folder.unwrap().path())
+        .collect();
+    folders.sort();
+    println!(
+        "{:<32} {:>8} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "domain", "formulas", "cnf clauses", "cnf time", "ts clauses", "ts time", "is_sat time"
+    );
+    let (mut total_cnf, mut total_tseitin, mut total_sat) = (Duration::ZERO, Duration::ZERO, Duration::ZERO);
+    for folder in folders {
+        let domain = fs::read(folder.join("domain.hddl")).unwrap();
+        let domain = HDDLAnalyzer::parse_domain(&domain).unwrap();
+        let formulas: Vec<&Formula> = domain
+            .actions
+            .iter()
+            .flat_map(|action| action.preconditions.iter().chain(action.effects.iter()))
+            .chain(domain.methods.iter().flat_map(|method| method.precondition.iter()))
+            .collect();
+        let (cnf_clauses, cnf_time) = time(|| {
+            formulas.iter().map(|f| clause_count(&f.to_cnf())).sum::<usize>()
+        });
+        let (tseitin_clauses, tseitin_time) = time(|| {
+            formulas
+                .iter()
+                .map(|f| f.to_tseitin_clauses().map_or(0, |(_, clauses)| clauses.len()))
+                .sum::<usize>()
+        });
+        let (_, sat_time) = time(|| formulas.iter().filter(|f| f.is_sat()).count());
+        total_cnf += cnf_time;
+        total_tseitin += tseitin_time;
+        total_sat += sat_time;
+        println!(
+            "{:<32} {:>8} {:>12} {:>12.2?} {:>12} {:>12.2?} {:>12.2?}",
+            folder.file_name().unwrap().to_string_lossy(),
+            formulas.len(),
+            cnf_clauses,
+            cnf_time,
+            tseitin_clauses,
+            tseitin_time,
+            sat_time
+        );
+    }
+    println!(
+        "{:<32} {:>8} {:>12} {:>12.2?} {:>12} {:>12.2?} {:>12.2?}",
+        "total", "", "", total_cnf, "", total_tseitin, total_sat
+    );
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use hddl_analyzer::TokenPosition;
+use lsp_types::{Position, Range, Url};
+
+// contents of the files the editor has opened
+pub struct Documents {
+    texts: HashMap<Url, String>,
+}
+
+impl Documents {
+    pub fn new() -> Documents {
+        Documents {
+            texts: HashMap::new(),
+        }
+    }
+
+    pub fn open(&mut self, uri: Url, text: String) {
+        self.texts.insert(uri, text);
+    }
+
+    pub fn close(&mut self, uri: &Url) {
+        self.texts.remove(uri);
+    }
+
+    pub fn get(&self, uri: &Url) -> Option<&String> {
+        self.texts.get(uri)
+    }
+
+    pub fn uris(&self) -> Vec<Url> {
+        self.texts.keys().cloned().collect()
+    }
+
+    // finds the domain of a problem, preferring open documents over files on disk
+    pub fn find_domain(&self, problem_uri: &Url) -> Option<(Url, String)> {
+        let problem_path = problem_uri.to_file_path().ok()?;
+        let directory = problem_path.parent()?;
+        let mut candidates: Vec<(Url, String)> = self
+            .texts
+            .iter()
+            .filter(|(uri, text)| {
+                !is_problem(text)
+                    && uri
+                        .to_file_path()
+                        .map_or(false, |path| path.parent() == Some(directory))
+            })
+            .map(|(uri, text)| (uri.clone(), text.clone()))
+            .collect();
+        if candidates.is_empty() {
+            for entry in fs::read_dir(directory).ok()?.flatten() {
+                let path = entry.path();
+                if path.extension().map_or(true, |extension| extension != "hddl") {
+                    continue;
+                }
+                if let (Ok(text), Ok(uri)) = (fs::read_to_string(&path), Url::from_file_path(&path)) {
+                    if !is_problem(&text) {
+                        candidates.push((uri, text));
+                    }
+                }
+            }
+        }
+        // e.g. "x-domain.hddl" belongs to "x-problem.hddl"
+        candidates.sort_by_key(|(uri, _)| {
+            let name = uri.to_file_path().ok();
+            let name = name.as_deref().and_then(Path::file_name).and_then(|n| n.to_str());
+            let shared = name.map_or(0, |name| common_prefix(name, &problem_path));
+            (usize::MAX - shared, uri.to_string())
+        });
+        candidates.into_iter().next()
+    }
+}
+
+fn common_prefix(name: &str, problem_path: &Path) -> usize {
+    let problem_name = problem_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    name.chars()
+        .zip(problem_name.chars())
+        .take_while(|(a, b)| a == b)
+        .count()
+}
+
+// whether the text starts with "(define (problem"
+pub fn is_problem(text: &str) -> bool {
+    let mut tokens = vec![];
+    for line in text.lines() {
+        let code = line.split(';').next().unwrap_or("").to_lowercase();
+        for token in code.replace('(', " ( ").split_whitespace() {
+            tokens.push(token.to_string());
+            if tokens.len() == 4 {
+                return tokens == ["(", "define", "(", "problem"];
+            }
+        }
+    }
+    false
+}
+
+// converts a byte offset to an LSP position (UTF-16 based)
+pub fn offset_to_position(text: &str, offset: usize) -> Position {
+    let offset = offset.min(text.len());
+    let prefix = &text.as_bytes()[..offset];
+    let line = prefix.iter().filter(|c| **c == b'\n').count();
+    let line_start = prefix.iter().rposition(|c| *c == b'\n').map_or(0, |i| i + 1);
+    let character = String::from_utf8_lossy(&prefix[line_start..])
+        .encode_utf16()
+        .count();
+    Position::new(line as u32, character as u32)
+}
+
+// converts an LSP position to a byte offset
+pub fn position_to_offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let mut units = 0;
+    for (i, c) in text[line_start..].char_indices() {
+        if units >= position.character as usize || c == '\n' {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    text.len()
+}
+
+pub fn to_range(text: &str, position: &TokenPosition) -> Range {
+    Range::new(
+        offset_to_position(text, position.start),
+        offset_to_position(text, position.end),
+    )
+}
@@ -0,0 +1,36 @@
+mod documents;
+mod server;
+mod tests;
+
+use lsp_server::Connection;
+use lsp_types::{
+    HoverProviderCapability, OneOf, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind,
+};
+
+use server::Server;
+
+// language server for HDDL, communicating over stdin/stdout
+pub fn main() {
+    let (connection, io_threads) = Connection::stdio();
+    let capabilities = ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        ..Default::default()
+    };
+    let capabilities = serde_json::to_value(capabilities).unwrap();
+    if let Err(error) = connection.initialize(capabilities) {
+        eprintln!("[Error] {}", error);
+        return;
+    }
+    let mut server = Server::new(&connection);
+    if let Err(error) = server.run() {
+        eprintln!("[Error] {}", error);
+    }
+    drop(connection);
+    if let Err(error) = io_threads.join() {
+        eprintln!("[Error] {}", error);
+    }
+}
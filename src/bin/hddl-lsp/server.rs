@@ -0,0 +1,326 @@
+use std::collections::HashMap;
+use std::error::Error;
+
+use hddl_analyzer::{
+    HDDLAnalyzer, Label, ParsingError, SourceFile, SymbolIndex, SymbolReference, VerifyOptions, WarningType,
+};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _,
+    PublishDiagnostics,
+};
+use lsp_types::request::{GotoDefinition, HoverRequest, References, Request as _};
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, Location, MarkupContent, MarkupKind,
+    PublishDiagnosticsParams, ReferenceParams, TextDocumentPositionParams, Url,
+};
+
+use crate::documents::*;
+
+// the last successful analysis of a document, along with the texts it was computed from
+struct Analysis {
+    index: SymbolIndex,
+    domain: (Url, String),
+    problem: Option<(Url, String)>,
+}
+
+impl Analysis {
+    fn file_of(&self, uri: &Url) -> SourceFile {
+        match &self.problem {
+            Some((problem_uri, _)) if problem_uri == uri => SourceFile::Problem,
+            _ => SourceFile::Domain,
+        }
+    }
+
+    fn source(&self, file: SourceFile) -> &(Url, String) {
+        match (file, &self.problem) {
+            (SourceFile::Problem, Some(problem)) => problem,
+            _ => &self.domain,
+        }
+    }
+
+    fn location(&self, reference: &SymbolReference) -> Location {
+        let (uri, text) = self.source(reference.file);
+        Location::new(uri.clone(), to_range(text, &reference.position))
+    }
+}
+
+pub struct Server<'a> {
+    connection: &'a Connection,
+    documents: Documents,
+    analyses: HashMap<Url, Analysis>,
+}
+
+impl<'a> Server<'a> {
+    pub fn new(connection: &'a Connection) -> Server<'a> {
+        Server {
+            connection,
+            documents: Documents::new(),
+            analyses: HashMap::new(),
+        }
+    }
+
+    pub fn run(&mut self) -> Result<(), Box<dyn Error + Sync + Send>> {
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    self.connection.sender.send(Message::Response(response))?;
+                }
+                Message::Notification(notification) => {
+                    self.handle_notification(notification)?;
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    pub fn handle_request(&self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            GotoDefinition::METHOD => serde_json::from_value(request.params).map(|params: GotoDefinitionParams| {
+                serde_json::to_value(self.definition(params.text_document_position_params))
+            }),
+            References::METHOD => serde_json::from_value(request.params).map(|params: ReferenceParams| {
+                serde_json::to_value(self.references(
+                    params.text_document_position,
+                    params.context.include_declaration,
+                ))
+            }),
+            HoverRequest::METHOD => serde_json::from_value(request.params).map(|params: HoverParams| {
+                serde_json::to_value(self.hover(params.text_document_position_params))
+            }),
+            method => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("unsupported request {}", method),
+                )
+            }
+        };
+        match result {
+            Ok(Ok(value)) => Response::new_ok(id, value),
+            Ok(Err(error)) => error_response(id, ErrorCode::InternalError, error.to_string()),
+            Err(error) => error_response(id, ErrorCode::InvalidParams, error.to_string()),
+        }
+    }
+
+    pub fn handle_notification(&mut self, notification: Notification) -> Result<(), Box<dyn Error + Sync + Send>> {
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.open(uri.clone(), params.text_document.text);
+                self.update(&uri)?;
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                // full synchronization, so the last change holds the whole text
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.open(uri.clone(), change.text);
+                    self.update(&uri)?;
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                self.documents.close(&uri);
+                self.analyses.remove(&uri);
+                self.publish(&uri, vec![])?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // re-analyzes a document, and the open problems of it if it is a domain
+    fn update(&mut self, uri: &Url) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let Some(text) = self.documents.get(uri) else {
+            return Ok(());
+        };
+        if is_problem(text) {
+            return self.analyze_problem(uri);
+        }
+        self.analyze_domain(uri)?;
+        for other in self.documents.uris() {
+            let is_dependent = self.documents.get(&other).map_or(false, |text| is_problem(text))
+                && self.documents.find_domain(&other).map_or(false, |(domain, _)| &domain == uri);
+            if is_dependent {
+                self.analyze_problem(&other)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn analyze_domain(&mut self, uri: &Url) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let text = self.documents.get(uri).unwrap().clone();
+        let bytes = text.clone().into_bytes();
+        // smells are shown as hints
+        let diagnostics = HDDLAnalyzer::verify_all_with(&bytes, None, &VerifyOptions { smells: true, ..Default::default() });
+        let mut items: Vec<Diagnostic> = diagnostics
+            .errors
+            .iter()
+            .map(|error| error_diagnostic(uri, &text, error))
+            .collect();
+        items.extend(
+            diagnostics
+                .warnings
+                .iter()
+                .filter(|warning| !warning.is_in_problem())
+                .map(|warning| warning_diagnostic(uri, &text, warning)),
+        );
+        self.publish(uri, items)?;
+        if let Ok(index) = HDDLAnalyzer::get_symbol_index(&bytes, None) {
+            let analysis = Analysis {
+                index,
+                domain: (uri.clone(), text),
+                problem: None,
+            };
+            self.analyses.insert(uri.clone(), analysis);
+        }
+        Ok(())
+    }
+
+    fn analyze_problem(&mut self, uri: &Url) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let text = self.documents.get(uri).unwrap().clone();
+        let Some((domain_uri, domain_text)) = self.documents.find_domain(uri) else {
+            let message = "could not find the domain of this problem in the same directory";
+            let item = Diagnostic::new_simple(Default::default(), message.to_string());
+            return self.publish(uri, vec![item]);
+        };
+        let bytes = text.clone().into_bytes();
+        let domain_bytes = domain_text.clone().into_bytes();
+        let diagnostics = HDDLAnalyzer::verify_all(&domain_bytes, Some(&bytes));
+        let mut items: Vec<Diagnostic> = diagnostics
+            .problem_errors
+            .iter()
+            .map(|error| error_diagnostic(uri, &text, error))
+            .collect();
+        items.extend(
+            diagnostics
+                .warnings
+                .iter()
+                .filter(|warning| warning.is_in_problem())
+                .map(|warning| warning_diagnostic(uri, &text, warning)),
+        );
+        if diagnostics.errors.len() > 0 {
+            let message = format!("domain {} has errors", domain_uri);
+            items.push(Diagnostic::new_simple(Default::default(), message));
+        }
+        self.publish(uri, items)?;
+        if let Ok(index) = HDDLAnalyzer::get_symbol_index(&domain_bytes, Some(&bytes)) {
+            let analysis = Analysis {
+                index,
+                domain: (domain_uri, domain_text),
+                problem: Some((uri.clone(), text)),
+            };
+            self.analyses.insert(uri.clone(), analysis);
+        }
+        Ok(())
+    }
+
+    fn publish(&self, uri: &Url, diagnostics: Vec<Diagnostic>) -> Result<(), Box<dyn Error + Sync + Send>> {
+        let params = PublishDiagnosticsParams::new(uri.clone(), diagnostics, None);
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection.sender.send(Message::Notification(notification))?;
+        Ok(())
+    }
+
+    // the analysis of a document and the symbol under the cursor
+    fn symbol_at(&self, position: &TextDocumentPositionParams) -> Option<(&Analysis, &SymbolReference)> {
+        let uri = &position.text_document.uri;
+        let analysis = self.analyses.get(uri)?;
+        let file = analysis.file_of(uri);
+        let (_, text) = analysis.source(file);
+        let offset = position_to_offset(text, position.position);
+        let symbol = analysis.index.symbol_at(file, offset)?;
+        Some((analysis, symbol))
+    }
+
+    fn definition(&self, position: TextDocumentPositionParams) -> Option<GotoDefinitionResponse> {
+        let (analysis, symbol) = self.symbol_at(&position)?;
+        let declaration = analysis.index.declaration(symbol.kind, &symbol.name)?;
+        Some(GotoDefinitionResponse::Scalar(analysis.location(declaration)))
+    }
+
+    fn references(&self, position: TextDocumentPositionParams, include_declaration: bool) -> Option<Vec<Location>> {
+        let (analysis, symbol) = self.symbol_at(&position)?;
+        let locations = analysis
+            .index
+            .references(symbol.kind, &symbol.name)
+            .into_iter()
+            .filter(|reference| include_declaration || !reference.is_declaration)
+            .map(|reference| analysis.location(reference))
+            .collect();
+        Some(locations)
+    }
+
+    fn hover(&self, position: TextDocumentPositionParams) -> Option<Hover> {
+        let (analysis, symbol) = self.symbol_at(&position)?;
+        let signature = analysis.index.signature(symbol.kind, &symbol.name)?;
+        let (_, text) = analysis.source(symbol.file);
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("{}\n```hddl\n{}\n```", symbol.kind, signature),
+            }),
+            range: Some(to_range(text, &symbol.position)),
+        })
+    }
+}
+
+fn error_response(id: RequestId, code: ErrorCode, message: String) -> Response {
+    Response::new_err(id, code as i32, message)
+}
+
+fn error_diagnostic(uri: &Url, text: &str, error: &ParsingError) -> Diagnostic {
+    to_diagnostic(uri, text, error.to_string(), error.labels(), DiagnosticSeverity::ERROR)
+}
+
+fn warning_diagnostic(uri: &Url, text: &str, warning: &WarningType) -> Diagnostic {
+    // smells are shown as hints, so that they do not bury the warnings about actual flaws
+    let severity = match warning.is_smell() {
+        true => DiagnosticSeverity::HINT,
+        false => DiagnosticSeverity::WARNING,
+    };
+    to_diagnostic(uri, text, warning.to_string(), warning.labels(), severity)
+}
+
+fn to_diagnostic(uri: &Url, text: &str, message: String, labels: Vec<Label>, severity: DiagnosticSeverity) -> Diagnostic {
+    let range = labels
+        .iter()
+        .find(|label| label.primary)
+        .map_or(Default::default(), |label| to_range(text, &label.position));
+    let related: Vec<DiagnosticRelatedInformation> = labels
+        .iter()
+        .filter(|label| !label.primary)
+        .map(|label| DiagnosticRelatedInformation {
+            location: Location::new(uri.clone(), to_range(text, &label.position)),
+            message: label.message.clone(),
+        })
+        .collect();
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        source: Some("hddl".to_string()),
+        message: strip_line(&message).to_string(),
+        related_information: if related.is_empty() { None } else { Some(related) },
+        ..Default::default()
+    }
+}
+
+// the editor shows the location already, so "line 3: " is dropped from messages
+fn strip_line(message: &str) -> &str {
+    let message = message.trim_end();
+    match message.strip_prefix("line ").and_then(|rest| rest.split_once(": ")) {
+        Some((line, rest)) if line.chars().all(|c| c.is_ascii_digit()) => rest,
+        _ => message,
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(test)]
+mod document_tests {
+    use crate::documents;
+    use lsp_types::Position;
+
+    #[test]
+    pub fn problem_recognition_test() {
+        assert!(documents::is_problem("; comment (domain\n(define(problem p)"));
+        assert!(documents::is_problem("(DEFINE ( Problem p)"));
+        assert!(!documents::is_problem("(define (domain d)"));
+    }
+
+    #[test]
+    pub fn position_conversion_test() {
+        let text = "(a\n\t(é b)\n";
+        let offset = text.find('b').unwrap();
+        let position = documents::offset_to_position(text, offset);
+        assert_eq!(position, Position::new(1, 4));
+        assert_eq!(documents::position_to_offset(text, position), offset);
+        assert_eq!(documents::position_to_offset(text, Position::new(5, 0)), text.len());
+    }
+}
+
+#[cfg(test)]
+mod handler_tests {
+    use crate::documents::offset_to_position;
+    use crate::server::Server;
+    use lsp_server::{Connection, Message, Notification, Request, RequestId, Response};
+    use lsp_types::notification::{DidOpenTextDocument, Notification as _, PublishDiagnostics};
+    use lsp_types::request::{GotoDefinition, HoverRequest, References, Request as _};
+    use lsp_types::{
+        DiagnosticSeverity, DidOpenTextDocumentParams, GotoDefinitionResponse, Hover, Location,
+        Position, PublishDiagnosticsParams, TextDocumentIdentifier, TextDocumentItem, Url,
+    };
+    use serde_json::json;
+
+    const DOMAIN: &str = "(define (domain delivery)
+  (:types package location - object)
+  (:predicates (at ?p - package ?l - location))
+  (:task deliver :parameters (?p - package ?l - location))
+  (:method m_deliver
+    :parameters (?p - package ?from ?to - location)
+    :task (deliver ?p ?to)
+    :ordered-subtasks (and (t1 (move ?p ?from ?to))))
+  (:action move
+    :parameters (?p - package ?from ?to - location)
+    :precondition (at ?p ?from)
+    :effect (and (not (at ?p ?from)) (at ?p ?to)))
+)
+";
+
+    const PROBLEM: &str = "(define (problem p) (:domain delivery)
+  (:objects box - package a b - location)
+  (:htn :ordered-subtasks (and (t1 (deliver box b))))
+  (:init (at box a))
+)
+";
+
+    fn uri(name: &str) -> Url {
+        Url::from_file_path(std::env::temp_dir().join("hddl-lsp-tests").join(name)).unwrap()
+    }
+
+    // the position of the n-th occurrence of a pattern
+    fn position_of(text: &str, pattern: &str, n: usize) -> Position {
+        let (offset, _) = text.match_indices(pattern).nth(n).unwrap();
+        offset_to_position(text, offset)
+    }
+
+    fn open(server: &mut Server, client: &Connection, uri: &Url, text: &str) -> Vec<PublishDiagnosticsParams> {
+        let params = DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(uri.clone(), "hddl".to_string(), 1, text.to_string()),
+        };
+        let notification = Notification::new(DidOpenTextDocument::METHOD.to_string(), params);
+        server.handle_notification(notification).unwrap();
+        client
+            .receiver
+            .try_iter()
+            .map(|message| match message {
+                Message::Notification(n) if n.method == PublishDiagnostics::METHOD => {
+                    serde_json::from_value(n.params).unwrap()
+                }
+                message => panic!("unexpected message {:?}", message),
+            })
+            .collect()
+    }
+
+    fn request(server: &Server, method: &str, uri: &Url, position: Position) -> Response {
+        let params = json!({
+            "textDocument": TextDocumentIdentifier::new(uri.clone()),
+            "position": position,
+            "context": { "includeDeclaration": true },
+        });
+        server.handle_request(Request::new(RequestId::from(1), method.to_string(), params))
+    }
+
+    #[test]
+    pub fn publish_diagnostics_test() {
+        let (connection, client) = Connection::memory();
+        let mut server = Server::new(&connection);
+        let flawed = DOMAIN.replace(":precondition (at ?p ?from)", ":precondition (on ?p ?from)");
+        let published = open(&mut server, &client, &uri("domain.hddl"), &flawed);
+        assert_eq!(published.len(), 1);
+        let error = published[0]
+            .diagnostics
+            .iter()
+            .find(|d| d.severity == Some(DiagnosticSeverity::ERROR))
+            .unwrap();
+        assert_eq!(error.range.start, position_of(&flawed, "on ?p", 0));
+        assert!(!error.message.starts_with("line "));
+        // the problem is analyzed against the open domain
+        let published = open(&mut server, &client, &uri("problem.hddl"), PROBLEM);
+        assert_eq!(published[0].uri, uri("problem.hddl"));
+        assert!(published[0].diagnostics.iter().any(|d| d.message.contains("has errors")));
+    }
+
+    #[test]
+    pub fn definition_test() {
+        let (connection, client) = Connection::memory();
+        let mut server = Server::new(&connection);
+        open(&mut server, &client, &uri("domain.hddl"), DOMAIN);
+        open(&mut server, &client, &uri("problem.hddl"), PROBLEM);
+        // from the subtask of the problem to the task declaration of the domain
+        let response = request(&server, GotoDefinition::METHOD, &uri("problem.hddl"), position_of(PROBLEM, "deliver box", 0));
+        let definition: GotoDefinitionResponse = serde_json::from_value(response.result.unwrap()).unwrap();
+        let GotoDefinitionResponse::Scalar(location) = definition else {
+            panic!("expected a single location")
+        };
+        assert_eq!(location.uri, uri("domain.hddl"));
+        assert_eq!(location.range.start, position_of(DOMAIN, "deliver :parameters", 0));
+    }
+
+    #[test]
+    pub fn references_test() {
+        let (connection, client) = Connection::memory();
+        let mut server = Server::new(&connection);
+        open(&mut server, &client, &uri("domain.hddl"), DOMAIN);
+        let response = request(&server, References::METHOD, &uri("domain.hddl"), position_of(DOMAIN, "move", 0));
+        let locations: Vec<Location> = serde_json::from_value(response.result.unwrap()).unwrap();
+        let mut starts: Vec<Position> = locations.iter().map(|location| location.range.start).collect();
+        starts.sort();
+        assert_eq!(starts, vec![position_of(DOMAIN, "move", 0), position_of(DOMAIN, "move", 1)]);
+    }
+
+    #[test]
+    pub fn hover_test() {
+        let (connection, client) = Connection::memory();
+        let mut server = Server::new(&connection);
+        open(&mut server, &client, &uri("domain.hddl"), DOMAIN);
+        let response = request(&server, HoverRequest::METHOD, &uri("domain.hddl"), position_of(DOMAIN, "at ?p ?from", 0));
+        let hover: Hover = serde_json::from_value(response.result.unwrap()).unwrap();
+        let lsp_types::HoverContents::Markup(contents) = hover.contents else {
+            panic!("expected markdown")
+        };
+        assert!(contents.value.contains("(at ?p - package ?l - location)"));
+        // nothing under the cursor
+        let response = request(&server, HoverRequest::METHOD, &uri("domain.hddl"), Position::new(0, 0));
+        assert_eq!(response.result, Some(serde_json::Value::Null));
+    }
+}
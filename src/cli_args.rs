@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 #[derive(Parser)]
 pub struct CLIArgs {
@@ -8,8 +8,24 @@ pub struct CLIArgs {
 
 #[derive(Subcommand)]
 pub enum Commands {
-    Verify(HDDLInfo),
-    Metadata(HDDLInfo)
+    Verify(VerifyInfo),
+    Metadata(HDDLInfo),
+    /// check a plan in the IPC HTN format against a domain and a problem
+    VerifyPlan(PlanInfo),
+    /// execute a sequence of primitive actions from the initial state of a problem
+    Simulate(SimulateInfo),
+    /// rewrite domains and problems as canonical HDDL
+    Fmt(FormatInfo),
+    /// instantiate a problem and report the size of its ground model
+    Ground(GroundInfo),
+    /// export the task decomposition graph or the type hierarchy
+    Graph(GraphInfo),
+    /// list the tasks, methods and actions that every solution of a problem uses
+    Landmarks(LandmarksInfo),
+    /// decompose the initial task network of a problem step by step
+    Explore(ExploreInfo),
+    /// search for a plan of a small problem
+    Solve(SolveInfo)
 }
 
 #[derive(Parser)]
@@ -18,4 +34,151 @@ pub struct HDDLInfo {
     pub domain_path: String,
     #[arg(short, long)]
     pub problem_path: Option<String>,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Parser)]
+pub struct VerifyInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: Option<String>,
+    /// report all errors instead of stopping at the first one
+    #[arg(short, long)]
+    pub all_errors: bool,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// SAT solver binary to detect contradictions with, instead of the built-in one
+    #[arg(long)]
+    pub sat_solver: Option<String>,
+    /// write the CNF of each precondition and effect to this directory, in the DIMACS format
+    #[arg(long)]
+    pub dump_cnf: Option<String>,
+    /// also report modeling smells (unused declarations, immutable predicates, and redundant effects)
+    #[arg(short, long)]
+    pub smells: bool,
+}
+
+#[derive(Parser)]
+pub struct PlanInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// plan to verify
+    #[arg(index = 2)]
+    pub plan_path: String,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Parser)]
+pub struct SimulateInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// actions to execute, one per line
+    #[arg(index = 2)]
+    pub actions_path: String,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+}
+
+#[derive(Parser)]
+pub struct FormatInfo {
+    #[arg(required = true)]
+    pub paths: Vec<String>,
+    /// only report the files that are not formatted, without rewriting them
+    #[arg(short, long)]
+    pub check: bool,
+}
+
+#[derive(Parser)]
+pub struct GroundInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// write the ground model to this file, in the numeric format of the PANDA planners
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct LandmarksInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+}
+
+#[derive(Parser)]
+pub struct ExploreInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+}
+
+#[derive(Parser)]
+pub struct SolveInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    #[arg(short, long)]
+    pub problem_path: String,
+    /// maximum number of decompositions and actions in a plan
+    #[arg(short, long, default_value_t = 100)]
+    pub depth: usize,
+    /// maximum number of search nodes to expand
+    #[arg(short, long, default_value_t = 1_000_000)]
+    pub nodes: usize,
+}
+
+#[derive(Parser)]
+pub struct GraphInfo {
+    #[arg(index = 1)]
+    pub domain_path: String,
+    /// highlight what the initial task network of this problem reaches
+    #[arg(short, long, conflicts_with = "from")]
+    pub problem_path: Option<String>,
+    /// graph to export
+    #[arg(short, long, value_enum, default_value_t = GraphKind::Tdg)]
+    pub graph: GraphKind,
+    /// output format
+    #[arg(short, long, value_enum, default_value_t = GraphFormat::Dot)]
+    pub format: GraphFormat,
+    /// highlight the recursive cycle of the domain
+    #[arg(long)]
+    pub cycle: bool,
+    /// highlight the nullable tasks
+    #[arg(long)]
+    pub nullables: bool,
+    /// highlight what this task reaches
+    #[arg(long)]
+    pub from: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphKind {
+    Tdg,
+    Types,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum GraphFormat {
+    Dot,
+    Graphml,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Sarif,
 }
\ No newline at end of file
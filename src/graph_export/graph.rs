@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use petgraph::prelude::GraphMap;
+use petgraph::Directed;
+
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    CompoundTask,
+    PrimitiveTask,
+    Method,
+    Type,
+}
+
+impl NodeKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeKind::CompoundTask => "compound_task",
+            NodeKind::PrimitiveTask => "primitive_task",
+            NodeKind::Method => "method",
+            NodeKind::Type => "type",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    /// Unique among the nodes, since a task and a method may share a name.
+    pub id: String,
+    pub label: String,
+    pub kind: NodeKind,
+    pub is_in_cycle: bool,
+    pub is_nullable: bool,
+    pub is_reachable: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    pub source: String,
+    pub target: String,
+    pub is_in_cycle: bool,
+    pub is_reachable: bool,
+}
+
+/// A task decomposition graph or a type hierarchy, ready to be written as DOT
+/// or GraphML. Nodes and edges can be highlighted, which only changes how they
+/// are drawn.
+#[derive(Debug, Clone)]
+pub struct Graph {
+    pub name: &'static str,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl Graph {
+    /// Tasks point to their methods, and methods point to their (distinct) subtasks.
+    pub fn from_tdg(tdg: &TDG) -> Graph {
+        let mut graph = Graph {
+            name: "tdg",
+            nodes: vec![],
+            edges: vec![],
+        };
+        for task in tdg.compound_tasks() {
+            graph.add_node(task_id(task), task, NodeKind::CompoundTask);
+        }
+        for task in tdg.primitive_tasks() {
+            graph.add_node(task_id(task), task, NodeKind::PrimitiveTask);
+        }
+        for method in tdg.methods() {
+            graph.add_node(method_id(method), method, NodeKind::Method);
+        }
+        for task in tdg.compound_tasks() {
+            for method in tdg.methods_of(task) {
+                graph.add_edge(task_id(task), method_id(method));
+            }
+        }
+        for method in tdg.methods() {
+            for subtask in tdg.subtasks_of(method) {
+                graph.add_edge(method_id(method), task_id(subtask));
+            }
+        }
+        graph
+    }
+
+    /// Subtypes point to their parent types.
+    pub fn from_type_hierarchy(type_hierarchy: &GraphMap<&str, (), Directed>) -> Graph {
+        let mut graph = Graph {
+            name: "types",
+            nodes: vec![],
+            edges: vec![],
+        };
+        for node in type_hierarchy.nodes() {
+            graph.add_node(type_id(node), node, NodeKind::Type);
+        }
+        for (subtype, parent, _) in type_hierarchy.all_edges() {
+            graph.add_edge(type_id(subtype), type_id(parent));
+        }
+        graph
+    }
+
+    fn add_node(&mut self, id: String, label: &str, kind: NodeKind) {
+        self.nodes.push(Node {
+            id,
+            label: label.to_string(),
+            kind,
+            is_in_cycle: false,
+            is_nullable: false,
+            is_reachable: false,
+        });
+    }
+
+    fn add_edge(&mut self, source: String, target: String) {
+        self.edges.push(Edge {
+            source,
+            target,
+            is_in_cycle: false,
+            is_reachable: false,
+        });
+    }
+
+    /// Highlights a cycle of (task, method) pairs, as found by `TDG::get_recursion_type`.
+    pub fn highlight_cycle(&mut self, cycle: &[(String, String)]) {
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
+        for (i, (task, method)) in cycle.iter().enumerate() {
+            nodes.insert(task_id(task));
+            // the last pair only closes the cycle
+            if let Some((next_task, _)) = cycle.get(i + 1) {
+                nodes.insert(method_id(method));
+                edges.insert((task_id(task), method_id(method)));
+                edges.insert((method_id(method), task_id(next_task)));
+            }
+        }
+        for node in self.nodes.iter_mut() {
+            node.is_in_cycle |= nodes.contains(&node.id);
+        }
+        for edge in self.edges.iter_mut() {
+            edge.is_in_cycle |= edges.contains(&(edge.source.clone(), edge.target.clone()));
+        }
+    }
+
+    pub fn highlight_nullables(&mut self, nullables: &HashSet<&str>) {
+        for node in self.nodes.iter_mut() {
+            let is_task = matches!(node.kind, NodeKind::CompoundTask | NodeKind::PrimitiveTask);
+            node.is_nullable |= is_task && nullables.contains(node.label.as_str());
+        }
+    }
+
+    /// Highlights the tasks and methods that some decomposition of the given
+    /// tasks reaches (including themselves). Tasks that are not in the TDG are ignored.
+    pub fn highlight_reachable(&mut self, tdg: &TDG, tasks: &[&str]) {
+        let declared: HashSet<&str> = tdg.compound_tasks().into_iter().chain(tdg.primitive_tasks()).collect();
+        let mut nodes = HashSet::new();
+        for task in tasks.iter().filter(|task| declared.contains(*task)) {
+            let reachable = tdg.reachable(task).unwrap();
+            nodes.insert(task_id(task));
+            nodes.extend(reachable.primitives.iter().map(|t| task_id(t)));
+            for compound_task in reachable.compounds.iter() {
+                nodes.insert(task_id(compound_task));
+                nodes.extend(tdg.methods_of(compound_task).iter().map(|m| method_id(m)));
+            }
+        }
+        for node in self.nodes.iter_mut() {
+            node.is_reachable |= nodes.contains(&node.id);
+        }
+        // an edge is taken whenever its source is reached
+        for edge in self.edges.iter_mut() {
+            edge.is_reachable |= nodes.contains(&edge.source);
+        }
+    }
+}
+
+fn task_id(name: &str) -> String {
+    format!("task:{}", name)
+}
+
+fn method_id(name: &str) -> String {
+    format!("method:{}", name)
+}
+
+fn type_id(name: &str) -> String {
+    format!("type:{}", name)
+}
@@ -0,0 +1,8 @@
+mod graph;
+mod writers;
+#[cfg(test)]
+mod tests;
+
+use crate::semantic_analyzer::TDG;
+
+pub use graph::*;
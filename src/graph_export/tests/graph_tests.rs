@@ -0,0 +1,95 @@
+use super::*;
+use crate::semantic_analyzer::TypeChecker;
+
+const DOMAIN: &str = "(define (domain walk)
+    (:types room hall - location)
+    (:predicates (at ?l - location))
+    (:task go :parameters (?l - location))
+    (:task rest :parameters ())
+    (:method m_go_step
+     :parameters (?l - location)
+     :task (go ?l)
+     :ordered-subtasks (and (step ?l) (go ?l))
+    )
+    (:method m_go_done
+     :parameters (?l - location)
+     :task (go ?l)
+     :subtasks ()
+    )
+    (:method m_rest
+     :parameters ()
+     :task (rest)
+     :subtasks (and (step ?l))
+    )
+    (:action step
+     :parameters (?l - location)
+     :effect (at ?l)
+    )
+)";
+
+fn parse(program: &Vec<u8>) -> DomainAST<'_> {
+    match Parser::new(LexicalAnalyzer::new(program)).parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => d,
+        _ => panic!("wrong definition"),
+    }
+}
+
+#[test]
+pub fn tdg_graph_test() {
+    let program = DOMAIN.to_string().into_bytes();
+    let domain = parse(&program);
+    let tdg = TDG::new(&domain).unwrap();
+    let mut graph = Graph::from_tdg(&tdg);
+    let nullables = tdg.compute_nullables();
+    let recursion = tdg.get_recursion_type(&nullables);
+    graph.highlight_cycle(recursion.cycle().unwrap());
+    graph.highlight_nullables(&nullables);
+    graph.highlight_reachable(&tdg, &["rest"]);
+    assert_eq!(
+        graph.to_dot(),
+        "digraph tdg {
+    \"task:go\" [label=\"go\", shape=box, style=\"dashed\", color=red];
+    \"task:rest\" [label=\"rest\", shape=box, fillcolor=lightblue, style=\"filled\"];
+    \"task:step\" [label=\"step\", shape=ellipse, fillcolor=lightblue, style=\"filled\"];
+    \"method:m_go_step\" [label=\"m_go_step\", shape=diamond, color=red];
+    \"method:m_go_done\" [label=\"m_go_done\", shape=diamond];
+    \"method:m_rest\" [label=\"m_rest\", shape=diamond, fillcolor=lightblue, style=\"filled\"];
+    \"task:go\" -> \"method:m_go_step\" [color=red];
+    \"task:go\" -> \"method:m_go_done\";
+    \"task:rest\" -> \"method:m_rest\" [style=bold];
+    \"method:m_go_step\" -> \"task:go\" [color=red];
+    \"method:m_go_step\" -> \"task:step\";
+    \"method:m_rest\" -> \"task:step\" [style=bold];
+}
+"
+    );
+    let graphml = graph.to_graphml();
+    assert!(graphml.contains(
+        "    <node id=\"task:go\">
+      <data key=\"label\">go</data>
+      <data key=\"kind\">compound_task</data>
+      <data key=\"cycle\">true</data>
+      <data key=\"nullable\">true</data>
+    </node>"
+    ));
+    assert!(graphml.contains("    <edge source=\"task:go\" target=\"method:m_go_done\"/>"));
+    assert_eq!(graphml.matches("<node ").count(), 6);
+    assert_eq!(graphml.matches("<edge ").count(), 6);
+}
+
+#[test]
+pub fn type_graph_test() {
+    let program = DOMAIN.to_string().into_bytes();
+    let domain = parse(&program);
+    let type_checker = TypeChecker::new(&domain.types);
+    let graph = Graph::from_type_hierarchy(&type_checker.type_hierarchy);
+    let nodes: Vec<&str> = graph.nodes.iter().map(|node| node.label.as_str()).collect();
+    assert_eq!(nodes, vec!["room", "location", "hall"]);
+    assert!(graph.nodes.iter().all(|node| node.kind == NodeKind::Type));
+    let edges: Vec<(&str, &str)> = graph
+        .edges
+        .iter()
+        .map(|edge| (edge.source.as_str(), edge.target.as_str()))
+        .collect();
+    assert_eq!(edges, vec![("type:room", "type:location"), ("type:hall", "type:location")]);
+}
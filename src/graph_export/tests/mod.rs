@@ -0,0 +1,5 @@
+mod graph_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
+use crate::syntactic_analyzer::*;
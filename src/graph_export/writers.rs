@@ -0,0 +1,114 @@
+use std::fmt::Write;
+
+use super::*;
+
+impl Graph {
+    /// Compound tasks are boxes, primitive tasks are ellipses, and methods are
+    /// diamonds. The cycle is red, nullable tasks are dashed, and the reachable
+    /// part is filled (with its edges in bold).
+    pub fn to_dot(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, "digraph {} {{", self.name).unwrap();
+        for node in self.nodes.iter() {
+            let shape = match node.kind {
+                NodeKind::CompoundTask => "box",
+                NodeKind::PrimitiveTask | NodeKind::Type => "ellipse",
+                NodeKind::Method => "diamond",
+            };
+            let mut attributes = vec![format!("label={}", quoted(&node.label)), format!("shape={}", shape)];
+            let mut styles = vec![];
+            if node.is_reachable {
+                styles.push("filled");
+                attributes.push("fillcolor=lightblue".to_string());
+            }
+            if node.is_nullable {
+                styles.push("dashed");
+            }
+            if !styles.is_empty() {
+                attributes.push(format!("style={}", quoted(&styles.join(","))));
+            }
+            if node.is_in_cycle {
+                attributes.push("color=red".to_string());
+            }
+            writeln!(output, "    {} [{}];", quoted(&node.id), attributes.join(", ")).unwrap();
+        }
+        for edge in self.edges.iter() {
+            let mut attributes = vec![];
+            if edge.is_reachable {
+                attributes.push("style=bold");
+            }
+            if edge.is_in_cycle {
+                attributes.push("color=red");
+            }
+            write!(output, "    {} -> {}", quoted(&edge.source), quoted(&edge.target)).unwrap();
+            if !attributes.is_empty() {
+                write!(output, " [{}]", attributes.join(", ")).unwrap();
+            }
+            writeln!(output, ";").unwrap();
+        }
+        writeln!(output, "}}").unwrap();
+        output
+    }
+
+    /// The kind of each node and the highlights are stored as data.
+    pub fn to_graphml(&self) -> String {
+        let mut output = String::new();
+        writeln!(output, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>").unwrap();
+        writeln!(output, "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">").unwrap();
+        writeln!(output, "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>").unwrap();
+        writeln!(output, "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>").unwrap();
+        for (key, domain) in [("cycle", "all"), ("nullable", "node"), ("reachable", "all")] {
+            writeln!(
+                output,
+                "  <key id=\"{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"boolean\"><default>false</default></key>",
+                key, domain, key
+            )
+            .unwrap();
+        }
+        writeln!(output, "  <graph id=\"{}\" edgedefault=\"directed\">", self.name).unwrap();
+        for node in self.nodes.iter() {
+            writeln!(output, "    <node id=\"{}\">", escaped(&node.id)).unwrap();
+            writeln!(output, "      <data key=\"label\">{}</data>", escaped(&node.label)).unwrap();
+            writeln!(output, "      <data key=\"kind\">{}</data>", node.kind.name()).unwrap();
+            for (key, value) in [
+                ("cycle", node.is_in_cycle),
+                ("nullable", node.is_nullable),
+                ("reachable", node.is_reachable),
+            ] {
+                if value {
+                    writeln!(output, "      <data key=\"{}\">true</data>", key).unwrap();
+                }
+            }
+            writeln!(output, "    </node>").unwrap();
+        }
+        for edge in self.edges.iter() {
+            let source = escaped(&edge.source);
+            let target = escaped(&edge.target);
+            if !edge.is_in_cycle && !edge.is_reachable {
+                writeln!(output, "    <edge source=\"{}\" target=\"{}\"/>", source, target).unwrap();
+                continue;
+            }
+            writeln!(output, "    <edge source=\"{}\" target=\"{}\">", source, target).unwrap();
+            for (key, value) in [("cycle", edge.is_in_cycle), ("reachable", edge.is_reachable)] {
+                if value {
+                    writeln!(output, "      <data key=\"{}\">true</data>", key).unwrap();
+                }
+            }
+            writeln!(output, "    </edge>").unwrap();
+        }
+        writeln!(output, "  </graph>").unwrap();
+        writeln!(output, "</graphml>").unwrap();
+        output
+    }
+}
+
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn escaped(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,1050 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use super::*;
+
+// the name of the compound task that is refined into the initial task network
+const TOP_TASK: &str = "__top";
+const TOP_METHOD: &str = "__top_method";
+
+pub(super) type GroundLiteral<'a> = (bool, GroundAtom<'a>);
+// a conjunction of literals, i.e. a disjunct of a formula in disjunctive normal form
+pub(super) type Conjunction<'a> = Vec<GroundLiteral<'a>>;
+pub(super) type TaskInstance<'a> = (&'a str, Vec<&'a str>);
+
+pub(super) struct ActionInstance<'a> {
+    pub name: &'a str,
+    pub arguments: Vec<&'a str>,
+    pub preconditions: Conjunction<'a>,
+    pub effects: Vec<(Conjunction<'a>, GroundLiteral<'a>)>,
+}
+
+impl<'a> ActionInstance<'a> {
+    pub fn task(&self) -> TaskInstance<'a> {
+        (self.name, self.arguments.clone())
+    }
+}
+
+pub(super) struct MethodInstance<'a> {
+    pub name: &'a str,
+    pub arguments: Vec<&'a str>,
+    pub task: TaskInstance<'a>,
+    pub preconditions: Conjunction<'a>,
+    pub subtasks: Vec<TaskInstance<'a>>,
+    pub orderings: Vec<(usize, usize)>,
+}
+
+// ground atoms (or tasks) that are known to be reachable, indexed by their name
+#[derive(Default)]
+struct Instances<'a> {
+    // the position of each instance in `by_name`
+    known: HashMap<TaskInstance<'a>, usize>,
+    by_name: HashMap<&'a str, Vec<Vec<&'a str>>>,
+    // positions in `by_name` of the instances with the given object at the given argument
+    by_argument: HashMap<(&'a str, usize, &'a str), Vec<usize>>,
+    // the number of instances of each name that were known in the previous round
+    old: HashMap<&'a str, usize>,
+}
+
+impl<'a> Instances<'a> {
+    fn insert(&mut self, name: &'a str, arguments: &Vec<&'a str>) -> bool {
+        if self.known.contains_key(&(name, arguments.clone())) {
+            return false;
+        }
+        let instances = self.by_name.entry(name).or_default();
+        self.known.insert((name, arguments.clone()), instances.len());
+        for (i, argument) in arguments.iter().enumerate() {
+            self.by_argument.entry((name, i, argument)).or_default().push(instances.len());
+        }
+        instances.push(arguments.clone());
+        true
+    }
+
+    fn contains(&self, name: &'a str, arguments: &[&'a str]) -> bool {
+        self.known.contains_key(&(name, arguments.to_vec()))
+    }
+
+    fn position(&self, name: &'a str, arguments: Vec<&'a str>) -> Option<usize> {
+        self.known.get(&(name, arguments)).copied()
+    }
+
+    fn of(&self, name: &str) -> &[Vec<&'a str>] {
+        match self.by_name.get(name) {
+            Some(instances) => instances,
+            None => &[],
+        }
+    }
+
+    // positions of every instance
+    fn all(&self, name: &str) -> Range<usize> {
+        0..self.of(name).len()
+    }
+
+    // positions of the (old instances, new instances)
+    fn split(&self, name: &str) -> (Range<usize>, Range<usize>) {
+        let length = self.of(name).len();
+        let old = self.old.get(name).copied().unwrap_or(0).min(length);
+        (0..old, old..length)
+    }
+
+    // positions in the range of the instances with the given object at the given argument
+    fn with_argument(&self, name: &'a str, argument: usize, object: &'a str, range: &Range<usize>) -> &[usize] {
+        match self.by_argument.get(&(name, argument, object)) {
+            Some(positions) => {
+                let start = positions.partition_point(|i| *i < range.start);
+                let end = positions.partition_point(|i| *i < range.end);
+                &positions[start..end]
+            }
+            None => &[],
+        }
+    }
+
+    fn end_round(&mut self) {
+        self.old = self.by_name.iter().map(|(name, instances)| (*name, instances.len())).collect();
+    }
+}
+
+// a lifted atom (or subtask), along with the instances it is known to have
+struct Query<'s, 'a> {
+    instances: &'s Instances<'a>,
+    name: &'a str,
+    terms: &'a Vec<Symbol<'a>>,
+}
+
+// the instances (at the given positions) that bind the variables in the terms
+struct Pattern<'s, 'a> {
+    instances: &'s Instances<'a>,
+    name: &'a str,
+    range: Range<usize>,
+    terms: &'a Vec<Symbol<'a>>,
+}
+
+impl<'s, 'a> Pattern<'s, 'a> {
+    // the positions of the instances that agree with the most selective bound term, if there is
+    // one (otherwise, every instance in the range is a candidate)
+    fn indexed(&self, value: impl Fn(&Symbol<'a>) -> Option<&'a str>) -> Option<&'s [usize]> {
+        self.terms
+            .iter()
+            .enumerate()
+            .filter_map(|(i, term)| Some(self.instances.with_argument(self.name, i, value(term)?, &self.range)))
+            .min_by_key(|positions| positions.len())
+    }
+}
+
+/// Instantiates the actions, methods and tasks of a domain over the objects of
+/// a problem. Only what is reachable from the initial state (in the delete
+/// relaxation) is instantiated. Methods are instantiated top-down for the tasks
+/// that the initial task network reaches, or bottom-up from the actions where
+/// the tasks above leave too many parameters open. The model is then pruned
+/// until every task can be refined into actions.
+pub struct Grounder<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    universe: Universe<'a>,
+    // objects of each type (and of no type)
+    typed_objects: HashMap<Option<&'a str>, (Vec<&'a str>, HashSet<&'a str>)>,
+}
+
+impl<'a> Grounder<'a> {
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        symbol_table: &SymbolTable<'a>,
+    ) -> Grounder<'a> {
+        let type_checker = TypeChecker {
+            type_hierarchy: symbol_table.type_hierarchy.clone(),
+        };
+        let universe = Universe::with_type_checker(domain, problem, type_checker);
+        let mut typed_objects = HashMap::new();
+        for object_type in symbol_table.type_hierarchy.nodes().map(Some).chain([None]) {
+            let objects = universe.objects_of_type(object_type);
+            let object_set = objects.iter().copied().collect();
+            typed_objects.insert(object_type, (objects, object_set));
+        }
+        Grounder {
+            domain,
+            problem,
+            universe,
+            typed_objects,
+        }
+    }
+
+    fn objects_of_type(&self, object_type: Option<&'a str>) -> &[&'a str] {
+        match self.typed_objects.get(&object_type) {
+            Some((objects, _)) => objects,
+            None => &[],
+        }
+    }
+
+    fn is_of_type(&self, object: &str, object_type: Option<&'a str>) -> bool {
+        match self.typed_objects.get(&object_type) {
+            Some((_, objects)) => objects.contains(object),
+            None => self.universe.is_of_type(object, object_type),
+        }
+    }
+
+    pub fn ground(&self) -> GroundModel<'a> {
+        let init_state: Vec<GroundAtom<'a>> = self
+            .problem
+            .init_state
+            .iter()
+            .map(|predicate| GroundAtom::new(predicate, &Binding::new()))
+            .collect();
+        let (mut facts, mut actions) = self.ground_actions(&init_state);
+        let mut methods = self.ground_methods(&init_state, &mut facts, &actions);
+        let top = self.problem.init_tn.as_ref().map(|_| (TOP_TASK, vec![]));
+        let relaxed_reachable = StageStatistics {
+            facts: facts.known.len(),
+            tasks: count_tasks(&actions, &methods),
+            actions: actions.len(),
+            methods: methods.len(),
+        };
+        let dynamic_facts = prune(&init_state, &mut actions, &mut methods, top.as_ref());
+        let goal = match &self.problem.goal {
+            Some(goal) => self.dnf(goal, &Binding::new(), true),
+            None => vec![vec![]],
+        };
+        let goal = goal
+            .into_iter()
+            .filter_map(|mut conjunction| match dynamic_facts.simplify(&mut conjunction) {
+                true => Some(conjunction),
+                false => None,
+            })
+            .collect();
+        let statistics = GroundingStatistics {
+            instantiations: self.count_instantiations(),
+            relaxed_reachable,
+            tdg_reachable: StageStatistics {
+                facts: dynamic_facts.facts.len(),
+                tasks: count_tasks(&actions, &methods),
+                actions: actions.len(),
+                methods: methods.len(),
+            },
+        };
+        index(&init_state, &dynamic_facts, &actions, &methods, goal, top, statistics)
+    }
+
+    // type-consistent instantiations, regardless of reachability
+    fn count_instantiations(&self) -> StageStatistics {
+        let count = |parameters: &Vec<Symbol<'a>>| {
+            parameters.iter().fold(1usize, |product, parameter| {
+                product.saturating_mul(self.objects_of_type(parameter.symbol_type).len())
+            })
+        };
+        let sum = |counts: Vec<usize>| counts.into_iter().fold(0usize, |sum, n| sum.saturating_add(n));
+        let actions = sum(self.domain.actions.iter().map(|a| count(&a.parameters)).collect());
+        let mut methods = sum(self.domain.methods.iter().map(|m| count(&m.params)).collect());
+        let mut compound_tasks = sum(self.domain.compound_tasks.iter().map(|t| count(&t.parameters)).collect());
+        if let Some(init_tn) = &self.problem.init_tn {
+            methods = methods.saturating_add(match &init_tn.parameters {
+                Some(parameters) => count(parameters),
+                None => 1,
+            });
+            compound_tasks = compound_tasks.saturating_add(1);
+        }
+        StageStatistics {
+            facts: sum(self.domain.predicates.iter().map(|p| count(&p.variables)).collect()),
+            tasks: actions.saturating_add(compound_tasks),
+            actions,
+            methods,
+        }
+    }
+
+    // instantiates the actions whose preconditions are reachable in the delete relaxation
+    fn ground_actions(&self, init_state: &Vec<GroundAtom<'a>>) -> (Instances<'a>, Vec<ActionInstance<'a>>) {
+        let mut facts = Instances::default();
+        for atom in init_state.iter() {
+            facts.insert(atom.predicate, &atom.arguments);
+        }
+        let mut instances: Vec<ActionInstance<'a>> = vec![];
+        // actions whose preconditions are not reachable yet, along with their disjunctive normal form
+        let mut pending = vec![];
+        // effects whose conditions are not reachable yet
+        let mut conditional_effects = vec![];
+        let mut is_first_round = true;
+        loop {
+            for action in self.domain.actions.iter() {
+                let queries: Vec<Query> = match &action.preconditions {
+                    Some(precondition) => required_atoms(precondition)
+                        .into_iter()
+                        .map(|atom| Query {
+                            instances: &facts,
+                            name: atom.name,
+                            terms: &atom.variables,
+                        })
+                        .collect(),
+                    None => vec![],
+                };
+                for binding in self.instantiate_new(&action.parameters, &queries, is_first_round) {
+                    let conjunctions = match &action.preconditions {
+                        Some(precondition) => self.dnf(precondition, &binding, true),
+                        None => vec![vec![]],
+                    };
+                    pending.push((action, binding, conjunctions));
+                }
+            }
+            facts.end_round();
+            let mut is_changed = false;
+            let mut still_pending = vec![];
+            for (action, binding, conjunctions) in pending {
+                if !conjunctions.iter().any(|conjunction| is_relaxed_reachable(conjunction, &facts)) {
+                    still_pending.push((action, binding, conjunctions));
+                    continue;
+                }
+                let mut effects = vec![];
+                if let Some(effect) = &action.effects {
+                    self.effects(effect, &binding, &vec![], &mut effects);
+                }
+                for (conditions, (is_positive, atom)) in effects.iter() {
+                    if *is_positive {
+                        conditional_effects.push((conditions.clone(), atom.clone()));
+                    }
+                }
+                let arguments = ground_terms(&action.parameters, &binding);
+                // the remaining disjuncts may become reachable as well, so all of them are kept
+                for preconditions in conjunctions {
+                    instances.push(ActionInstance {
+                        name: action.name,
+                        arguments: arguments.clone(),
+                        preconditions,
+                        effects: effects.clone(),
+                    });
+                }
+            }
+            pending = still_pending;
+            conditional_effects.retain(|(conditions, atom)| {
+                if is_relaxed_reachable(conditions, &facts) {
+                    is_changed |= facts.insert(atom.predicate, &atom.arguments);
+                    false
+                } else {
+                    true
+                }
+            });
+            if !is_changed {
+                // disjuncts that never became reachable
+                instances.retain(|instance| is_relaxed_reachable(&instance.preconditions, &facts));
+                return (facts, instances);
+            }
+            is_first_round = false;
+        }
+    }
+
+    // instantiates the methods top-down from the initial task network, except for the ones of the
+    // compound tasks that are instantiated bottom-up (see `bottom_up_tasks`)
+    fn ground_methods(
+        &self,
+        init_state: &Vec<GroundAtom<'a>>,
+        facts: &mut Instances<'a>,
+        actions: &Vec<ActionInstance<'a>>,
+    ) -> Vec<MethodInstance<'a>> {
+        // preconditions that can never hold rule out a method before its subtasks are reached
+        let dynamic_facts = relaxed_reachable(init_state, actions);
+        let primitives: HashSet<&str> = self.domain.actions.iter().map(|action| action.name).collect();
+        let bottom_up = self.bottom_up_tasks(&primitives);
+        let mut tasks = Instances::default();
+        for action in actions.iter() {
+            tasks.insert(action.name, &action.arguments);
+        }
+        let mut instances = self.ground_bottom_up(&bottom_up, &dynamic_facts, facts, &mut tasks);
+        if let Some(init_tn) = &self.problem.init_tn {
+            // the compound tasks that are instantiated top-down
+            let top_down: HashSet<&str> = self
+                .domain
+                .compound_tasks
+                .iter()
+                .map(|task| task.name)
+                .filter(|name| !bottom_up.contains(name))
+                .collect();
+            instances.extend(self.ground_top_down(init_tn, &top_down, &dynamic_facts, facts, &tasks));
+        }
+        instances
+    }
+
+    // Top-down, a parameter of a method that only its compound subtasks use ranges over every object
+    // of its type (and so do the parameters of the subtasks' methods, and so on). A subtask whose
+    // free parameters have more combinations than there are objects, and every task below it, is
+    // instantiated bottom-up instead, as is everything when there is no initial task network.
+    fn bottom_up_tasks(&self, primitives: &HashSet<&str>) -> HashSet<&'a str> {
+        let Some(init_tn) = &self.problem.init_tn else {
+            return self.domain.compound_tasks.iter().map(|task| task.name).collect();
+        };
+        let no_parameters = vec![];
+        let networks = self
+            .domain
+            .methods
+            .iter()
+            .map(|method| (&method.params, &method.task_terms[..], &method.precondition, &method.tn))
+            .chain([(init_tn.parameters.as_ref().unwrap_or(&no_parameters), &[][..], &None, &init_tn.tn)]);
+        let objects = self.objects_of_type(None).len();
+        let mut bottom_up = HashSet::new();
+        for (parameters, task_terms, precondition, tn) in networks {
+            let mut bound: HashSet<&str> = task_terms.iter().map(|term| term.name).collect();
+            if let Some(precondition) = precondition {
+                for atom in required_atoms(precondition) {
+                    bound.extend(atom.variables.iter().map(|term| term.name));
+                }
+            }
+            for subtask in tn.subtasks.iter().filter(|subtask| primitives.contains(subtask.task.name)) {
+                bound.extend(subtask.terms.iter().map(|term| term.name));
+            }
+            for subtask in tn.subtasks.iter().filter(|subtask| !primitives.contains(subtask.task.name)) {
+                let combinations = parameters
+                    .iter()
+                    .filter(|parameter| !bound.contains(parameter.name))
+                    .filter(|parameter| subtask.terms.iter().any(|term| term.name == parameter.name))
+                    .fold(1usize, |product, parameter| {
+                        product.saturating_mul(self.objects_of_type(parameter.symbol_type).len())
+                    });
+                if combinations > objects {
+                    bottom_up.insert(subtask.task.name);
+                }
+            }
+        }
+        // the tasks below them
+        let mut queue: Vec<&str> = bottom_up.iter().copied().collect();
+        while let Some(name) = queue.pop() {
+            for method in self.domain.methods.iter().filter(|method| method.task.name == name) {
+                for subtask in method.tn.subtasks.iter() {
+                    if !primitives.contains(subtask.task.name) && bottom_up.insert(subtask.task.name) {
+                        queue.push(subtask.task.name);
+                    }
+                }
+            }
+        }
+        bottom_up
+    }
+
+    // instantiates the methods of the tasks whose subtasks can be refined into the ground actions
+    fn ground_bottom_up(
+        &self,
+        bottom_up: &HashSet<&'a str>,
+        dynamic_facts: &DynamicFacts<'a>,
+        facts: &mut Instances<'a>,
+        tasks: &mut Instances<'a>,
+    ) -> Vec<MethodInstance<'a>> {
+        let mut instances = vec![];
+        let mut is_first_round = true;
+        loop {
+            let mut candidates = vec![];
+            for method in self.domain.methods.iter().filter(|method| bottom_up.contains(method.task.name)) {
+                let queries = network_queries(&method.precondition, &method.tn, &HashSet::new(), facts, tasks);
+                for binding in self.instantiate_new(&method.params, &queries, is_first_round) {
+                    candidates.push((method, binding));
+                }
+            }
+            facts.end_round();
+            tasks.end_round();
+            let mut is_changed = false;
+            for (method, binding) in candidates {
+                let new_instances = self.ground_network(
+                    (method.name.name, ground_terms(&method.params, &binding)),
+                    (method.task.name, ground_terms(&method.task_terms, &binding)),
+                    &method.precondition,
+                    &method.tn,
+                    &binding,
+                    dynamic_facts,
+                );
+                for instance in new_instances {
+                    is_changed |= tasks.insert(instance.task.0, &instance.task.1);
+                    instances.push(instance);
+                }
+            }
+            if !is_changed {
+                return instances;
+            }
+            is_first_round = false;
+        }
+    }
+
+    // instantiates the methods of the (top-down) tasks that the initial task network reaches, where
+    // the precondition is relaxed reachable and the other subtasks are known
+    fn ground_top_down(
+        &self,
+        init_tn: &'a InitialTaskNetwork<'a>,
+        top_down: &HashSet<&'a str>,
+        dynamic_facts: &DynamicFacts<'a>,
+        facts: &Instances<'a>,
+        tasks: &Instances<'a>,
+    ) -> Vec<MethodInstance<'a>> {
+        let mut methods_of: HashMap<&str, Vec<&Method>> = HashMap::new();
+        for method in self.domain.methods.iter() {
+            methods_of.entry(method.task.name).or_default().push(method);
+        }
+        // the initial task network is the only method of the top task
+        let mut instances = vec![];
+        let no_parameters = vec![];
+        let parameters = init_tn.parameters.as_ref().unwrap_or(&no_parameters);
+        let queries = network_queries(&None, &init_tn.tn, top_down, facts, tasks);
+        for binding in self.instantiate_with(parameters, &queries, Binding::new()) {
+            instances.extend(self.ground_network(
+                (TOP_METHOD, ground_terms(parameters, &binding)),
+                (TOP_TASK, vec![]),
+                &None,
+                &init_tn.tn,
+                &binding,
+                dynamic_facts,
+            ));
+        }
+        let top_methods = instances.len();
+        let mut reachable = HashSet::new();
+        let mut queue = vec![];
+        let mut next = 0;
+        loop {
+            // the top-down subtasks of the new instances
+            for instance in instances[next..].iter() {
+                for subtask in instance.subtasks.iter() {
+                    if top_down.contains(subtask.0) && reachable.insert(subtask.clone()) {
+                        queue.push(subtask.clone());
+                    }
+                }
+            }
+            next = instances.len();
+            let Some((name, arguments)) = queue.pop() else {
+                // the methods of the top task come last
+                instances.rotate_left(top_methods);
+                return instances;
+            };
+            for method in methods_of.get(name).into_iter().flatten() {
+                let Some(binding) = self.bind_task(method, &arguments) else {
+                    continue;
+                };
+                let queries = network_queries(&method.precondition, &method.tn, top_down, facts, tasks);
+                for binding in self.instantiate_with(&method.params, &queries, binding) {
+                    instances.extend(self.ground_network(
+                        (method.name.name, ground_terms(&method.params, &binding)),
+                        (name, arguments.clone()),
+                        &method.precondition,
+                        &method.tn,
+                        &binding,
+                        dynamic_facts,
+                    ));
+                }
+            }
+        }
+    }
+
+    // the binding of the method's parameters that instantiates its task with the arguments
+    fn bind_task(&self, method: &'a Method<'a>, arguments: &[&'a str]) -> Option<Binding<'a>> {
+        if method.task_terms.len() != arguments.len() {
+            return None;
+        }
+        let mut binding = Binding::new();
+        for (term, object) in method.task_terms.iter().zip(arguments.iter()) {
+            match method.params.iter().find(|parameter| parameter.name == term.name) {
+                Some(parameter) => match binding.get(parameter.name) {
+                    Some(value) if value != object => return None,
+                    Some(_) => {}
+                    None if self.is_of_type(object, parameter.symbol_type) => {
+                        binding.insert(parameter.name, object);
+                    }
+                    None => return None,
+                },
+                // constants
+                None if term.name != *object => return None,
+                None => {}
+            }
+        }
+        Some(binding)
+    }
+
+    // extensions of the binding that match every query
+    fn instantiate_with(
+        &self,
+        parameters: &Vec<Symbol<'a>>,
+        queries: &[Query<'_, 'a>],
+        mut binding: Binding<'a>,
+    ) -> Vec<Binding<'a>> {
+        let patterns: Vec<Pattern> = queries
+            .iter()
+            .map(|query| Pattern {
+                instances: query.instances,
+                name: query.name,
+                range: query.instances.all(query.name),
+                terms: query.terms,
+            })
+            .collect();
+        let mut found = vec![];
+        self.instantiate(parameters, &patterns, &mut vec![false; patterns.len()], &mut binding, &mut found);
+        found
+    }
+
+    // bindings that match some instance which is new since the previous round (semi-naive evaluation)
+    fn instantiate_new(
+        &self,
+        parameters: &Vec<Symbol<'a>>,
+        queries: &[Query<'_, 'a>],
+        is_first_round: bool,
+    ) -> Vec<Binding<'a>> {
+        if is_first_round {
+            return self.instantiate_with(parameters, queries, Binding::new());
+        }
+        let mut found = vec![];
+        for i in 0..queries.len() {
+            let patterns: Vec<Pattern> = queries
+                .iter()
+                .enumerate()
+                .map(|(j, query)| {
+                    let (old, new) = query.instances.split(query.name);
+                    let range = match j.cmp(&i) {
+                        Ordering::Less => old,
+                        Ordering::Equal => new,
+                        Ordering::Greater => query.instances.all(query.name),
+                    };
+                    Pattern {
+                        instances: query.instances,
+                        name: query.name,
+                        range,
+                        terms: query.terms,
+                    }
+                })
+                .collect();
+            if !patterns[i].range.is_empty() {
+                self.instantiate(parameters, &patterns, &mut vec![false; patterns.len()], &mut Binding::new(), &mut found);
+            }
+        }
+        found
+    }
+
+    // one instance of a method (given by its name and arguments) for each disjunct of its precondition
+    fn ground_network(
+        &self,
+        (name, arguments): (&'a str, Vec<&'a str>),
+        task: TaskInstance<'a>,
+        precondition: &'a Option<Formula<'a>>,
+        tn: &'a HTN<'a>,
+        binding: &Binding<'a>,
+        dynamic_facts: &DynamicFacts<'a>,
+    ) -> Vec<MethodInstance<'a>> {
+        let is_consistent = tn.constraints.iter().flatten().all(|constraint| match constraint {
+            Constraint::Equal(a, b) => resolve(a.name, binding) == resolve(b.name, binding),
+            Constraint::NotEqual(a, b) => resolve(a.name, binding) != resolve(b.name, binding),
+        });
+        if !is_consistent {
+            return vec![];
+        }
+        let conjunctions = match precondition {
+            Some(precondition) => self.dnf(precondition, binding, true),
+            None => vec![vec![]],
+        };
+        let subtasks: Vec<TaskInstance<'a>> = tn
+            .subtasks
+            .iter()
+            .map(|subtask| (subtask.task.name, ground_terms(&subtask.terms, binding)))
+            .collect();
+        let orderings = orderings(tn);
+        conjunctions
+            .into_iter()
+            .filter_map(|mut conjunction| dynamic_facts.simplify(&mut conjunction).then_some(conjunction))
+            .map(|preconditions| MethodInstance {
+                name,
+                arguments: arguments.clone(),
+                task: task.clone(),
+                preconditions,
+                subtasks: subtasks.clone(),
+                orderings: orderings.clone(),
+            })
+            .collect()
+    }
+
+    // extends the binding to every parameter, matching the patterns against their known instances first
+    fn instantiate(
+        &self,
+        parameters: &Vec<Symbol<'a>>,
+        patterns: &[Pattern<'_, 'a>],
+        is_matched: &mut Vec<bool>,
+        binding: &mut Binding<'a>,
+        found: &mut Vec<Binding<'a>>,
+    ) {
+        // the object a term is bound to, if any
+        let value = |term: &Symbol<'a>, binding: &Binding<'a>| -> Option<&'a str> {
+            match parameters.iter().find(|parameter| parameter.name == term.name) {
+                Some(parameter) => binding.get(parameter.name).copied(),
+                // constants
+                None => Some(term.name),
+            }
+        };
+        // a pattern whose terms are all bound only has to be looked up
+        let is_bound = |pattern: &Pattern<'_, 'a>| pattern.terms.iter().all(|term| value(term, binding).is_some());
+        if let Some(next) = (0..patterns.len()).find(|i| !is_matched[*i] && is_bound(&patterns[*i])) {
+            let pattern = &patterns[next];
+            let arguments = pattern.terms.iter().filter_map(|term| value(term, binding)).collect();
+            let position = pattern.instances.position(pattern.name, arguments);
+            if position.is_some_and(|position| pattern.range.contains(&position)) {
+                is_matched[next] = true;
+                self.instantiate(parameters, patterns, is_matched, binding, found);
+                is_matched[next] = false;
+            }
+            return;
+        }
+        // the pattern with the fewest candidates is matched next, which keeps the joins small
+        let next = (0..patterns.len())
+            .filter(|i| !is_matched[*i])
+            .map(|i| (i, patterns[i].indexed(|term| value(term, binding))))
+            .min_by_key(|(i, positions)| positions.map_or(patterns[*i].range.len(), |positions| positions.len()));
+        let Some((next, positions)) = next else {
+            let unbound: Vec<&Symbol> = parameters
+                .iter()
+                .filter(|parameter| !binding.contains_key(parameter.name))
+                .collect();
+            self.enumerate(&unbound, &mut binding.clone(), found);
+            return;
+        };
+        let positions: Vec<usize> = match positions {
+            Some(positions) => positions.to_vec(),
+            None => patterns[next].range.clone().collect(),
+        };
+        let pattern = &patterns[next];
+        let instances = pattern.instances.of(pattern.name);
+        is_matched[next] = true;
+        for position in positions {
+            let instance = &instances[position];
+            if instance.len() != pattern.terms.len() {
+                continue;
+            }
+            let mut bound = vec![];
+            let is_match = pattern.terms.iter().zip(instance.iter()).all(|(term, object)| {
+                match parameters.iter().find(|parameter| parameter.name == term.name) {
+                    Some(parameter) => match binding.get(parameter.name) {
+                        Some(value) => value == object,
+                        None if self.is_of_type(object, parameter.symbol_type) => {
+                            binding.insert(parameter.name, object);
+                            bound.push(parameter.name);
+                            true
+                        }
+                        None => false,
+                    },
+                    // constants
+                    None => term.name == *object,
+                }
+            });
+            if is_match {
+                self.instantiate(parameters, patterns, is_matched, binding, found);
+            }
+            for name in bound {
+                binding.remove(name);
+            }
+        }
+        is_matched[next] = false;
+    }
+
+    // every extension of the binding to the variables
+    fn enumerate(&self, variables: &[&Symbol<'a>], binding: &mut Binding<'a>, found: &mut Vec<Binding<'a>>) {
+        match variables.split_first() {
+            None => found.push(binding.clone()),
+            Some((variable, rest)) => {
+                for object in self.objects_of_type(variable.symbol_type) {
+                    binding.insert(variable.name, object);
+                    self.enumerate(rest, binding, found);
+                }
+                binding.remove(variable.name);
+            }
+        }
+    }
+
+    fn extensions(&self, variables: &'a Vec<Symbol<'a>>, binding: &Binding<'a>) -> Vec<Binding<'a>> {
+        let variables: Vec<&Symbol> = variables.iter().collect();
+        let mut extensions = vec![];
+        self.enumerate(&variables, &mut binding.clone(), &mut extensions);
+        extensions
+    }
+
+    // the ground formula (or its negation) in disjunctive normal form
+    fn dnf(&self, formula: &'a Formula<'a>, binding: &Binding<'a>, is_positive: bool) -> Vec<Conjunction<'a>> {
+        let parts = |formulas: &'a Vec<Box<Formula<'a>>>, is_positive: bool| -> Vec<Vec<Conjunction<'a>>> {
+            formulas.iter().map(|f| self.dnf(f, binding, is_positive)).collect()
+        };
+        match formula {
+            Formula::Empty => truth(is_positive),
+            Formula::Atom(predicate) => vec![vec![(is_positive, GroundAtom::new(predicate, binding))]],
+            Formula::Not(f) => self.dnf(f, binding, !is_positive),
+            Formula::And(fs) => match is_positive {
+                true => conjoin(parts(fs, true)),
+                false => disjoin(parts(fs, false)),
+            },
+            Formula::Or(fs) => match is_positive {
+                true => disjoin(parts(fs, true)),
+                false => conjoin(parts(fs, false)),
+            },
+            // exactly one of the formulas holds
+            Formula::Xor(fs) => {
+                let positives = parts(fs, true);
+                let negatives = parts(fs, false);
+                let mut disjuncts = vec![];
+                if is_positive {
+                    for (i, positive) in positives.iter().enumerate() {
+                        let mut conjuncts = vec![positive.clone()];
+                        conjuncts.extend((0..fs.len()).filter(|j| *j != i).map(|j| negatives[j].clone()));
+                        disjuncts.push(conjoin(conjuncts));
+                    }
+                } else {
+                    disjuncts.push(conjoin(negatives));
+                    for i in 0..fs.len() {
+                        for j in i + 1..fs.len() {
+                            disjuncts.push(conjoin(vec![positives[i].clone(), positives[j].clone()]));
+                        }
+                    }
+                }
+                disjoin(disjuncts)
+            }
+            Formula::Imply(antecedents, consequences) => match is_positive {
+                true => disjoin(vec![disjoin(parts(antecedents, false)), conjoin(parts(consequences, true))]),
+                false => conjoin(vec![conjoin(parts(antecedents, true)), disjoin(parts(consequences, false))]),
+            },
+            Formula::Exists(variables, f) => {
+                let instances = self
+                    .extensions(variables, binding)
+                    .iter()
+                    .map(|b| self.dnf(f, b, is_positive))
+                    .collect();
+                match is_positive {
+                    true => disjoin(instances),
+                    false => conjoin(instances),
+                }
+            }
+            Formula::ForAll(variables, f) => {
+                let instances = self
+                    .extensions(variables, binding)
+                    .iter()
+                    .map(|b| self.dnf(f, b, is_positive))
+                    .collect();
+                match is_positive {
+                    true => conjoin(instances),
+                    false => disjoin(instances),
+                }
+            }
+            Formula::Equals(a, b) => truth((resolve(a.name, binding) == resolve(b.name, binding)) == is_positive),
+        }
+    }
+
+    // ground (conditional) effects, where the conditions are in disjunctive normal form
+    fn effects(
+        &self,
+        effect: &'a Formula<'a>,
+        binding: &Binding<'a>,
+        conditions: &Conjunction<'a>,
+        effects: &mut Vec<(Conjunction<'a>, GroundLiteral<'a>)>,
+    ) {
+        match effect {
+            Formula::Atom(predicate) => {
+                effects.push((conditions.clone(), (true, GroundAtom::new(predicate, binding))));
+            }
+            Formula::Not(f) => {
+                if let Formula::Atom(predicate) = &**f {
+                    effects.push((conditions.clone(), (false, GroundAtom::new(predicate, binding))));
+                }
+            }
+            Formula::And(fs) => {
+                for f in fs.iter() {
+                    self.effects(f, binding, conditions, effects);
+                }
+            }
+            Formula::ForAll(variables, f) => {
+                for b in self.extensions(variables, binding) {
+                    self.effects(f, &b, conditions, effects);
+                }
+            }
+            Formula::Imply(antecedents, consequences) => {
+                let antecedents = antecedents.iter().map(|f| self.dnf(f, binding, true)).collect();
+                for antecedent in conjoin(antecedents) {
+                    let Some(conditions) = merge(conditions, &antecedent) else {
+                        continue;
+                    };
+                    for f in consequences.iter() {
+                        self.effects(f, binding, &conditions, effects);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// the subtasks (except for the top-down ones) have to be known, along with the atoms that the
+// precondition requires
+fn network_queries<'s, 'a>(
+    precondition: &'a Option<Formula<'a>>,
+    tn: &'a HTN<'a>,
+    top_down: &HashSet<&str>,
+    facts: &'s Instances<'a>,
+    tasks: &'s Instances<'a>,
+) -> Vec<Query<'s, 'a>> {
+    let mut queries: Vec<Query> = tn
+        .subtasks
+        .iter()
+        .filter(|subtask| !top_down.contains(subtask.task.name))
+        .map(|subtask| Query {
+            instances: tasks,
+            name: subtask.task.name,
+            terms: &subtask.terms,
+        })
+        .collect();
+    if let Some(precondition) = precondition {
+        queries.extend(required_atoms(precondition).into_iter().map(|atom| Query {
+            instances: facts,
+            name: atom.name,
+            terms: &atom.variables,
+        }));
+    }
+    queries
+}
+
+// atoms that every model of the formula satisfies, i.e. the ones in its top-level conjunction
+fn required_atoms<'a>(formula: &'a Formula<'a>) -> Vec<&'a Predicate<'a>> {
+    match formula {
+        Formula::Atom(predicate) => vec![predicate],
+        Formula::And(fs) => fs.iter().flat_map(|f| required_atoms(f)).collect(),
+        _ => vec![],
+    }
+}
+
+fn ground_terms<'a>(terms: &Vec<Symbol<'a>>, binding: &Binding<'a>) -> Vec<&'a str> {
+    terms.iter().map(|term| resolve(term.name, binding)).collect()
+}
+
+// pairs of subtask positions, where the first precedes the second
+fn orderings(tn: &HTN) -> Vec<(usize, usize)> {
+    match &tn.orderings {
+        TaskOrdering::Total => (1..tn.subtasks.len()).map(|i| (i - 1, i)).collect(),
+        TaskOrdering::Partial(orderings) => {
+            let index_of = |id: &str| {
+                tn.subtasks
+                    .iter()
+                    .position(|subtask| subtask.id.as_ref().is_some_and(|s| s.name == id))
+            };
+            orderings
+                .iter()
+                .filter_map(|(first, second)| Some((index_of(first)?, index_of(second)?)))
+                .collect()
+        }
+    }
+}
+
+fn is_relaxed_reachable(conjunction: &Conjunction, facts: &Instances) -> bool {
+    conjunction
+        .iter()
+        .all(|(is_positive, atom)| !is_positive || facts.contains(atom.predicate, &atom.arguments))
+}
+
+fn truth<'a>(value: bool) -> Vec<Conjunction<'a>> {
+    match value {
+        true => vec![vec![]],
+        false => vec![],
+    }
+}
+
+fn disjoin<'a>(disjuncts: Vec<Vec<Conjunction<'a>>>) -> Vec<Conjunction<'a>> {
+    disjuncts.into_iter().flatten().collect()
+}
+
+// the cross product of the disjuncts, without the contradictory conjunctions
+fn conjoin<'a>(conjuncts: Vec<Vec<Conjunction<'a>>>) -> Vec<Conjunction<'a>> {
+    let mut result = vec![vec![]];
+    for disjuncts in conjuncts.iter() {
+        result = result
+            .iter()
+            .flat_map(|conjunction| disjuncts.iter().filter_map(|disjunct| merge(conjunction, disjunct)))
+            .collect();
+    }
+    result
+}
+
+fn merge<'a>(a: &Conjunction<'a>, b: &Conjunction<'a>) -> Option<Conjunction<'a>> {
+    let mut merged = a.clone();
+    for literal in b.iter() {
+        if merged.iter().any(|(is_positive, atom)| *is_positive != literal.0 && *atom == literal.1) {
+            return None;
+        }
+        if !merged.contains(literal) {
+            merged.push(literal.clone());
+        }
+    }
+    Some(merged)
+}
+
+fn count_tasks(actions: &Vec<ActionInstance>, methods: &Vec<MethodInstance>) -> usize {
+    let mut tasks: HashSet<TaskInstance> = actions.iter().map(|action| action.task()).collect();
+    tasks.extend(methods.iter().map(|method| method.task.clone()));
+    tasks.len()
+}
+
+// replaces the ground atoms and tasks by their indices
+fn index<'a>(
+    init_state: &Vec<GroundAtom<'a>>,
+    dynamic_facts: &DynamicFacts<'a>,
+    actions: &Vec<ActionInstance<'a>>,
+    methods: &Vec<MethodInstance<'a>>,
+    goal: Vec<Conjunction<'a>>,
+    top: Option<TaskInstance<'a>>,
+    statistics: GroundingStatistics,
+) -> GroundModel<'a> {
+    let fact_ids: HashMap<&GroundAtom, usize> = dynamic_facts
+        .facts
+        .iter()
+        .enumerate()
+        .map(|(id, fact)| (fact, id))
+        .collect();
+    let literals = |conjunction: &Conjunction<'a>| -> Vec<Literal> {
+        conjunction
+            .iter()
+            .map(|(is_positive, atom)| Literal {
+                fact: fact_ids[atom],
+                is_positive: *is_positive,
+            })
+            .collect()
+    };
+    let mut tasks = vec![];
+    let mut task_ids = HashMap::new();
+    let primitive_tasks = actions.iter().map(|action| (action.task(), true));
+    let compound_tasks = methods.iter().map(|method| (method.task.clone(), false));
+    for (task, is_primitive) in primitive_tasks.chain(compound_tasks) {
+        if !task_ids.contains_key(&task) {
+            task_ids.insert(task.clone(), tasks.len());
+            tasks.push(GroundTask {
+                name: task.0,
+                arguments: task.1,
+                is_primitive,
+            });
+        }
+    }
+    GroundModel {
+        facts: dynamic_facts.facts.clone(),
+        actions: actions
+            .iter()
+            .map(|action| GroundAction {
+                task: task_ids[&action.task()],
+                preconditions: literals(&action.preconditions),
+                effects: action
+                    .effects
+                    .iter()
+                    .map(|(conditions, effect)| ConditionalEffect {
+                        conditions: literals(conditions),
+                        effect: literals(&vec![effect.clone()])[0],
+                    })
+                    .collect(),
+            })
+            .collect(),
+        methods: methods
+            .iter()
+            .map(|method| GroundMethod {
+                name: method.name,
+                arguments: method.arguments.clone(),
+                task: task_ids[&method.task],
+                preconditions: literals(&method.preconditions),
+                subtasks: method.subtasks.iter().map(|subtask| task_ids[subtask]).collect(),
+                orderings: method.orderings.clone(),
+            })
+            .collect(),
+        init_state: dynamic_facts
+            .facts
+            .iter()
+            .enumerate()
+            .filter(|(_, fact)| init_state.contains(fact))
+            .map(|(id, _)| id)
+            .collect(),
+        goal: goal.iter().map(literals).collect(),
+        top: top.and_then(|top| task_ids.get(&top).copied()),
+        tasks,
+        statistics,
+    }
+}
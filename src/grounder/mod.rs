@@ -0,0 +1,14 @@
+mod instantiation;
+mod model;
+mod reachability;
+mod panda;
+#[cfg(test)]
+mod tests;
+
+use crate::syntactic_analyzer::*;
+use crate::semantic_analyzer::{SymbolTable, TypeChecker};
+use crate::plan_verifier::{resolve, Binding, GroundAtom, Universe};
+use reachability::*;
+
+pub use instantiation::Grounder;
+pub use model::*;
@@ -0,0 +1,143 @@
+use std::fmt;
+
+use super::*;
+
+/// A ground HTN model. Facts and tasks are referred to by their index, and
+/// facts that never change (or are never reachable) are compiled away.
+#[derive(Debug)]
+pub struct GroundModel<'a> {
+    pub facts: Vec<GroundAtom<'a>>,
+    /// Primitive tasks come before compound ones.
+    pub tasks: Vec<GroundTask<'a>>,
+    pub actions: Vec<GroundAction>,
+    pub methods: Vec<GroundMethod<'a>>,
+    pub init_state: Vec<usize>,
+    /// Disjuncts of the goal, i.e. a conjunctive goal has exactly one of them,
+    /// and an unreachable goal has none.
+    pub goal: Vec<Vec<Literal>>,
+    /// The compound task whose methods are the initial task networks.
+    pub top: Option<usize>,
+    pub statistics: GroundingStatistics,
+}
+
+impl<'a> GroundModel<'a> {
+    pub fn actions_of(&self, task: usize) -> Vec<&GroundAction> {
+        self.actions.iter().filter(|action| action.task == task).collect()
+    }
+
+    pub fn methods_of(&self, task: usize) -> Vec<&GroundMethod<'a>> {
+        self.methods.iter().filter(|method| method.task == task).collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroundTask<'a> {
+    pub name: &'a str,
+    pub arguments: Vec<&'a str>,
+    pub is_primitive: bool,
+}
+
+impl<'a> fmt::Display for GroundTask<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}", self.name)?;
+        for argument in self.arguments.iter() {
+            write!(f, " {}", argument)?;
+        }
+        write!(f, ")")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Literal {
+    pub fact: usize,
+    pub is_positive: bool,
+}
+
+/// An effect that takes place if its conditions hold before the action.
+/// Unconditional effects have no conditions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConditionalEffect {
+    pub conditions: Vec<Literal>,
+    pub effect: Literal,
+}
+
+/// An action whose precondition is a conjunction. Actions with disjunctive
+/// preconditions are split into one ground action for each disjunct, so a
+/// primitive task may have several of them.
+#[derive(Debug, Clone)]
+pub struct GroundAction {
+    pub task: usize,
+    pub preconditions: Vec<Literal>,
+    pub effects: Vec<ConditionalEffect>,
+}
+
+#[derive(Debug, Clone)]
+pub struct GroundMethod<'a> {
+    pub name: &'a str,
+    /// The objects bound to the parameters of the method.
+    pub arguments: Vec<&'a str>,
+    pub task: usize,
+    pub preconditions: Vec<Literal>,
+    pub subtasks: Vec<usize>,
+    /// Pairs of positions in `subtasks`, where the first precedes the second.
+    pub orderings: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StageStatistics {
+    pub facts: usize,
+    pub tasks: usize,
+    pub actions: usize,
+    pub methods: usize,
+}
+
+impl StageStatistics {
+    fn pruned(&self, next: &StageStatistics) -> StageStatistics {
+        StageStatistics {
+            facts: self.facts.saturating_sub(next.facts),
+            tasks: self.tasks.saturating_sub(next.tasks),
+            actions: self.actions.saturating_sub(next.actions),
+            methods: self.methods.saturating_sub(next.methods),
+        }
+    }
+}
+
+/// Sizes of the model after each stage of the grounding.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GroundingStatistics {
+    /// Every type-consistent instantiation of the predicates, tasks, actions and methods.
+    pub instantiations: StageStatistics,
+    /// What is reachable from the initial state in the delete relaxation, as
+    /// instantiated before pruning.
+    pub relaxed_reachable: StageStatistics,
+    /// What is reachable from the initial task network as well (pruned until a fixpoint).
+    pub tdg_reachable: StageStatistics,
+}
+
+impl GroundingStatistics {
+    pub fn pruned_by_relaxed_reachability(&self) -> StageStatistics {
+        self.instantiations.pruned(&self.relaxed_reachable)
+    }
+
+    pub fn pruned_by_tdg_reachability(&self) -> StageStatistics {
+        self.relaxed_reachable.pruned(&self.tdg_reachable)
+    }
+}
+
+impl fmt::Display for GroundingStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<20}{:>12}{:>12}{:>12}{:>12}", "Stage", "Facts", "Tasks", "Actions", "Methods")?;
+        for (stage, statistics) in [
+            ("Instantiations", &self.instantiations),
+            ("Relaxed reachable", &self.relaxed_reachable),
+            ("TDG reachable", &self.tdg_reachable),
+        ] {
+            writeln!(
+                f,
+                "{:<20}{:>12}{:>12}{:>12}{:>12}",
+                stage, statistics.facts, statistics.tasks, statistics.actions, statistics.methods
+            )?;
+        }
+        Ok(())
+    }
+}
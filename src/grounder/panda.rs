@@ -0,0 +1,323 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::*;
+
+// a primitive task of the output, where each condition and effect is a fact index
+struct PandaAction {
+    name: String,
+    preconditions: Vec<usize>,
+    add_effects: Vec<(Vec<usize>, usize)>,
+    delete_effects: Vec<(Vec<usize>, usize)>,
+}
+
+// a task of the output before the primitive ones are counted
+#[derive(Clone, Copy)]
+enum TaskReference {
+    Primitive(usize),
+    Abstract(usize),
+}
+
+struct PandaMethod {
+    name: String,
+    task: TaskReference,
+    subtasks: Vec<TaskReference>,
+    orderings: Vec<(usize, usize)>,
+}
+
+impl<'a> GroundModel<'a> {
+    /// Writes the model in the numeric intermediate format of the PANDA planners.
+    ///
+    /// The format has neither negative conditions, method preconditions, nor
+    /// disjunctive goals, so they are compiled away:
+    /// * a fact that is required to be false gets a complementary fact, which
+    ///   the actions keep up to date,
+    /// * a method with a precondition starts with an action that checks it,
+    /// * a disjunctive goal is checked by an action at the end of the initial
+    ///   task network, with one method of the top task for each disjunct,
+    /// * a primitive task with several actions (i.e., a disjunctive precondition)
+    ///   becomes an abstract task with one method for each of them.
+    ///
+    /// Without an initial task network, the top task has no methods, so the
+    /// written problem has no solution.
+    pub fn to_panda(&self) -> String {
+        let mut writer = PandaWriter::new(self);
+        writer.compile();
+        writer.write()
+    }
+}
+
+struct PandaWriter<'m, 'a> {
+    model: &'m GroundModel<'a>,
+    // the complementary fact of each fact that is required to be false
+    complements: HashMap<usize, usize>,
+    fact_names: Vec<String>,
+    actions: Vec<PandaAction>,
+    abstract_tasks: Vec<String>,
+    methods: Vec<PandaMethod>,
+    goal: Vec<usize>,
+    top: TaskReference,
+}
+
+impl<'m, 'a> PandaWriter<'m, 'a> {
+    fn new(model: &'m GroundModel<'a>) -> PandaWriter<'m, 'a> {
+        let mut fact_names: Vec<String> = model
+            .facts
+            .iter()
+            .map(|fact| format!("+{}", bracketed(fact.predicate, &fact.arguments)))
+            .collect();
+        let conditions = model
+            .actions
+            .iter()
+            .flat_map(|action| {
+                action
+                    .preconditions
+                    .iter()
+                    .chain(action.effects.iter().flat_map(|effect| effect.conditions.iter()))
+            })
+            .chain(model.methods.iter().flat_map(|method| method.preconditions.iter()))
+            .chain(model.goal.iter().flatten());
+        let mut complements = HashMap::new();
+        for literal in conditions.filter(|literal| !literal.is_positive) {
+            if let Entry::Vacant(entry) = complements.entry(literal.fact) {
+                let fact = &model.facts[literal.fact];
+                entry.insert(fact_names.len());
+                fact_names.push(format!("-{}", bracketed(fact.predicate, &fact.arguments)));
+            }
+        }
+        PandaWriter {
+            model,
+            complements,
+            fact_names,
+            actions: vec![],
+            abstract_tasks: vec![],
+            methods: vec![],
+            goal: vec![],
+            top: TaskReference::Abstract(0),
+        }
+    }
+
+    fn fact(&self, literal: &Literal) -> usize {
+        match literal.is_positive {
+            true => literal.fact,
+            false => self.complements[&literal.fact],
+        }
+    }
+
+    fn facts(&self, literals: &[Literal]) -> Vec<usize> {
+        literals.iter().map(|literal| self.fact(literal)).collect()
+    }
+
+    // an action that only checks the literals
+    fn check(&mut self, name: String, literals: &[Literal]) -> TaskReference {
+        self.actions.push(PandaAction {
+            name,
+            preconditions: self.facts(literals),
+            add_effects: vec![],
+            delete_effects: vec![],
+        });
+        TaskReference::Primitive(self.actions.len() - 1)
+    }
+
+    fn compile(&mut self) {
+        let model = self.model;
+        // the output task of each task of the model
+        let mut tasks = vec![];
+        for (id, task) in model.tasks.iter().enumerate() {
+            let name = bracketed(task.name, &task.arguments);
+            if !task.is_primitive {
+                self.abstract_tasks.push(name);
+                tasks.push(TaskReference::Abstract(self.abstract_tasks.len() - 1));
+                continue;
+            }
+            let actions = model.actions_of(id);
+            let mut references = vec![];
+            for (i, action) in actions.iter().enumerate() {
+                let name = match actions.len() {
+                    1 => name.clone(),
+                    _ => bracketed(&format!("{}_{}", task.name, i), &task.arguments),
+                };
+                references.push(self.action(name, action));
+            }
+            if let [reference] = references[..] {
+                tasks.push(reference);
+                continue;
+            }
+            self.abstract_tasks.push(name.clone());
+            let wrapper = TaskReference::Abstract(self.abstract_tasks.len() - 1);
+            for (i, reference) in references.into_iter().enumerate() {
+                let method = bracketed(&format!("__disjunct_{}_{}", task.name, i), &task.arguments);
+                self.methods.push(PandaMethod {
+                    name: method,
+                    task: wrapper,
+                    subtasks: vec![reference],
+                    orderings: vec![],
+                });
+            }
+            tasks.push(wrapper);
+        }
+        match model.top.map(|top| tasks[top]) {
+            Some(top) => self.top = top,
+            None => {
+                self.abstract_tasks.push(bracketed("__top", &[]));
+                self.top = TaskReference::Abstract(self.abstract_tasks.len() - 1);
+            }
+        }
+        if model.goal.len() == 1 {
+            self.goal = self.facts(&model.goal[0]);
+        }
+        for method in model.methods.iter() {
+            let name = bracketed(method.name, &method.arguments);
+            let mut subtasks: Vec<TaskReference> = method.subtasks.iter().map(|subtask| tasks[*subtask]).collect();
+            let mut orderings = method.orderings.clone();
+            if !method.preconditions.is_empty() {
+                let check = self.check(format!("__method_precondition_{}", name), &method.preconditions);
+                orderings = orderings.iter().map(|(first, second)| (first + 1, second + 1)).collect();
+                orderings.extend((1..=subtasks.len()).map(|i| (0, i)));
+                subtasks.insert(0, check);
+            }
+            if Some(method.task) != model.top || model.goal.len() == 1 {
+                self.methods.push(PandaMethod {
+                    name,
+                    task: tasks[method.task],
+                    subtasks,
+                    orderings,
+                });
+                continue;
+            }
+            // the goal is checked after the whole task network, for each disjunct
+            for (i, goal) in model.goal.iter().enumerate() {
+                let check = self.check(bracketed(&format!("__goal_{}", i), &[]), goal);
+                let mut subtasks = subtasks.clone();
+                let mut orderings = orderings.clone();
+                orderings.extend((0..subtasks.len()).map(|j| (j, subtasks.len())));
+                subtasks.push(check);
+                self.methods.push(PandaMethod {
+                    name: bracketed(&format!("{}_{}", method.name, i), &method.arguments),
+                    task: tasks[method.task],
+                    subtasks,
+                    orderings,
+                });
+            }
+        }
+    }
+
+    // an action of the model, whose effects keep the complementary facts up to date
+    fn action(&mut self, name: String, action: &GroundAction) -> TaskReference {
+        let mut add_effects = vec![];
+        let mut delete_effects = vec![];
+        for effect in action.effects.iter() {
+            let conditions = self.facts(&effect.conditions);
+            let complement = self.complements.get(&effect.effect.fact).copied();
+            let (added, deleted) = match effect.effect.is_positive {
+                true => (Some(effect.effect.fact), complement),
+                false => (complement, Some(effect.effect.fact)),
+            };
+            if let Some(fact) = added {
+                add_effects.push((conditions.clone(), fact));
+            }
+            if let Some(fact) = deleted {
+                delete_effects.push((conditions, fact));
+            }
+        }
+        self.actions.push(PandaAction {
+            name,
+            preconditions: self.facts(&action.preconditions),
+            add_effects,
+            delete_effects,
+        });
+        TaskReference::Primitive(self.actions.len() - 1)
+    }
+
+    // primitive tasks come first, and have the same indices as their actions
+    fn task_index(&self, task: TaskReference) -> usize {
+        match task {
+            TaskReference::Primitive(i) => i,
+            TaskReference::Abstract(i) => self.actions.len() + i,
+        }
+    }
+
+    fn write(self) -> String {
+        let mut output = String::new();
+        // facts that are not in the initial state have their complement there instead
+        let mut init_state = self.model.init_state.clone();
+        let mut complements: Vec<(&usize, &usize)> = self.complements.iter().collect();
+        complements.sort_by_key(|(_, complement)| **complement);
+        for (fact, complement) in complements {
+            if !self.model.init_state.contains(fact) {
+                init_state.push(*complement);
+            }
+        }
+
+        writeln!(output, ";; #state features").unwrap();
+        writeln!(output, "{}", self.fact_names.len()).unwrap();
+        for name in self.fact_names.iter() {
+            writeln!(output, "{}", name).unwrap();
+        }
+        // every fact is a mutex group of its own
+        writeln!(output, "\n;; Mutex Groups").unwrap();
+        writeln!(output, "{}", self.fact_names.len()).unwrap();
+        for (i, name) in self.fact_names.iter().enumerate() {
+            writeln!(output, "{} {} {}", i, i, name).unwrap();
+        }
+        for section in ["further strict Mutex Groups", "further non strict Mutex Groups", "known invariants"] {
+            writeln!(output, "\n;; {}\n0", section).unwrap();
+        }
+        writeln!(output, "\n;; Actions").unwrap();
+        writeln!(output, "{}", self.actions.len()).unwrap();
+        for action in self.actions.iter() {
+            writeln!(output, "1").unwrap();
+            writeln!(output, "{}", list(&action.preconditions)).unwrap();
+            writeln!(output, "{}", effect_list(&action.add_effects)).unwrap();
+            writeln!(output, "{}", effect_list(&action.delete_effects)).unwrap();
+        }
+        writeln!(output, "\n;; initial state\n{}", list(&init_state)).unwrap();
+        writeln!(output, "\n;; goal\n{}", list(&self.goal)).unwrap();
+        writeln!(output, "\n;; tasks (primitive and abstract)").unwrap();
+        writeln!(output, "{}", self.actions.len() + self.abstract_tasks.len()).unwrap();
+        for action in self.actions.iter() {
+            writeln!(output, "0 {}", action.name).unwrap();
+        }
+        for task in self.abstract_tasks.iter() {
+            writeln!(output, "1 {}", task).unwrap();
+        }
+        writeln!(output, "\n;; initial abstract task\n{}", self.task_index(self.top)).unwrap();
+        writeln!(output, "\n;; methods").unwrap();
+        writeln!(output, "{}", self.methods.len()).unwrap();
+        for method in self.methods.iter() {
+            let subtasks: Vec<usize> = method.subtasks.iter().map(|subtask| self.task_index(*subtask)).collect();
+            writeln!(output, "{}", method.name).unwrap();
+            writeln!(output, "{}", self.task_index(method.task)).unwrap();
+            writeln!(output, "{}", list(&subtasks)).unwrap();
+            let orderings: Vec<usize> = method.orderings.iter().flat_map(|(first, second)| [*first, *second]).collect();
+            writeln!(output, "{}", list(&orderings)).unwrap();
+        }
+        output
+    }
+}
+
+fn bracketed(name: &str, arguments: &[&str]) -> String {
+    format!("{}[{}]", name, arguments.join(","))
+}
+
+// numbers terminated by -1
+fn list(numbers: &[usize]) -> String {
+    let mut output = String::new();
+    for number in numbers.iter() {
+        write!(output, "{} ", number).unwrap();
+    }
+    output.push_str("-1");
+    output
+}
+
+// the number of conditions, the conditions, and the fact of each effect
+fn effect_list(effects: &[(Vec<usize>, usize)]) -> String {
+    let mut numbers = vec![];
+    for (conditions, fact) in effects.iter() {
+        numbers.push(conditions.len());
+        numbers.extend(conditions.iter().copied());
+        numbers.push(*fact);
+    }
+    list(&numbers)
+}
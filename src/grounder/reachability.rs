@@ -0,0 +1,181 @@
+use std::collections::{HashMap, HashSet};
+
+use super::instantiation::*;
+use super::*;
+
+// facts that may change, along with the ones that never do
+pub(super) struct DynamicFacts<'a> {
+    pub facts: Vec<GroundAtom<'a>>,
+    reachable: HashSet<GroundAtom<'a>>,
+    // facts of the initial state that no action deletes
+    always_true: HashSet<GroundAtom<'a>>,
+}
+
+impl<'a> DynamicFacts<'a> {
+    // the value of a fact that never changes
+    fn value(&self, atom: &GroundAtom<'a>) -> Option<bool> {
+        if !self.reachable.contains(atom) {
+            Some(false)
+        } else if self.always_true.contains(atom) {
+            Some(true)
+        } else {
+            None
+        }
+    }
+
+    // drops the literals whose value never changes, and tells whether the rest may hold
+    pub fn simplify(&self, conjunction: &mut Conjunction<'a>) -> bool {
+        let mut is_satisfiable = true;
+        conjunction.retain(|(is_positive, atom)| match self.value(atom) {
+            Some(value) => {
+                is_satisfiable &= value == *is_positive;
+                false
+            }
+            None => true,
+        });
+        is_satisfiable
+    }
+}
+
+// prunes the instances until relaxed reachability, refinability and TDG reachability agree
+pub(super) fn prune<'a>(
+    init_state: &Vec<GroundAtom<'a>>,
+    actions: &mut Vec<ActionInstance<'a>>,
+    methods: &mut Vec<MethodInstance<'a>>,
+    top: Option<&TaskInstance<'a>>,
+) -> DynamicFacts<'a> {
+    loop {
+        let size = model_size(actions, methods);
+        let dynamic_facts = relaxed_reachable(init_state, actions);
+        actions.retain_mut(|action| {
+            action.effects.retain_mut(|(conditions, effect)| {
+                dynamic_facts.simplify(conditions) && dynamic_facts.value(&effect.1).is_none()
+            });
+            dynamic_facts.simplify(&mut action.preconditions)
+        });
+        methods.retain_mut(|method| dynamic_facts.simplify(&mut method.preconditions));
+        prune_unrefinable(actions, methods);
+        if let Some(top) = top {
+            prune_unreachable(top, actions, methods);
+        }
+        if model_size(actions, methods) == size {
+            return dynamic_facts;
+        }
+    }
+}
+
+fn model_size(actions: &Vec<ActionInstance>, methods: &Vec<MethodInstance>) -> (usize, usize, usize) {
+    let literals = actions
+        .iter()
+        .map(|action| action.preconditions.len() + action.effects.iter().map(|e| e.0.len() + 1).sum::<usize>())
+        .chain(methods.iter().map(|method| method.preconditions.len()))
+        .sum();
+    (actions.len(), methods.len(), literals)
+}
+
+// facts that are reachable in the delete relaxation of the actions
+pub(super) fn relaxed_reachable<'a>(init_state: &Vec<GroundAtom<'a>>, actions: &Vec<ActionInstance<'a>>) -> DynamicFacts<'a> {
+    let mut facts = vec![];
+    let mut reachable = HashSet::new();
+    for atom in init_state.iter() {
+        if reachable.insert(atom.clone()) {
+            facts.push(atom.clone());
+        }
+    }
+    let holds = |conjunction: &Conjunction<'a>, reachable: &HashSet<GroundAtom<'a>>| {
+        conjunction.iter().all(|(is_positive, atom)| !is_positive || reachable.contains(atom))
+    };
+    let mut is_changed = true;
+    while is_changed {
+        is_changed = false;
+        for action in actions.iter() {
+            if !holds(&action.preconditions, &reachable) {
+                continue;
+            }
+            for (conditions, (is_positive, atom)) in action.effects.iter() {
+                if *is_positive && holds(conditions, &reachable) && reachable.insert(atom.clone()) {
+                    facts.push(atom.clone());
+                    is_changed = true;
+                }
+            }
+        }
+    }
+    let deleted: HashSet<&GroundAtom> = actions
+        .iter()
+        .filter(|action| holds(&action.preconditions, &reachable))
+        .flat_map(|action| action.effects.iter())
+        .filter(|(conditions, (is_positive, _))| !is_positive && holds(conditions, &reachable))
+        .map(|(_, (_, atom))| atom)
+        .collect();
+    let always_true: HashSet<GroundAtom> = init_state
+        .iter()
+        .filter(|atom| !deleted.contains(atom))
+        .cloned()
+        .collect();
+    DynamicFacts {
+        facts: facts.into_iter().filter(|atom| !always_true.contains(atom)).collect(),
+        reachable,
+        always_true,
+    }
+}
+
+// methods with a subtask that can not be refined into actions
+fn prune_unrefinable<'a>(actions: &Vec<ActionInstance<'a>>, methods: &mut Vec<MethodInstance<'a>>) {
+    // the methods of each subtask, along with the number of subtasks a method is still missing
+    let mut users: HashMap<&TaskInstance, Vec<usize>> = HashMap::new();
+    let mut missing: Vec<usize> = vec![0; methods.len()];
+    for (i, method) in methods.iter().enumerate() {
+        for subtask in method.subtasks.iter() {
+            users.entry(subtask).or_default().push(i);
+            missing[i] += 1;
+        }
+    }
+    let mut refinable: HashSet<TaskInstance> = HashSet::new();
+    let mut queue: Vec<TaskInstance> = vec![];
+    for action in actions.iter() {
+        let task = action.task();
+        if refinable.insert(task.clone()) {
+            queue.push(task);
+        }
+    }
+    for method in methods.iter().filter(|method| method.subtasks.is_empty()) {
+        if refinable.insert(method.task.clone()) {
+            queue.push(method.task.clone());
+        }
+    }
+    while let Some(task) = queue.pop() {
+        // a subtask may occur in a method more than once
+        for i in users.get(&task).into_iter().flatten() {
+            missing[*i] -= 1;
+            if missing[*i] == 0 && refinable.insert(methods[*i].task.clone()) {
+                queue.push(methods[*i].task.clone());
+            }
+        }
+    }
+    methods.retain(|method| method.subtasks.iter().all(|subtask| refinable.contains(subtask)));
+}
+
+// actions and methods that no decomposition of the top task reaches
+fn prune_unreachable<'a>(
+    top: &TaskInstance<'a>,
+    actions: &mut Vec<ActionInstance<'a>>,
+    methods: &mut Vec<MethodInstance<'a>>,
+) {
+    let mut methods_of: HashMap<&TaskInstance, Vec<&MethodInstance>> = HashMap::new();
+    for method in methods.iter() {
+        methods_of.entry(&method.task).or_default().push(method);
+    }
+    let mut reachable = HashSet::from([top.clone()]);
+    let mut queue = vec![top.clone()];
+    while let Some(task) = queue.pop() {
+        for method in methods_of.get(&task).into_iter().flatten() {
+            for subtask in method.subtasks.iter() {
+                if reachable.insert(subtask.clone()) {
+                    queue.push(subtask.clone());
+                }
+            }
+        }
+    }
+    actions.retain(|action| reachable.contains(&action.task()));
+    methods.retain(|method| reachable.contains(&method.task));
+}
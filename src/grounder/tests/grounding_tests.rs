@@ -0,0 +1,169 @@
+use super::*;
+use crate::test_fixtures::*;
+
+fn ground<'a>(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> GroundModel<'a> {
+    let symbol_table = DomainSemanticAnalyzer::new(domain).verify_domain().unwrap();
+    Grounder::new(domain, problem, &symbol_table).ground()
+}
+
+fn names(model: &GroundModel) -> Vec<String> {
+    model.facts.iter().map(|fact| fact.to_string()).collect()
+}
+
+#[test]
+pub fn grounding_test() {
+    let problem = delivery_problem(
+        "p1 p2 - package a b c - location",
+        ":ordered-subtasks (and (task0 (deliver p1 b)) (task1 (mark p1 b)))",
+        "(at p1 a) (at p2 a) (road a b) (road b c)",
+        "(delivered p1)",
+    );
+    with_delivery(&problem, |d, p| {
+        let model = ground(d, p);
+        // roads never change, and p2 is never part of a task
+        assert_eq!(names(&model), vec!["(at p1 a)", "(at p1 b)", "(at p1 c)", "(delivered p1)"]);
+        assert_eq!(model.init_state, vec![0]);
+        let tasks: Vec<String> = model.tasks.iter().map(|task| task.to_string()).collect();
+        assert_eq!(
+            tasks,
+            vec!["(move p1 a b)", "(move p1 b c)", "(mark p1 b)", "(deliver p1 b)", "(__top)"]
+        );
+        assert!(model.tasks[0].is_primitive && !model.tasks[3].is_primitive);
+        assert_eq!(model.top, Some(4));
+        // the static road of the precondition is compiled away
+        assert_eq!(
+            model.actions[0].preconditions,
+            vec![Literal { fact: 0, is_positive: true }]
+        );
+        let effects: Vec<Literal> = model.actions[0].effects.iter().map(|e| e.effect).collect();
+        assert_eq!(
+            effects,
+            vec![Literal { fact: 0, is_positive: false }, Literal { fact: 1, is_positive: true }]
+        );
+        // m_stay is dropped because its constraints contradict each other
+        let methods: Vec<(&str, Vec<&str>)> = model
+            .methods
+            .iter()
+            .map(|method| (method.name, method.arguments.clone()))
+            .collect();
+        assert_eq!(
+            methods,
+            vec![
+                ("m_direct", vec!["p1", "a", "b"]),
+                ("m_via", vec!["p1", "a", "b", "b"]),
+                ("m_via", vec!["p1", "b", "c", "b"]),
+                ("m_already", vec!["p1", "b"]),
+                ("__top_method", vec![])
+            ]
+        );
+        assert_eq!(model.methods[1].subtasks, vec![0, 3]);
+        assert_eq!(model.methods[1].orderings, vec![(0, 1)]);
+        assert_eq!(model.methods[4].subtasks, vec![3, 2]);
+        assert_eq!(model.methods_of(3).len(), 4);
+        assert_eq!(model.goal, vec![vec![Literal { fact: 3, is_positive: true }]]);
+
+        let statistics = model.statistics;
+        assert_eq!(
+            statistics.instantiations,
+            StageStatistics { facts: 17, tasks: 43, actions: 24, methods: 109 }
+        );
+        // methods are only instantiated for the task of the initial task network
+        assert_eq!(
+            statistics.relaxed_reachable,
+            StageStatistics { facts: 10, tasks: 12, actions: 10, methods: 5 }
+        );
+        assert_eq!(
+            statistics.tdg_reachable,
+            StageStatistics { facts: 4, tasks: 5, actions: 3, methods: 5 }
+        );
+        assert_eq!(
+            statistics.pruned_by_tdg_reachability(),
+            StageStatistics { facts: 6, tasks: 7, actions: 7, methods: 0 }
+        );
+    })
+}
+
+#[test]
+pub fn formula_grounding_test() {
+    let domain = "(define (domain lights)
+        (:types room - object hall - room)
+        (:predicates (on ?r - room) (broken ?r - room) (open ?r - room))
+        (:task switch :parameters ())
+        (:method m_switch
+         :parameters (?r - hall)
+         :task (switch)
+         :subtasks (and (toggle ?r) (reset))
+        )
+        (:action toggle
+         :parameters (?r - room)
+         :precondition (or (open ?r) (not (broken ?r)))
+         :effect (on ?r)
+        )
+        (:action reset
+         :parameters ()
+         :precondition (exists (?r - room) (on ?r))
+         :effect (forall (?r - room) (when (on ?r) (not (on ?r))))
+        )
+    )"
+    .to_string()
+    .into_bytes();
+    let problem = "(define (problem p) (:domain lights)
+        (:objects kitchen - room lobby - hall)
+        (:htn :parameters () :subtasks (and (task0 (switch))))
+        (:init (open lobby))
+        (:goal (not (on lobby)))
+    )"
+    .to_string()
+    .into_bytes();
+    let (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) = (parse(&domain), parse(&problem)) else {
+        panic!("wrong definitions")
+    };
+    let model = ground(&d, &p);
+    // only the hall is switched, and nothing is ever broken or closed
+    assert_eq!(names(&model), vec!["(on lobby)"]);
+    let tasks: Vec<String> = model.tasks.iter().map(|task| task.to_string()).collect();
+    assert_eq!(tasks, vec!["(toggle lobby)", "(reset)", "(switch)", "(__top)"]);
+    // each disjunct of the preconditions is an action of its own, which are simplified to always hold
+    assert_eq!(model.actions_of(0).len(), 2);
+    assert!(model.actions_of(0).iter().all(|action| action.preconditions.is_empty()));
+    let reset = model.actions_of(1);
+    assert_eq!(reset.len(), 1);
+    assert_eq!(reset[0].preconditions, vec![Literal { fact: 0, is_positive: true }]);
+    assert_eq!(
+        reset[0].effects,
+        vec![ConditionalEffect {
+            conditions: vec![Literal { fact: 0, is_positive: true }],
+            effect: Literal { fact: 0, is_positive: false },
+        }]
+    );
+    assert_eq!(model.goal, vec![vec![Literal { fact: 0, is_positive: false }]]);
+}
+
+#[test]
+pub fn ipc_grounding_test() {
+    let domain = std::fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = std::fs::read("tests/ipc/Transport/pfile01.hddl").unwrap();
+    let (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) = (parse(&domain), parse(&problem)) else {
+        panic!("wrong definitions")
+    };
+    let model = ground(&d, &p);
+    let statistics = model.statistics;
+    for (before, after) in [
+        (statistics.instantiations, statistics.relaxed_reachable),
+        (statistics.relaxed_reachable, statistics.tdg_reachable),
+    ] {
+        assert!(before.actions >= after.actions);
+        assert!(before.methods >= after.methods);
+        assert!(before.tasks >= after.tasks);
+    }
+    assert!(model.top.is_some());
+    assert!(!model.actions.is_empty());
+    assert_eq!(model.goal.len(), 1);
+    for method in model.methods.iter() {
+        assert!(!model.tasks[method.task].is_primitive);
+        assert!(method.subtasks.iter().all(|subtask| *subtask < model.tasks.len()));
+    }
+    for action in model.actions.iter() {
+        assert!(model.tasks[action.task].is_primitive);
+    }
+}
@@ -0,0 +1,6 @@
+mod grounding_tests;
+mod panda_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
+use crate::semantic_analyzer::DomainSemanticAnalyzer;
@@ -0,0 +1,114 @@
+use super::*;
+
+fn panda(domain: &str, problem: &str) -> String {
+    let domain = domain.to_string().into_bytes();
+    let problem = problem.to_string().into_bytes();
+    let (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) = (
+        Parser::new(LexicalAnalyzer::new(&domain)).parse().unwrap(),
+        Parser::new(LexicalAnalyzer::new(&problem)).parse().unwrap(),
+    ) else {
+        panic!("wrong definitions")
+    };
+    let symbol_table = DomainSemanticAnalyzer::new(&d).verify_domain().unwrap();
+    Grounder::new(&d, &p, &symbol_table).ground().to_panda()
+}
+
+// the lines of the given section of the output
+fn section<'a>(output: &'a str, name: &str) -> Vec<&'a str> {
+    output
+        .split(&format!(";; {}\n", name))
+        .nth(1)
+        .unwrap()
+        .lines()
+        .take_while(|line| !line.is_empty())
+        .collect()
+}
+
+const DOMAIN: &str = "(define (domain door)
+    (:predicates (open) (locked) (inside))
+    (:task enter :parameters ())
+    (:method m_enter
+     :parameters ()
+     :task (enter)
+     :precondition (not (locked))
+     :ordered-subtasks (and (t1 (push)) (t2 (walk)))
+    )
+    (:action push
+     :parameters ()
+     :precondition (not (open))
+     :effect (open)
+    )
+    (:action walk
+     :parameters ()
+     :precondition (open)
+     :effect (and (inside) (when (inside) (not (open))))
+    )
+)";
+
+#[test]
+pub fn panda_test() {
+    let problem = "(define (problem p) (:domain door)
+        (:htn :parameters () :subtasks (and (task0 (enter))))
+        (:init)
+        (:goal (inside))
+    )";
+    let output = panda(DOMAIN, problem);
+    // nothing locks the door, so the precondition of the method is compiled away
+    assert_eq!(section(&output, "#state features"), vec!["3", "+open[]", "+inside[]", "-open[]"]);
+    assert_eq!(section(&output, "Mutex Groups"), vec!["3", "0 0 +open[]", "1 1 +inside[]", "2 2 -open[]"]);
+    // the complement of (open) is kept up to date, including by the conditional effect
+    assert_eq!(
+        section(&output, "Actions"),
+        vec!["2", "1", "2 -1", "0 0 -1", "0 2 -1", "1", "0 -1", "0 1 1 1 2 -1", "1 1 0 -1"]
+    );
+    assert_eq!(section(&output, "initial state"), vec!["2 -1"]);
+    assert_eq!(section(&output, "goal"), vec!["1 -1"]);
+    assert_eq!(
+        section(&output, "tasks (primitive and abstract)"),
+        vec!["4", "0 push[]", "0 walk[]", "1 enter[]", "1 __top[]"]
+    );
+    assert_eq!(section(&output, "initial abstract task"), vec!["3"]);
+    assert_eq!(
+        section(&output, "methods"),
+        vec!["2", "m_enter[]", "2", "0 1 -1", "0 1 -1", "__top_method[]", "3", "2 -1", "-1"]
+    );
+}
+
+#[test]
+pub fn panda_compilation_test() {
+    let problem = "(define (problem p) (:domain door)
+        (:htn :parameters () :subtasks (and (task0 (enter))))
+        (:init (locked))
+        (:goal (or (inside) (open)))
+    )";
+    let output = panda(DOMAIN, problem);
+    // the door is unlocked by no action, so the method can not be applied
+    assert_eq!(section(&output, "methods"), vec!["0"]);
+
+    let domain = DOMAIN.replace(
+        "(:action push",
+        "(:method m_unlock :parameters () :task (enter) :ordered-subtasks (and (t1 (unlock)) (t2 (enter))))
+        (:action unlock :parameters () :effect (not (locked)))
+        (:action push",
+    );
+    let output = panda(&domain, problem);
+    assert_eq!(section(&output, "goal"), vec!["-1"]);
+    assert_eq!(
+        section(&output, "tasks (primitive and abstract)"),
+        vec![
+            "8",
+            "0 unlock[]",
+            "0 push[]",
+            "0 walk[]",
+            "0 __method_precondition_m_enter[]",
+            "0 __goal_0[]",
+            "0 __goal_1[]",
+            "1 enter[]",
+            "1 __top[]",
+        ]
+    );
+    // the method starts with checking its precondition, and the initial task network ends with checking the goal
+    let methods = section(&output, "methods");
+    assert_eq!(methods[0], "4");
+    assert!(methods.ends_with(&["__top_method_1[]", "7", "6 5 -1", "0 1 -1"]));
+}
@@ -206,6 +206,31 @@ mod lexer_test {
         }
     }
 
+    #[test]
+    pub fn requirement_recognition_test() {
+        let program = String::from(
+            ":strips :adl :conditional-effects :existential-preconditions\n
+            :quantified-preconditions :disjunctive-preconditions :action-costs\n"
+        ).into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let expected = [
+            RequirementType::STRIPS,
+            RequirementType::ADL,
+            RequirementType::ConditionalEffects,
+            RequirementType::ExistentialPreconditions,
+            RequirementType::QuantifiedPreconditions,
+            RequirementType::DisjunctivePreconditions,
+            RequirementType::ActionCosts,
+        ];
+        for requirement in expected {
+            match lexer.get_token() {
+                Ok(Token::Requirement(req)) => assert_eq!(req, requirement),
+                _ => panic!("wrong token")
+            }
+        }
+        assert!(RequirementType::ADL.expand().contains(&RequirementType::UniversalPreconditions));
+    }
+
     #[test]
     pub fn identifier_recognition_test() {
         let program = String::from(
@@ -260,4 +285,27 @@ mod lexer_test {
             assert_eq!(peek.unwrap(), actual.unwrap());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn token_position_test() {
+        let program = String::from("(define ; comment (\n\t?x :action)").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let expected = [(1, 1, 0, 1), (1, 2, 1, 7), (2, 2, 21, 23), (2, 5, 24, 31), (2, 12, 31, 32)];
+        for (line, column, start, end) in expected {
+            lexer.get_token().unwrap();
+            let position = lexer.get_last_token_position();
+            assert_eq!(position.line, line);
+            assert_eq!(position.column, column);
+            assert_eq!(position.start, start);
+            assert_eq!(position.end, end);
+        }
+        // peeking does not move the position of later tokens
+        let program = String::from("a\n b").into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        lexer.get_token().unwrap();
+        lexer.lookahead().unwrap();
+        lexer.get_token().unwrap();
+        let position = lexer.get_last_token_position();
+        assert_eq!((position.line, position.column, position.start, position.end), (2, 2, 3, 4));
+    }
+}
@@ -1,6 +1,16 @@
 use std::fmt;
 
-#[derive(Debug, Clone, Copy)]
+// location of a lexeme, where program[start..end] is the lexeme itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct TokenPosition {
     pub line: u32,
-}
\ No newline at end of file
+    pub column: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl fmt::Display for TokenPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
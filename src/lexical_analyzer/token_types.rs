@@ -79,13 +79,64 @@ impl fmt::Display for OperationType {
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum RequirementType {
+    // HDDL
     MethodPreconditions,
     Hierarchy,
+    // PDDL
     TypedObjects,
     NegativePreconditions,
+    DisjunctivePreconditions,
+    ExistentialPreconditions,
     UniversalPreconditions,
+    QuantifiedPreconditions,
+    ConditionalEffects,
     Equality,
-    STRIPS
+    STRIPS,
+    ADL,
+    Fluents,
+    NumericFluents,
+    ObjectFluents,
+    ActionCosts,
+    DurativeActions,
+    DurationInequalities,
+    ContinuousEffects,
+    DerivedPredicates,
+    TimedInitialLiterals,
+    Preferences,
+    Constraints,
+}
+
+impl RequirementType {
+    // the requirement itself, along with the ones it is a shorthand for
+    pub fn expand(&self) -> Vec<RequirementType> {
+        let mut requirements = vec![*self];
+        match self {
+            RequirementType::QuantifiedPreconditions => {
+                requirements.extend([
+                    RequirementType::ExistentialPreconditions,
+                    RequirementType::UniversalPreconditions,
+                ]);
+            }
+            RequirementType::ADL => {
+                requirements.extend([
+                    RequirementType::STRIPS,
+                    RequirementType::TypedObjects,
+                    RequirementType::NegativePreconditions,
+                    RequirementType::DisjunctivePreconditions,
+                    RequirementType::Equality,
+                    RequirementType::QuantifiedPreconditions,
+                    RequirementType::ExistentialPreconditions,
+                    RequirementType::UniversalPreconditions,
+                    RequirementType::ConditionalEffects,
+                ]);
+            }
+            RequirementType::Fluents => {
+                requirements.extend([RequirementType::NumericFluents, RequirementType::ObjectFluents]);
+            }
+            _ => {}
+        }
+        requirements
+    }
 }
 
 impl fmt::Display for RequirementType {
@@ -95,9 +146,25 @@ impl fmt::Display for RequirementType {
             RequirementType::Hierarchy => ":hierarchy",
             RequirementType::TypedObjects => ":typing",
             RequirementType::NegativePreconditions => ":negative-preconditions",
+            RequirementType::DisjunctivePreconditions => ":disjunctive-preconditions",
+            RequirementType::ExistentialPreconditions => ":existential-preconditions",
             RequirementType::UniversalPreconditions => ":universal-preconditions",
+            RequirementType::QuantifiedPreconditions => ":quantified-preconditions",
+            RequirementType::ConditionalEffects => ":conditional-effects",
             RequirementType::Equality => ":equality",
             RequirementType::STRIPS => ":strips",
+            RequirementType::ADL => ":adl",
+            RequirementType::Fluents => ":fluents",
+            RequirementType::NumericFluents => ":numeric-fluents",
+            RequirementType::ObjectFluents => ":object-fluents",
+            RequirementType::ActionCosts => ":action-costs",
+            RequirementType::DurativeActions => ":durative-actions",
+            RequirementType::DurationInequalities => ":duration-inequalities",
+            RequirementType::ContinuousEffects => ":continuous-effects",
+            RequirementType::DerivedPredicates => ":derived-predicates",
+            RequirementType::TimedInitialLiterals => ":timed-initial-literals",
+            RequirementType::Preferences => ":preferences",
+            RequirementType::Constraints => ":constraints",
         };
         write!(f, "{}", requirement)
     }
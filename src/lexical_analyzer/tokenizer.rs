@@ -1,4 +1,3 @@
-
 use std::{cell::Cell, str::from_utf8};
 
 use super::*;
@@ -6,6 +5,9 @@ use super::*;
 pub struct LexicalAnalyzer<'a> {
     program: &'a Vec<u8>,
     cursor: Cell<usize>,
+    // line of the cursor, and the offset at which that line starts
+    line: Cell<u32>,
+    line_start: Cell<usize>,
     last_token_pos: Cell<TokenPosition>,
 }
 
@@ -14,62 +16,78 @@ impl <'a> LexicalAnalyzer <'a> {
         LexicalAnalyzer {
             program,
             cursor: Cell::new(0),
+            line: Cell::new(1),
+            line_start: Cell::new(0),
             last_token_pos: Cell::new(TokenPosition {
                 line: 1,
+                column: 1,
+                start: 0,
+                end: 0,
             }),
         }
     }
     // get the next token without advancing the cursor
-    pub fn lookahead(&self) -> Result<Token, LexicalError> {
-        return self.parse(true);
+    pub fn lookahead(&self) -> Result<Token<'a>, LexicalError> {
+        let cursor = self.cursor.get();
+        let line = self.line.get();
+        let line_start = self.line_start.get();
+        let token = self.parse();
+        self.cursor.set(cursor);
+        self.line.set(line);
+        self.line_start.set(line_start);
+        return token;
     }
 
     pub fn get_last_token_position(&self) -> TokenPosition {
         self.last_token_pos.get()
     }
 
-    pub fn get_token(&self) -> Result<Token, LexicalError> {
-        return self.parse(false);
+    pub fn get_token(&self) -> Result<Token<'a>, LexicalError> {
+        return self.parse();
+    }
+
+    // position of the lexeme in program[start..end]
+    fn position(&self, start: usize, end: usize) -> TokenPosition {
+        TokenPosition {
+            line: self.line.get(),
+            column: (start - self.line_start.get() + 1) as u32,
+            start,
+            end,
+        }
     }
 
-    fn parse(&self, peek: bool) -> Result<Token, LexicalError> {
+    fn parse(&self) -> Result<Token<'a>, LexicalError> {
         self.skip_whitespaces();
+        let start = self.cursor.get();
+        self.last_token_pos.set(self.position(start, start));
+        let token = self.parse_token()?;
+        self.last_token_pos.set(self.position(start, self.cursor.get()));
+        Ok(token)
+    }
+
+    fn parse_token(&self) -> Result<Token<'a>, LexicalError> {
         if self.cursor.get() == self.program.len() {
             return Ok(Token::EOF);
         }
         if let Some(char) = self.peek_next_char() {
-            if !peek {
-                self.cursor.set(self.cursor.get() + 1);
-            }
+            self.cursor.set(self.cursor.get() + 1);
             match char {
                 // Punctuations
                 '-' => Ok(Token::Punctuator(PunctuationType::Dash)),
                 '(' => Ok(Token::Punctuator(PunctuationType::LParentheses)),
                 ')' => Ok(Token::Punctuator(PunctuationType::RParentheses)),
                 // Ordering Relations
-                p @ ('<' | '>' | '=') => Ok(Token::Operator(self.ordering_type(&p, peek))),
+                p @ ('<' | '>' | '=') => Ok(Token::Operator(self.ordering_type(&p))),
                 // Variables
                 '?' => {
-                    let mut init_cur_pos = self.cursor.get();
-                    if peek {
-                        init_cur_pos += 1;
-                    }
-                    let (var_name, new_cur_pos) = self.peek_lexeme(init_cur_pos)?;
-                    if !peek {
-                        self.cursor.set(new_cur_pos);
-                    }
+                    let (var_name, new_cur_pos) = self.peek_lexeme(self.cursor.get())?;
+                    self.cursor.set(new_cur_pos);
                     Ok(Token::Identifier(var_name))
                 }
                 // Keywords (Note that 2 keywords, namely "domain" and "problem", can start without ':' as well)
                 ':' => {
-                    let mut init_cur_pos = self.cursor.get();
-                    if peek {
-                        init_cur_pos += 1;
-                    }
-                    let (lexeme, new_cur_pos) = self.peek_lexeme(init_cur_pos)?;
-                    if !peek {
-                        self.cursor.set(new_cur_pos);
-                    }
+                    let (lexeme, new_cur_pos) = self.peek_lexeme(self.cursor.get())?;
+                    self.cursor.set(new_cur_pos);
                     match lexeme {
                         // Requirements
                         "negative-preconditions" => Ok(Token::Requirement(
@@ -83,6 +101,22 @@ impl <'a> LexicalAnalyzer <'a> {
                         )),
                         "typing" => Ok(Token::Requirement(RequirementType::TypedObjects)),
                         "universal-preconditions" => Ok(Token::Requirement(RequirementType::UniversalPreconditions)),
+                        "disjunctive-preconditions" => Ok(Token::Requirement(RequirementType::DisjunctivePreconditions)),
+                        "existential-preconditions" => Ok(Token::Requirement(RequirementType::ExistentialPreconditions)),
+                        "quantified-preconditions" => Ok(Token::Requirement(RequirementType::QuantifiedPreconditions)),
+                        "conditional-effects" => Ok(Token::Requirement(RequirementType::ConditionalEffects)),
+                        "adl" => Ok(Token::Requirement(RequirementType::ADL)),
+                        "fluents" => Ok(Token::Requirement(RequirementType::Fluents)),
+                        "numeric-fluents" => Ok(Token::Requirement(RequirementType::NumericFluents)),
+                        "object-fluents" => Ok(Token::Requirement(RequirementType::ObjectFluents)),
+                        "action-costs" => Ok(Token::Requirement(RequirementType::ActionCosts)),
+                        "durative-actions" => Ok(Token::Requirement(RequirementType::DurativeActions)),
+                        "duration-inequalities" => Ok(Token::Requirement(RequirementType::DurationInequalities)),
+                        "continuous-effects" => Ok(Token::Requirement(RequirementType::ContinuousEffects)),
+                        "derived-predicates" => Ok(Token::Requirement(RequirementType::DerivedPredicates)),
+                        "timed-initial-literals" => Ok(Token::Requirement(RequirementType::TimedInitialLiterals)),
+                        "preferences" => Ok(Token::Requirement(RequirementType::Preferences)),
+                        // note that ":constraints" is tokenized as a keyword (see the requirement parser)
                         // Keywords
                         "requirements" => Ok(Token::Keyword(KeywordName::Requirements)),
                         "objects" => Ok(Token::Keyword(KeywordName::Objects)),
@@ -109,29 +143,14 @@ impl <'a> LexicalAnalyzer <'a> {
                         _ => Err(LexicalError {
                             error_type: LexicalErrorType::InvalidKeyword,
                             lexeme: lexeme.to_string(),
-                            position: self.last_token_pos.get()
+                            position: self.position_until(new_cur_pos)
                         }),
                     }
                 }
-                // Comment
-                ';' => {
-                        let mut current = self.program[self.cursor.get()] as char;
-                        while current != '\n' {
-                            self.cursor.set(self.cursor.get() + 1);
-                            current = self.program[self.cursor.get()] as char;
-                        }
-                        return self.parse(peek);
-                }
                 // Other
                 _ => {
-                    let mut init_cur_pos = self.cursor.get() - 1;
-                    if peek {
-                        init_cur_pos += 1;
-                    }
-                    let (lexeme, new_cur_pos) = self.peek_lexeme(init_cur_pos)?;
-                    if !peek {
-                        self.cursor.set(new_cur_pos);
-                    }
+                    let (lexeme, new_cur_pos) = self.peek_lexeme(self.cursor.get() - 1)?;
+                    self.cursor.set(new_cur_pos);
                     match lexeme {
                         // Remaining Keywords
                         "define" => return Ok(Token::Keyword(KeywordName::Define)),
@@ -149,7 +168,7 @@ impl <'a> LexicalAnalyzer <'a> {
                                         Err(LexicalError {
                                             error_type: LexicalErrorType::InvalidIdentifier,
                                             lexeme: lexeme.to_string(),
-                                            position: self.last_token_pos.get()
+                                            position: self.position_until(new_cur_pos)
                                         })
                                     }
                                 }
@@ -163,10 +182,17 @@ impl <'a> LexicalAnalyzer <'a> {
         }
     }
 
+    // position of the current token, ending at the given offset
+    fn position_until(&self, end: usize) -> TokenPosition {
+        TokenPosition {
+            end,
+            ..self.last_token_pos.get()
+        }
+    }
+
     // get next lexeme and new cursor position (to commit peek)
-    fn peek_lexeme(&self, init_cur_pos: usize) -> Result<(&str, usize), LexicalError> {
+    fn peek_lexeme(&self, init_cur_pos: usize) -> Result<(&'a str, usize), LexicalError> {
         let mut cursor_pos = init_cur_pos;
-        let mut next_ch = self.program[cursor_pos] as char;
         let mut is_invalid = false;
         let mut is_valid_character = |c| match c {
             '_' | '-' => true,
@@ -182,19 +208,14 @@ impl <'a> LexicalAnalyzer <'a> {
                 }
             }
         };
-        while is_valid_character(next_ch) {
-            if cursor_pos < self.program.len() - 1 {
-                cursor_pos += 1;
-                next_ch = self.program[cursor_pos] as char;
-            } else {
-                break;
-            }
+        while cursor_pos < self.program.len() && is_valid_character(self.program[cursor_pos] as char) {
+            cursor_pos += 1;
         }
         if is_invalid {
             return Err(LexicalError {
                 error_type: LexicalErrorType::InvalidIdentifier,
                 lexeme: from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap().to_string(),
-                position: self.last_token_pos.get()
+                position: self.position_until(cursor_pos)
             })
         } else {
             return Ok((from_utf8(&self.program[init_cur_pos..cursor_pos]).unwrap(), cursor_pos))
@@ -209,15 +230,19 @@ impl <'a> LexicalAnalyzer <'a> {
         Some(current)
     }
 
+    // skips whitespaces and comments
     fn skip_whitespaces(&self) {
+        let mut is_comment = false;
         while self.cursor.get() != self.program.len() {
             let current = self.program[self.cursor.get()] as char;
-            if !LexicalAnalyzer::is_whitespace(&current) {
-                break;
+            if current == ';' {
+                is_comment = true;
             } else if current == '\n' {
-                self.last_token_pos.set(TokenPosition { 
-                    line: self.last_token_pos.get().line + 1 
-                });
+                is_comment = false;
+                self.line.set(self.line.get() + 1);
+                self.line_start.set(self.cursor.get() + 1);
+            } else if !is_comment && !LexicalAnalyzer::is_whitespace(&current) {
+                break;
             }
             self.cursor.set(self.cursor.get() + 1);
         }
@@ -232,18 +257,17 @@ impl <'a> LexicalAnalyzer <'a> {
             "forall" => Some(OperationType::ForAll),
             "exists" => Some(OperationType::Exists),
             "imply" => Some(OperationType::Implication),
+            "when" => Some(OperationType::Implication),
             _ => None,
         }
     }
 
-    fn ordering_type(&self, c: &char, peek: bool) -> OperationType {
+    fn ordering_type(&self, c: &char) -> OperationType {
         match c {
             '<' => {
                 match self.peek_next_char() {
                     Some('=') => {
-                        if !peek {
-                            self.cursor.set(self.cursor.get() + 1);
-                        }
+                        self.cursor.set(self.cursor.get() + 1);
                         OperationType::LessThanOrEqual
                     }
                     _ => OperationType::LessThan,
@@ -252,9 +276,7 @@ impl <'a> LexicalAnalyzer <'a> {
             '>' => {
                 match self.peek_next_char() {
                     Some('=') => {
-                        if !peek {
-                            self.cursor.set(self.cursor.get() + 1);
-                        }
+                        self.cursor.set(self.cursor.get() + 1);
                         OperationType::GreaterThanOrEqual
                     }
                     _ => OperationType::GreaterThan,
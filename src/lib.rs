@@ -1,74 +1,357 @@
+//! Parser and semantic analyzer for HDDL domains and problems.
+//!
+//! Besides the ready-made checks of [`HDDLAnalyzer::verify`] and
+//! [`HDDLAnalyzer::get_metadata`], the parsed syntax trees and the results of
+//! the analysis are available to downstream tools:
+//!
+//! ```
+//! use hddl_analyzer::HDDLAnalyzer;
+//!
+//! let domain = std::fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+//! let domain = HDDLAnalyzer::parse_domain(&domain).unwrap();
+//! let analysis = HDDLAnalyzer::analyze(&domain, None).unwrap();
+//! assert!(analysis.symbol_table.actions.iter().any(|action| action.name == "drive"));
+//! assert!(analysis.tdg.methods_of("deliver").contains(&"m_deliver_ordering_0"));
+//! ```
+//!
+//! Syntax trees borrow names from the program they are parsed from, so the
+//! program must outlive them.
+
 mod lexical_analyzer;
 mod syntactic_analyzer;
 mod semantic_analyzer;
 mod output;
+mod plan_verifier;
+mod pretty_printer;
+mod grounder;
+mod planner;
+mod graph_export;
+mod sat;
+#[cfg(test)]
+mod test_fixtures;
 
-use crate::lexical_analyzer::TokenPosition;
+pub use crate::lexical_analyzer::{TokenPosition, RequirementType};
 use lexical_analyzer::LexicalAnalyzer;
-use output::MetaData;
-pub use output::{ParsingError, SemanticErrorType, LexicalErrorType, SyntacticError, WarningType};
+pub use output::{ParsingError, SemanticErrorType, LexicalErrorType, SyntacticError, PlanError, PlanErrorType, WarningType, WarningInfo, RefinementInfo, BlockingCause, BlockedMethod, BlockedTask, ParameterInfo, EffectInfo, FormulaInfo, EffectRedundancy, Diagnostics, Label, Snippet, MetaData, RecursionType, RecursiveComponent, HierarchyClass, HierarchyClassification, TaskBounds};
 use semantic_analyzer::*;
-use syntactic_analyzer::AbstractSyntaxTree;
+pub use semantic_analyzer::{SymbolIndex, SymbolKind, SymbolReference, SourceFile};
+pub use semantic_analyzer::{Analysis, SymbolTable, TDG, ReachableSet, Landmarks};
+pub use grounder::{
+    Grounder, GroundModel, GroundTask, GroundAction, GroundMethod, Literal, ConditionalEffect,
+    GroundingStatistics, StageStatistics,
+};
+pub use plan_verifier::{GroundAtom, State, Simulator, Explorer, OpenTask, Plan, PlanStep};
+pub use planner::{Planner, SearchResult};
+pub use graph_export::{Graph, Node, NodeKind, Edge};
+pub use sat::{SatSolver, Dpll, ExternalSolver, to_dimacs};
+pub use syntactic_analyzer::{
+    AbstractSyntaxTree, DomainAST, ProblemAST, Formula, Predicate, Symbol, Task, Action, Method,
+    HTN, Subtask, TaskOrdering, Constraint, InitialTaskNetwork,
+};
+
+/// Options of the checks of [`HDDLAnalyzer::verify_with`] and [`HDDLAnalyzer::verify_all_with`].
+#[derive(Default)]
+pub struct VerifyOptions {
+    /// Also report modeling smells (see [`WarningType::is_smell`]), which are
+    /// far more common than actual flaws.
+    pub smells: bool,
+    /// The SAT solver that contradictory preconditions and effects are
+    /// detected with, instead of the built-in [`Dpll`].
+    pub sat_solver: Option<Box<dyn SatSolver>>,
+}
+
+impl VerifyOptions {
+    fn solver(&self) -> &dyn SatSolver {
+        self.sat_solver.as_deref().unwrap_or(&Dpll)
+    }
 
+    fn select(&self, mut warnings: Vec<WarningType>) -> Vec<WarningType> {
+        if !self.smells {
+            warnings.retain(|warning| !warning.is_smell());
+        }
+        warnings
+    }
+}
+
+/// Entry points for checking and analyzing HDDL domains and problems.
 pub struct HDDLAnalyzer {}
 
 impl HDDLAnalyzer {
-    pub fn verify(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<Vec<output::WarningType>, output::ParsingError> {
+    /// Parses a domain, without checking its semantics.
+    pub fn parse_domain<'a>(domain: &'a Vec<u8>) -> Result<DomainAST<'a>, ParsingError> {
+        let lexer = LexicalAnalyzer::new(domain);
+        let parser = syntactic_analyzer::Parser::new(lexer);
+        match parser.parse()? {
+            AbstractSyntaxTree::Domain(d) => Ok(d),
+            AbstractSyntaxTree::Problem(_) => Err(wrong_definition("domain", "problem")),
+        }
+    }
+
+    /// Parses a problem, without checking its semantics.
+    pub fn parse_problem<'a>(problem: &'a Vec<u8>) -> Result<ProblemAST<'a>, ParsingError> {
+        let lexer = LexicalAnalyzer::new(problem);
+        let parser = syntactic_analyzer::Parser::new(lexer);
+        match parser.parse()? {
+            AbstractSyntaxTree::Problem(p) => Ok(p),
+            AbstractSyntaxTree::Domain(_) => Err(wrong_definition("problem", "domain")),
+        }
+    }
+
+    /// Checks the semantics of a parsed domain (and problem), and returns its
+    /// symbol table and task decomposition graph. Stops at the first error.
+    pub fn analyze<'a>(
+        domain: &'a DomainAST<'a>,
+        problem: Option<&'a ProblemAST<'a>>,
+    ) -> Result<Analysis<'a>, ParsingError> {
+        let domain_semantic_verifier = DomainSemanticAnalyzer::new(domain);
+        let symbol_table = domain_semantic_verifier.verify_domain()?;
+        let warnings = match problem {
+            Some(p) => {
+                let problem_semantic_verifier = ProblemSemanticAnalyzer::new(p, domain, symbol_table.clone());
+                problem_semantic_verifier.verify_problem()?
+            }
+            None => symbol_table.warnings.clone(),
+        };
+        Ok(Analysis {
+            symbol_table,
+            tdg: TDG::new(domain)?,
+            warnings,
+        })
+    }
+
+    /// Checks a domain and problem, and instantiates them into a ground model.
+    pub fn ground<'a>(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> Result<GroundModel<'a>, ParsingError> {
+        let analysis = HDDLAnalyzer::analyze(domain, Some(problem))?;
+        Ok(Grounder::new(domain, problem, &analysis.symbol_table).ground())
+    }
+
+    /// Checks a domain and problem, and returns the compound tasks, methods and
+    /// actions that every decomposition of the initial task network uses, or
+    /// none if the initial task network can not be refined.
+    pub fn landmarks<'a>(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> Result<Option<Landmarks<'a>>, ParsingError> {
+        let analysis = HDDLAnalyzer::analyze(domain, Some(problem))?;
+        let tasks: Vec<&str> = match &problem.init_tn {
+            Some(init_tn) => init_tn.tn.subtasks.iter().map(|subtask| subtask.task.name).collect(),
+            None => vec![],
+        };
+        Ok(analysis.tdg.landmarks(&tasks)?)
+    }
+
+    /// Checks a domain (and problem), and returns their warnings. Stops at the first error.
+    pub fn verify(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<Vec<WarningType>, ParsingError> {
+        HDDLAnalyzer::verify_with(domain, problem, &VerifyOptions::default())
+    }
+
+    /// Same as [`HDDLAnalyzer::verify`], with the given options.
+    pub fn verify_with(
+        domain: &Vec<u8>,
+        problem: Option<&Vec<u8>>,
+        options: &VerifyOptions,
+    ) -> Result<Vec<WarningType>, ParsingError> {
+        let d = HDDLAnalyzer::parse_domain(domain)?;
+        let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d).with_solver(options.solver());
+        let symbol_table = domain_semantic_verifier.verify_domain()?;
+        let warnings = match problem {
+            Some(p) => {
+                let p_ast = HDDLAnalyzer::parse_problem(p)?;
+                let problem_semantic_verifier = ProblemSemanticAnalyzer::new(&p_ast, &d, symbol_table);
+                problem_semantic_verifier.verify_problem()?
+            }
+            None => symbol_table.warnings,
+        };
+        Ok(options.select(warnings))
+    }
+
+    /// Checks a domain (and problem), and reports all of their errors and
+    /// warnings instead of stopping at the first error.
+    pub fn verify_all(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Diagnostics {
+        HDDLAnalyzer::verify_all_with(domain, problem, &VerifyOptions::default())
+    }
+
+    /// Same as [`HDDLAnalyzer::verify_all`], with the given options.
+    pub fn verify_all_with(domain: &Vec<u8>, problem: Option<&Vec<u8>>, options: &VerifyOptions) -> Diagnostics {
+        let mut diagnostics = HDDLAnalyzer::diagnose(domain, problem, options.solver());
+        diagnostics.warnings = options.select(diagnostics.warnings);
+        diagnostics
+    }
+
+    fn diagnose(domain: &Vec<u8>, problem: Option<&Vec<u8>>, solver: &dyn SatSolver) -> Diagnostics {
+        let mut diagnostics = Diagnostics::new();
         let lexer = LexicalAnalyzer::new(&domain);
         let domain_parser = syntactic_analyzer::Parser::new(lexer);
-        let domain_ast = domain_parser.parse()?;
-        if let AbstractSyntaxTree::Domain(d) = domain_ast {
-            let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d);
-            let symbol_table = domain_semantic_verifier.verify_domain()?;
-            match problem {
-                Some(p) => {
-                    let lexer = LexicalAnalyzer::new(p);
-                    let problem_parser = syntactic_analyzer::Parser::new(lexer);
-                    let problem_ast = problem_parser.parse()?;
-                    match problem_ast {
-                        AbstractSyntaxTree::Problem(p_ast) => {
-                            let problem_semantic_verifier = ProblemSemanticAnalyzer::new(
-                                &p_ast,
-                                symbol_table
-                            );
-                            let warnings= problem_semantic_verifier.verify_problem()?;
-                            Ok(warnings)
-
-                        }
-                        _ => {
-                            panic!("expected problem, found domain")
-                        }
+        let domain_ast = match domain_parser.parse_all() {
+            Ok(ast) => ast,
+            Err(errors) => {
+                diagnostics.add_errors(errors);
+                return diagnostics;
+            }
+        };
+        let AbstractSyntaxTree::Domain(d) = domain_ast else {
+            diagnostics.errors.push(wrong_definition("domain", "problem"));
+            return diagnostics;
+        };
+        let domain_semantic_verifier = DomainSemanticAnalyzer::new(&d).with_solver(solver);
+        let (symbol_table, errors) = domain_semantic_verifier.analyze_domain();
+        diagnostics.add_errors(errors);
+        match problem {
+            Some(p) => {
+                let lexer = LexicalAnalyzer::new(p);
+                let problem_parser = syntactic_analyzer::Parser::new(lexer);
+                let problem_ast = match problem_parser.parse_all() {
+                    Ok(ast) => ast,
+                    Err(errors) => {
+                        diagnostics.warnings = symbol_table.warnings;
+                        diagnostics.add_problem_errors(errors);
+                        return diagnostics;
+                    }
+                };
+                match problem_ast {
+                    AbstractSyntaxTree::Problem(p_ast) => {
+                        let problem_semantic_verifier = ProblemSemanticAnalyzer::new(
+                            &p_ast,
+                            &d,
+                            symbol_table
+                        );
+                        let (warnings, errors) = problem_semantic_verifier.analyze_problem();
+                        diagnostics.warnings = warnings;
+                        diagnostics.add_problem_errors(errors);
+                        diagnostics
                     }
-                },
-                None => Ok(
-                    symbol_table.warnings
-                )
+                    AbstractSyntaxTree::Domain(_) => {
+                        diagnostics.warnings = symbol_table.warnings;
+                        diagnostics.problem_errors.push(wrong_definition("problem", "domain"));
+                        diagnostics
+                    }
+                }
+            },
+            None => {
+                diagnostics.warnings = symbol_table.warnings;
+                diagnostics
+            }
+        }
+    }
+
+    /// Indexes the declarations and uses of all named elements of a domain
+    /// (and problem), for editor tooling.
+    pub fn get_symbol_index(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<SymbolIndex, ParsingError> {
+        let d = HDDLAnalyzer::parse_domain(domain)?;
+        match problem {
+            Some(p) => {
+                let p_ast = HDDLAnalyzer::parse_problem(p)?;
+                Ok(SymbolIndex::new(&d, Some(&p_ast)))
             }
-        } else {
-            panic!("expected domain, found problem")
+            None => Ok(SymbolIndex::new(&d, None)),
         }
     }
 
+    /// Prints a domain or a problem as canonical HDDL. Comments are not kept.
+    pub fn format(program: &Vec<u8>) -> Result<String, ParsingError> {
+        let lexer = LexicalAnalyzer::new(program);
+        let parser = syntactic_analyzer::Parser::new(lexer);
+        match parser.parse()? {
+            AbstractSyntaxTree::Domain(d) => Ok(pretty_printer::PrettyPrinter::print_domain(&d)),
+            AbstractSyntaxTree::Problem(p) => Ok(pretty_printer::PrettyPrinter::print_problem(&p)),
+        }
+    }
 
-    pub fn get_metadata(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<MetaData, ParsingError> {
-        let lexer = LexicalAnalyzer::new(&domain);
-        let domain_parser = syntactic_analyzer::Parser::new(lexer);
-        let domain_ast = domain_parser.parse()?;
-        match domain_ast {
-            AbstractSyntaxTree::Domain(d) => {
-                let tdg = TDG::new(&d);
-                let nullables = tdg.compute_nullables();
-                let recursion_type= tdg.get_recursion_type(&nullables);
-                Ok(MetaData {
-                    recursion: recursion_type,
-                    nullables: nullables.iter().map(|x| x.to_string()).collect(),
-                    domain_name: String::new(),
-                    n_actions: d.actions.len() as u32,
-                    n_tasks: d.compound_tasks.len() as u32,
-                    n_methods: d.methods.len() as u32
-                })
+    /// Checks a parsed domain and problem, and then a plan in the IPC HTN
+    /// format against them.
+    pub fn verify_plan<'a>(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>, plan: &[u8]) -> Result<(), ParsingError> {
+        HDDLAnalyzer::analyze(domain, Some(problem))?;
+        let plan = String::from_utf8_lossy(plan);
+        let plan = plan_verifier::Plan::parse(&plan)?;
+        plan_verifier::PlanVerifier::new(domain, problem).verify(&plan)?;
+        Ok(())
+    }
+
+    /// Checks a parsed domain and problem, and executes a sequence of
+    /// primitive actions from the initial state of the problem. Returns the
+    /// facts of the final state.
+    pub fn simulate<'a>(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>, actions: &[u8]) -> Result<Vec<String>, ParsingError> {
+        HDDLAnalyzer::analyze(domain, Some(problem))?;
+        let actions = String::from_utf8_lossy(actions);
+        let steps = plan_verifier::Plan::parse_actions(&actions)?;
+        let mut simulator = plan_verifier::Simulator::new(domain, problem);
+        let mut facts: Vec<String> = simulator.run(&steps)?.atoms.iter().map(|atom| atom.to_string()).collect();
+        facts.sort();
+        Ok(facts)
+    }
+
+    /// Checks a parsed domain and problem, and searches for a plan with at
+    /// most `max_depth` decompositions and actions, expanding at most
+    /// `max_nodes` search nodes.
+    pub fn solve<'a>(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>, max_depth: usize, max_nodes: usize) -> Result<SearchResult, ParsingError> {
+        let model = HDDLAnalyzer::ground(domain, problem)?;
+        Ok(Planner::new(domain, problem, model, max_depth, max_nodes).search())
+    }
+
+    /// Converts each action precondition and effect, and each method
+    /// precondition, of a domain into its textbook CNF. Returns the formulas in
+    /// the DIMACS format (with comments naming the variables), along with names
+    /// such as `action.drive.precondition`.
+    pub fn dump_cnf(domain: &Vec<u8>) -> Result<Vec<(String, String)>, ParsingError> {
+        let d = HDDLAnalyzer::parse_domain(domain)?;
+        let mut formulas = vec![];
+        for action in d.actions.iter() {
+            if let Some(precondition) = &action.preconditions {
+                formulas.push((format!("action.{}.precondition", action.name), precondition));
+            }
+            if let Some(effect) = &action.effects {
+                formulas.push((format!("action.{}.effect", action.name), effect));
             }
-            _ => panic!("expected domain, found problem")
         }
+        for method in d.methods.iter() {
+            if let Some(precondition) = &method.precondition {
+                formulas.push((format!("method.{}.precondition", method.name.name), precondition));
+            }
+        }
+        Ok(formulas
+            .into_iter()
+            .map(|(name, formula)| {
+                let (symbols, clauses) = formula.to_clauses_with_symbols();
+                (name, to_dimacs(symbols.len() as u32, &clauses, &symbols))
+            })
+            .collect())
     }
+
+    /// Checks a domain (and problem), and computes general information about
+    /// the domain, such as its hierarchy classes, recursive components,
+    /// nullable tasks and task bounds. Stops at the first error.
+    pub fn get_metadata(domain: &Vec<u8>, problem: Option<&Vec<u8>>) -> Result<MetaData, ParsingError> {
+        let d = HDDLAnalyzer::parse_domain(domain)?;
+        let p = match problem {
+            Some(problem) => Some(HDDLAnalyzer::parse_problem(problem)?),
+            None => None,
+        };
+        // flaws such as undefined subtasks are reported instead of being analyzed
+        let analysis = HDDLAnalyzer::analyze(&d, p.as_ref())?;
+        let tdg = analysis.tdg;
+        let nullables = tdg.compute_nullables();
+        let recursion_type= tdg.get_recursion_type(&nullables);
+        Ok(MetaData {
+            recursion: recursion_type,
+            hierarchy_class: tdg.hierarchy_class(&nullables),
+            recursive_components: tdg.recursive_components(&nullables),
+            nullables: nullables.iter().map(|x| x.to_string()).collect(),
+            task_bounds: tdg.compute_task_bounds(),
+            domain_name: d.name.clone(),
+            n_actions: d.actions.len() as u32,
+            n_tasks: d.compound_tasks.len() as u32,
+            n_methods: d.methods.len() as u32
+        })
+    }
+}
+
+// a domain was given where a problem was expected, or vice versa
+fn wrong_definition(expected: &str, found: &str) -> ParsingError {
+    ParsingError::Syntactic(SyntacticError {
+        expected: format!("a {} definition", expected),
+        found: format!("a {} definition", found),
+        position: TokenPosition {
+            line: 1,
+            column: 1,
+            start: 0,
+            end: 0,
+        },
+    })
 }
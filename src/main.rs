@@ -1,10 +1,15 @@
 mod cli_args;
 
 use clap::Parser;
-use hddl_analyzer::HDDLAnalyzer;
+use hddl_analyzer::{
+    Diagnostics, DomainAST, Explorer, ExternalSolver, Graph, HDDLAnalyzer, ProblemAST, SatSolver, SearchResult, Snippet,
+    VerifyOptions,
+};
 use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
 
-use cli_args::{CLIArgs, Commands};
+use cli_args::{CLIArgs, Commands, GraphFormat, GraphKind, OutputFormat};
 
 pub fn main() {
     // ANSI escape color codes
@@ -17,66 +22,577 @@ pub fn main() {
     let args = CLIArgs::parse();
     match args.command {
         Commands::Metadata(info) => {
-            let domain = fs::read(info.domain_path);
-            match domain {
-                Ok(domain_content) => match HDDLAnalyzer::get_metadata(&domain_content, None) {
-                    Ok(result) => {
-                        print!("{}", result)
-                    }
-                    Err(error) => {
-                        eprintln!("{}[Error]{} {}", red, reset, error)
+            let paths: Vec<&str> = [Some(info.domain_path.as_str()), info.problem_path.as_deref()].into_iter().flatten().collect();
+            let Some(contents) = read_files(&paths) else {
+                return;
+            };
+            let (domain_content, problem_content) = (&contents[0], contents.get(1));
+            // metadata is only computed for a valid model
+            let diagnostics = verify_first(domain_content, problem_content, &VerifyOptions::default());
+            if diagnostics.has_errors() {
+                match info.format {
+                    OutputFormat::Text => {
+                        let problem = problem_content.zip(info.problem_path.as_deref());
+                        print_text(&diagnostics, (domain_content, &info.domain_path), problem, None);
                     }
+                    format => print_structured(&diagnostics, format, &info.domain_path, info.problem_path.as_deref(), None),
+                }
+                return;
+            }
+            match HDDLAnalyzer::get_metadata(domain_content, problem_content) {
+                Ok(result) => match info.format {
+                    OutputFormat::Text => print!("{}", result),
+                    OutputFormat::Json => println!("{}", result.to_json()),
+                    OutputFormat::Sarif => println!("{}", result.to_sarif()),
                 },
+                Err(error) => eprintln!("{}[Error]{} {}", red, reset, error),
+            }
+        }
+        Commands::Verify(input) => {
+            let domain_content = match fs::read(&input.domain_path) {
+                Ok(content) => content,
                 Err(read_error) => {
-                    eprintln!("{}[Error]{} {}", red, reset, read_error)
+                    eprintln!("{}[Error]{} {}", red, reset, read_error);
+                    return;
+                }
+            };
+            let problem_content = match &input.problem_path {
+                Some(problem_path) => match fs::read(problem_path) {
+                    Ok(content) => Some(content),
+                    Err(read_error) => {
+                        eprintln!("{}[Error]{} {}", red, reset, read_error);
+                        return;
+                    }
+                },
+                None => None,
+            };
+            if let Some(directory) = &input.dump_cnf {
+                let formulas = match HDDLAnalyzer::dump_cnf(&domain_content) {
+                    Ok(formulas) => formulas,
+                    Err(error) => {
+                        eprintln!("{}[Error]{} {}", red, reset, error);
+                        eprint!("{}", Snippet::new(&domain_content, &input.domain_path).render(&error.labels()));
+                        return;
+                    }
+                };
+                if let Err(error) = write_cnf(directory, formulas) {
+                    eprintln!("{}[Error]{} {}", red, reset, error);
+                    return;
                 }
             }
+            let options = VerifyOptions {
+                smells: input.smells,
+                sat_solver: input
+                    .sat_solver
+                    .as_ref()
+                    .map(|path| Box::new(ExternalSolver::new(path)) as Box<dyn SatSolver>),
+            };
+            let diagnostics = if input.all_errors {
+                HDDLAnalyzer::verify_all_with(&domain_content, problem_content.as_ref(), &options)
+            } else {
+                verify_first(&domain_content, problem_content.as_ref(), &options)
+            };
+            if input.format != OutputFormat::Text {
+                print_structured(&diagnostics, input.format, &input.domain_path, input.problem_path.as_deref(), None);
+                return;
+            }
+            let problem = problem_content.as_ref().zip(input.problem_path.as_deref());
+            print_text(&diagnostics, (&domain_content, &input.domain_path), problem, None);
         }
-        Commands::Verify(input) => {
-            let domain = fs::read(input.domain_path);
-            match domain {
-                Ok(domain_content) => match input.problem_path {
-                    Some(problem_path) => {
-                        let problem = fs::read(problem_path);
-                        match problem {
-                            Ok(problem_content) => {
-                                let output = HDDLAnalyzer::verify(&domain_content, Some(&problem_content));
-                                match output {
-                                    Ok(warnings) => {
-                                        for warning in warnings {
-                                            println!("{}[Warning]{} {}", yellow, reset, warning);
-                                        }
-                                        println!("{}[Ok]{}", green, reset);
-                                    }
-                                    Err(parsing_error) => {
-                                        eprintln!("{}[Error]{} {}", red, reset, parsing_error)
-                                    }
-                                }
-                            }
-                            Err(read_error) => {
-                                eprintln!("{}[Error]{} {}", red, reset, read_error)
-                            }
+        Commands::VerifyPlan(input) => {
+            let Some(contents) = read_files(&[&input.domain_path, &input.problem_path, &input.plan_path]) else {
+                return;
+            };
+            let (domain_content, problem_content, plan_content) = (&contents[0], &contents[1], &contents[2]);
+            // the plan is only checked against a valid model
+            let mut diagnostics = verify_first(domain_content, Some(problem_content), &VerifyOptions::default());
+            if !diagnostics.has_errors() {
+                let (domain, problem) = parse_verified(domain_content, problem_content);
+                if let Err(error) = HDDLAnalyzer::verify_plan(&domain, &problem, plan_content) {
+                    diagnostics.plan_errors.push(error);
+                }
+            }
+            if input.format != OutputFormat::Text {
+                print_structured(
+                    &diagnostics,
+                    input.format,
+                    &input.domain_path,
+                    Some(&input.problem_path),
+                    Some(&input.plan_path),
+                );
+                return;
+            }
+            print_text(
+                &diagnostics,
+                (domain_content, &input.domain_path),
+                Some((problem_content, &input.problem_path)),
+                Some((plan_content, &input.plan_path)),
+            );
+        }
+        Commands::Simulate(input) => {
+            let Some(contents) = read_files(&[&input.domain_path, &input.problem_path, &input.actions_path]) else {
+                return;
+            };
+            let (domain_content, problem_content, actions_content) = (&contents[0], &contents[1], &contents[2]);
+            // the actions are only executed in a valid model
+            let mut diagnostics = verify_first(domain_content, Some(problem_content), &VerifyOptions::default());
+            let mut final_state = vec![];
+            if !diagnostics.has_errors() {
+                let (domain, problem) = parse_verified(domain_content, problem_content);
+                match HDDLAnalyzer::simulate(&domain, &problem, actions_content) {
+                    Ok(facts) => final_state = facts,
+                    Err(error) => diagnostics.plan_errors.push(error),
+                }
+            }
+            if input.format != OutputFormat::Text {
+                print_structured(
+                    &diagnostics,
+                    input.format,
+                    &input.domain_path,
+                    Some(&input.problem_path),
+                    Some(&input.actions_path),
+                );
+                return;
+            }
+            print_text(
+                &diagnostics,
+                (domain_content, &input.domain_path),
+                Some((problem_content, &input.problem_path)),
+                Some((actions_content, &input.actions_path)),
+            );
+            if !diagnostics.has_errors() {
+                println!("Final state:");
+                for fact in final_state.iter() {
+                    println!("\t{}", fact);
+                }
+            }
+        }
+        Commands::Fmt(input) => {
+            let mut is_formatted = true;
+            for path in input.paths.iter() {
+                let content = match fs::read(path) {
+                    Ok(content) => content,
+                    Err(read_error) => {
+                        eprintln!("{}[Error]{} {}: {}", red, reset, path, read_error);
+                        is_formatted = false;
+                        continue;
+                    }
+                };
+                // the syntax tree has no comments, so formatting would drop them
+                if content.contains(&b';') {
+                    if input.check {
+                        // the file can not be formatted, so it does not pass the check
+                        println!("{}[Unformatted]{} {} has comments, which formatting would drop", yellow, reset, path);
+                        is_formatted = false;
+                    } else {
+                        println!("{}[Warning]{} {} is skipped, since it has comments", yellow, reset, path);
+                    }
+                    continue;
+                }
+                let formatted = match HDDLAnalyzer::format(&content) {
+                    Ok(formatted) => formatted,
+                    Err(error) => {
+                        eprintln!("{}[Error]{} {}", red, reset, error);
+                        eprint!("{}", Snippet::new(&content, path).render(&error.labels()));
+                        is_formatted = false;
+                        continue;
+                    }
+                };
+                if formatted.as_bytes() == content.as_slice() {
+                    continue;
+                }
+                if input.check {
+                    println!("{}[Unformatted]{} {}", yellow, reset, path);
+                    is_formatted = false;
+                } else if let Err(write_error) = fs::write(path, formatted) {
+                    eprintln!("{}[Error]{} {}: {}", red, reset, path, write_error);
+                    is_formatted = false;
+                } else {
+                    println!("{}[Formatted]{} {}", green, reset, path);
+                }
+            }
+            if !is_formatted {
+                process::exit(1);
+            }
+        }
+        Commands::Ground(input) => {
+            // only a valid model is grounded
+            let Some(contents) = read_files(&[&input.domain_path, &input.problem_path]) else {
+                return;
+            };
+            let Some((domain, problem)) = parse_valid(&contents, &input.domain_path, &input.problem_path) else {
+                return;
+            };
+            match HDDLAnalyzer::ground(&domain, &problem) {
+                Ok(model) => {
+                    print!("{}", model.statistics);
+                    if let Some(output_path) = &input.output {
+                        if let Err(write_error) = fs::write(output_path, model.to_panda()) {
+                            eprintln!("{}[Error]{} {}: {}", red, reset, output_path, write_error);
                         }
                     }
-                    None => {
-                        let output = HDDLAnalyzer::verify(&domain_content, None);
-                        match output {
-                            Ok(warnings) => {
-                                for warning in warnings {
-                                    println!("{}[Warning]{} {}", yellow, reset, warning);
-                                }
-                                println!("{}[Ok]{}", green, reset);
-                            }
-                            Err(parsing_error) => {
-                                eprintln!("{}[Error]{} {}", red, reset, parsing_error)
+                }
+                Err(error) => eprintln!("{}[Error]{} {}", red, reset, error),
+            }
+        }
+        Commands::Landmarks(input) => {
+            let Some(contents) = read_files(&[&input.domain_path, &input.problem_path]) else {
+                return;
+            };
+            let Some((domain, problem)) = parse_valid(&contents, &input.domain_path, &input.problem_path) else {
+                return;
+            };
+            match HDDLAnalyzer::landmarks(&domain, &problem) {
+                Ok(Some(landmarks)) => {
+                    let sections = [
+                        ("Compound task landmarks", &landmarks.compound_tasks),
+                        ("Method landmarks", &landmarks.methods),
+                        ("Action landmarks", &landmarks.actions),
+                    ];
+                    for (title, names) in sections {
+                        if names.is_empty() {
+                            println!("{}: None", title);
+                        } else {
+                            println!("{}:", title);
+                            for name in names.iter() {
+                                println!("\t{}", name);
                             }
                         }
                     }
-                },
+                }
+                Ok(None) => println!("{}[Warning]{} the initial task network has no primitive refinement", yellow, reset),
+                Err(error) => eprintln!("{}[Error]{} {}", red, reset, error),
+            }
+        }
+        Commands::Explore(input) => {
+            let Some(contents) = read_files(&[&input.domain_path, &input.problem_path]) else {
+                return;
+            };
+            let Some((domain, problem)) = parse_valid(&contents, &input.domain_path, &input.problem_path) else {
+                return;
+            };
+            explore(&mut Explorer::new(&domain, &problem));
+        }
+        Commands::Solve(input) => {
+            let Some(contents) = read_files(&[&input.domain_path, &input.problem_path]) else {
+                return;
+            };
+            let Some((domain, problem)) = parse_valid(&contents, &input.domain_path, &input.problem_path) else {
+                return;
+            };
+            match HDDLAnalyzer::solve(&domain, &problem, input.depth, input.nodes) {
+                Ok(SearchResult::Solution(plan)) => print!("{}", plan),
+                Ok(SearchResult::Unsolvable) => println!("{}[Warning]{} the problem has no solution", yellow, reset),
+                Ok(SearchResult::NoSolutionUpToDepth(depth)) => {
+                    println!("{}[Warning]{} the problem has no solution up to depth {}", yellow, reset, depth)
+                }
+                Ok(SearchResult::NodeBoundReached(nodes)) => {
+                    println!("{}[Warning]{} no solution was found within {} search nodes", yellow, reset, nodes)
+                }
+                Err(error) => eprintln!("{}[Error]{} {}", red, reset, error),
+            }
+        }
+        Commands::Graph(input) => {
+            let domain_content = match fs::read(&input.domain_path) {
+                Ok(content) => content,
                 Err(read_error) => {
-                    eprintln!("{}[Error]{} {}", red, reset, read_error)
+                    eprintln!("{}[Error]{} {}", red, reset, read_error);
+                    return;
+                }
+            };
+            let problem_content = match &input.problem_path {
+                Some(problem_path) => match fs::read(problem_path) {
+                    Ok(content) => Some(content),
+                    Err(read_error) => {
+                        eprintln!("{}[Error]{} {}", red, reset, read_error);
+                        return;
+                    }
+                },
+                None => None,
+            };
+            // only a valid model is exported
+            let diagnostics = verify_first(&domain_content, problem_content.as_ref(), &VerifyOptions::default());
+            if diagnostics.has_errors() {
+                let problem = problem_content.as_ref().zip(input.problem_path.as_deref());
+                print_text(&diagnostics, (&domain_content, &input.domain_path), problem, None);
+                return;
+            }
+            let domain = HDDLAnalyzer::parse_domain(&domain_content).unwrap();
+            let analysis = HDDLAnalyzer::analyze(&domain, None).unwrap();
+            let tdg = &analysis.tdg;
+            let mut graph = match input.graph {
+                GraphKind::Tdg => Graph::from_tdg(tdg),
+                GraphKind::Types => Graph::from_type_hierarchy(&analysis.symbol_table.type_hierarchy),
+            };
+            if input.graph == GraphKind::Tdg {
+                let nullables = tdg.compute_nullables();
+                if input.cycle {
+                    if let Some(cycle) = tdg.get_recursion_type(&nullables).cycle() {
+                        graph.highlight_cycle(cycle);
+                    }
+                }
+                if input.nullables {
+                    graph.highlight_nullables(&nullables);
+                }
+                if let Some(task) = &input.from {
+                    let is_declared = tdg.compound_tasks().contains(&task.as_str())
+                        || tdg.primitive_tasks().contains(&task.as_str());
+                    if !is_declared {
+                        eprintln!("{}[Error]{} task {} is not declared", red, reset, task);
+                        return;
+                    }
+                    graph.highlight_reachable(tdg, &[task.as_str()]);
+                }
+                if let Some(problem_content) = &problem_content {
+                    let problem = HDDLAnalyzer::parse_problem(problem_content).unwrap();
+                    let tasks: Vec<&str> = match &problem.init_tn {
+                        Some(init_tn) => init_tn.tn.subtasks.iter().map(|subtask| subtask.task.name).collect(),
+                        None => vec![],
+                    };
+                    graph.highlight_reachable(tdg, &tasks);
+                }
+            }
+            match input.format {
+                GraphFormat::Dot => print!("{}", graph.to_dot()),
+                GraphFormat::Graphml => print!("{}", graph.to_graphml()),
+            }
+        }
+    }
+}
+
+// prints each diagnostic along with a snippet of the file it belongs to
+fn print_text(
+    diagnostics: &Diagnostics,
+    domain: (&Vec<u8>, &str),
+    problem: Option<(&Vec<u8>, &str)>,
+    plan: Option<(&Vec<u8>, &str)>,
+) {
+    // ANSI escape color codes
+    let yellow = "\x1b[33m";
+    let green = "\x1b[32m";
+    let red = "\x1b[31m";
+    let cyan = "\x1b[36m";
+    // ANSI escape code to reset text color
+    let reset = "\x1b[0m";
+
+    let domain_snippet = Snippet::new(domain.0, domain.1);
+    let problem_snippet = problem.map(|(content, path)| Snippet::new(content, path));
+    for warning in diagnostics.warnings.iter() {
+        match warning.is_smell() {
+            true => println!("{}[Note]{} {}", cyan, reset, warning),
+            false => println!("{}[Warning]{} {}", yellow, reset, warning),
+        }
+        let snippet = match (&problem_snippet, warning.is_in_problem()) {
+            (Some(problem_snippet), true) => problem_snippet,
+            _ => &domain_snippet,
+        };
+        print!("{}", snippet.render(&warning.labels()));
+    }
+    for error in diagnostics.errors.iter() {
+        eprintln!("{}[Error]{} {}", red, reset, error);
+        eprint!("{}", domain_snippet.render(&error.labels()));
+    }
+    for (source, errors) in [(problem, &diagnostics.problem_errors), (plan, &diagnostics.plan_errors)] {
+        if let Some((content, path)) = source {
+            let snippet = Snippet::new(content, path);
+            for error in errors.iter() {
+                eprintln!("{}[Error]{} {}", red, reset, error);
+                eprint!("{}", snippet.render(&error.labels()));
+            }
+        }
+    }
+    if !diagnostics.has_errors() {
+        println!("{}[Ok]{}", green, reset);
+    }
+}
+
+const EXPLORE_HELP: &str = "Commands:
+\tdecompose <task id> <method> [?parameter=object ...]
+\texecute <task id>
+\tmethods <task id>
+\tstate
+\tundo
+\tplan
+\tquit";
+
+// reads commands from the standard input until it is closed
+fn explore(explorer: &mut Explorer) {
+    let yellow = "\x1b[33m";
+    let green = "\x1b[32m";
+    let red = "\x1b[31m";
+    let reset = "\x1b[0m";
+
+    println!("{}", EXPLORE_HELP);
+    print_task_network(explorer);
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            println!();
+            return;
+        }
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let id = tokens.get(1).and_then(|token| token.parse::<usize>().ok());
+        let result = match (tokens.first().copied(), id) {
+            (None, _) => continue,
+            (Some("quit" | "exit"), _) => return,
+            (Some("decompose"), Some(id)) if tokens.len() > 2 => {
+                let mut bindings = vec![];
+                for binding in tokens[3..].iter() {
+                    match binding.split_once('=') {
+                        Some(pair) => bindings.push(pair),
+                        None => eprintln!("{}[Error]{} '{}' is not of the form ?parameter=object", red, reset, binding),
+                    }
+                }
+                if bindings.len() + 3 != tokens.len() {
+                    continue;
+                }
+                explorer.decompose(id, tokens[2], &bindings)
+            }
+            (Some("execute"), Some(id)) => explorer.execute(id),
+            (Some("methods"), Some(id)) => {
+                match explorer.methods(id) {
+                    Ok(methods) => {
+                        for method in methods {
+                            let parameters: Vec<String> = method.params.iter().map(|p| format!("?{}", p.name)).collect();
+                            println!("\t{} ({})", method.name.name, parameters.join(" "));
+                        }
+                    }
+                    Err(error) => eprintln!("{}[Error]{} {}", red, reset, error),
+                }
+                continue;
+            }
+            (Some("state"), _) => {
+                let mut facts: Vec<String> = explorer.state().atoms.iter().map(|atom| atom.to_string()).collect();
+                facts.sort();
+                for fact in facts {
+                    println!("\t{}", fact);
                 }
+                continue;
+            }
+            (Some("undo"), _) => {
+                if !explorer.undo() {
+                    println!("{}[Warning]{} there is nothing to undo", yellow, reset);
+                    continue;
+                }
+                Ok(())
+            }
+            (Some("plan"), _) => {
+                print!("{}", explorer.partial_plan());
+                continue;
+            }
+            _ => {
+                println!("{}", EXPLORE_HELP);
+                continue;
+            }
+        };
+        match result {
+            Ok(()) => {
+                print_task_network(explorer);
+                if explorer.tasks().is_empty() {
+                    let goal = explorer.unsatisfied_goal();
+                    if goal.is_empty() {
+                        println!("{}[Ok]{} the task network is fully refined", green, reset);
+                    } else {
+                        println!("{}[Warning]{} the goal does not hold: {}", yellow, reset, goal.join(", "));
+                    }
+                }
+            }
+            Err(error) => eprintln!("{}[Error]{} {}", red, reset, error),
+        }
+    }
+}
+
+fn print_task_network(explorer: &Explorer) {
+    println!("Task network:");
+    for task in explorer.tasks() {
+        let kind = if task.is_primitive { "action" } else { "task" };
+        let mut line = format!("\t{}: ({} {}", task.id, task.name, task.arguments.join(" "));
+        line = line.trim_end().to_string() + ")";
+        let predecessors: Vec<String> = explorer.predecessors(task.id).iter().map(|id| id.to_string()).collect();
+        if predecessors.is_empty() {
+            println!("{} [{}]", line, kind);
+        } else {
+            println!("{} [{}, after {}]", line, kind, predecessors.join(", "));
+        }
+    }
+}
+
+// writes one DIMACS file per precondition and effect
+fn write_cnf(directory: &str, formulas: Vec<(String, String)>) -> io::Result<()> {
+    fs::create_dir_all(directory)?;
+    for (name, dimacs) in formulas {
+        fs::write(std::path::Path::new(directory).join(format!("{}.cnf", name)), dimacs)?;
+    }
+    Ok(())
+}
+
+// reads the files, reporting the first one that can not be read
+fn read_files(paths: &[&str]) -> Option<Vec<Vec<u8>>> {
+    let red = "\x1b[31m";
+    let reset = "\x1b[0m";
+
+    let mut contents = vec![];
+    for path in paths {
+        match fs::read(path) {
+            Ok(content) => contents.push(content),
+            Err(read_error) => {
+                eprintln!("{}[Error]{} {}", red, reset, read_error);
+                return None;
             }
         }
     }
-}
\ No newline at end of file
+    Some(contents)
+}
+
+// parses a domain and problem that `verify_first` found no errors in
+fn parse_verified<'a>(domain: &'a Vec<u8>, problem: &'a Vec<u8>) -> (DomainAST<'a>, ProblemAST<'a>) {
+    (HDDLAnalyzer::parse_domain(domain).unwrap(), HDDLAnalyzer::parse_problem(problem).unwrap())
+}
+
+// parses the contents of a domain and problem file if they are valid, and prints their errors otherwise
+fn parse_valid<'a>(
+    contents: &'a [Vec<u8>],
+    domain_path: &str,
+    problem_path: &str,
+) -> Option<(DomainAST<'a>, ProblemAST<'a>)> {
+    let (domain, problem) = (&contents[0], &contents[1]);
+    let diagnostics = verify_first(domain, Some(problem), &VerifyOptions::default());
+    if diagnostics.has_errors() {
+        print_text(&diagnostics, (domain, domain_path), Some((problem, problem_path)), None);
+        return None;
+    }
+    Some(parse_verified(domain, problem))
+}
+
+// stops at the first error, verifying the domain on its own first to know which file the error belongs to
+fn verify_first(domain: &Vec<u8>, problem: Option<&Vec<u8>>, options: &VerifyOptions) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+    match HDDLAnalyzer::verify_with(domain, None, options) {
+        Err(error) => diagnostics.errors.push(error),
+        Ok(warnings) => match problem {
+            None => diagnostics.warnings = warnings,
+            Some(problem) => match HDDLAnalyzer::verify_with(domain, Some(problem), options) {
+                Err(error) => diagnostics.problem_errors.push(error),
+                Ok(warnings) => diagnostics.warnings = warnings,
+            },
+        },
+    }
+    diagnostics
+}
+
+fn print_structured(
+    diagnostics: &Diagnostics,
+    format: OutputFormat,
+    domain_path: &str,
+    problem_path: Option<&str>,
+    plan_path: Option<&str>,
+) {
+    match format {
+        OutputFormat::Json => println!("{}", diagnostics.to_json(domain_path, problem_path, plan_path)),
+        OutputFormat::Sarif => println!("{}", diagnostics.to_sarif(domain_path, problem_path, plan_path)),
+        OutputFormat::Text => unreachable!("text output is printed by the caller"),
+    }
+}
@@ -0,0 +1,223 @@
+use serde_json::{json, Value};
+
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+// all errors and warnings found in a single run
+#[derive(Debug, Default)]
+pub struct Diagnostics {
+    // errors found in the domain file
+    pub errors: Vec<ParsingError>,
+    // errors found in the problem file
+    pub problem_errors: Vec<ParsingError>,
+    // errors found in the plan file
+    pub plan_errors: Vec<ParsingError>,
+    pub warnings: Vec<WarningType>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Diagnostics {
+        Diagnostics::default()
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty() || !self.problem_errors.is_empty() || !self.plan_errors.is_empty()
+    }
+
+    pub fn add_errors<T: Into<ParsingError>>(&mut self, errors: Vec<T>) {
+        self.errors.extend(errors.into_iter().map(|error| error.into()));
+    }
+
+    pub fn add_problem_errors<T: Into<ParsingError>>(&mut self, errors: Vec<T>) {
+        self.problem_errors.extend(errors.into_iter().map(|error| error.into()));
+    }
+}
+
+// a single error or warning, flattened for machine-readable output
+struct Record<'a> {
+    kind: &'static str,
+    severity: &'static str,
+    message: String,
+    file: &'a str,
+    labels: Vec<Label>,
+    // further details of the diagnostic, if it has any
+    explanation: Option<Value>,
+}
+
+impl Diagnostics {
+    fn records<'a>(
+        &self,
+        domain_file: &'a str,
+        problem_file: Option<&'a str>,
+        plan_file: Option<&'a str>,
+    ) -> Vec<Record<'a>> {
+        let mut records = vec![];
+        for warning in self.warnings.iter() {
+            let file = match warning.is_in_problem() {
+                true => problem_file.unwrap_or(domain_file),
+                false => domain_file,
+            };
+            records.push(Record {
+                kind: warning.kind(),
+                severity: if warning.is_smell() { "note" } else { "warning" },
+                message: warning.to_string(),
+                file,
+                labels: warning.labels(),
+                explanation: explanation_json(warning),
+            });
+        }
+        let errors = self
+            .errors
+            .iter()
+            .map(|error| (error, domain_file))
+            .chain(self.problem_errors.iter().map(|error| (error, problem_file.unwrap_or(domain_file))))
+            .chain(self.plan_errors.iter().map(|error| (error, plan_file.unwrap_or(domain_file))));
+        for (error, file) in errors {
+            records.push(Record {
+                kind: error.kind(),
+                severity: "error",
+                message: error.to_string().trim_end().to_string(),
+                file,
+                labels: error.labels(),
+                explanation: None,
+            });
+        }
+        records
+    }
+
+    pub fn to_json(&self, domain_file: &str, problem_file: Option<&str>, plan_file: Option<&str>) -> String {
+        let diagnostics: Vec<Value> = self
+            .records(domain_file, problem_file, plan_file)
+            .into_iter()
+            .map(|record| {
+                let primary = record.labels.iter().find(|label| label.primary);
+                let related: Vec<Value> = record
+                    .labels
+                    .iter()
+                    .filter(|label| !label.primary)
+                    .map(|label| json!({ "message": label.message, "position": position_json(&label.position) }))
+                    .collect();
+                let mut diagnostic = json!({
+                    "kind": record.kind,
+                    "severity": record.severity,
+                    "message": record.message,
+                    "file": record.file,
+                    "position": primary.map(|label| position_json(&label.position)),
+                    "related": related,
+                });
+                if let Some(explanation) = &record.explanation {
+                    diagnostic["explanation"] = explanation.clone();
+                }
+                diagnostic
+            })
+            .collect();
+        let output = json!({
+            "success": !self.has_errors(),
+            "diagnostics": diagnostics,
+        });
+        serde_json::to_string_pretty(&output).unwrap()
+    }
+
+    // SARIF 2.1.0 log, as accepted by code scanning dashboards
+    pub fn to_sarif(&self, domain_file: &str, problem_file: Option<&str>, plan_file: Option<&str>) -> String {
+        serde_json::to_string_pretty(&self.sarif_log(domain_file, problem_file, plan_file)).unwrap()
+    }
+
+    pub(crate) fn sarif_log(&self, domain_file: &str, problem_file: Option<&str>, plan_file: Option<&str>) -> Value {
+        let records = self.records(domain_file, problem_file, plan_file);
+        let mut rules: Vec<&str> = records.iter().map(|record| record.kind).collect();
+        rules.sort();
+        rules.dedup();
+        let results: Vec<Value> = records
+            .iter()
+            .map(|record| {
+                let locations: Vec<Value> = record
+                    .labels
+                    .iter()
+                    .filter(|label| label.primary)
+                    .map(|label| sarif_location(record.file, label))
+                    .collect();
+                let related: Vec<Value> = record
+                    .labels
+                    .iter()
+                    .filter(|label| !label.primary)
+                    .map(|label| sarif_location(record.file, label))
+                    .collect();
+                let mut result = json!({
+                    "ruleId": record.kind,
+                    "level": record.severity,
+                    "message": { "text": record.message },
+                    "locations": locations,
+                    "relatedLocations": related,
+                });
+                if let Some(explanation) = &record.explanation {
+                    result["properties"] = json!({ "explanation": explanation });
+                }
+                result
+            })
+            .collect();
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules.iter().map(|rule| json!({ "id": rule })).collect::<Vec<Value>>(),
+                    }
+                },
+                "results": results,
+            }]
+        })
+    }
+}
+
+fn explanation_json(warning: &WarningType) -> Option<Value> {
+    match warning {
+        WarningType::NoPrimitiveRefinement(info) => Some(blocked_task_json(&info.symbol, &info.cause)),
+        _ => None,
+    }
+}
+
+fn blocked_task_json(task: &str, cause: &BlockingCause) -> Value {
+    let methods: Vec<Value> = cause
+        .methods()
+        .iter()
+        .map(|method| {
+            let subtasks: Vec<Value> = method
+                .blocked_subtasks
+                .iter()
+                .map(|subtask| blocked_task_json(&subtask.task, &subtask.cause))
+                .collect();
+            json!({ "method": method.method, "blocked_subtasks": subtasks })
+        })
+        .collect();
+    json!({ "task": task, "cause": cause.name(), "methods": methods })
+}
+
+fn position_json(position: &TokenPosition) -> Value {
+    json!({
+        "line": position.line,
+        "column": position.column,
+        "start": position.start,
+        "end": position.end,
+    })
+}
+
+fn sarif_location(file: &str, label: &Label) -> Value {
+    let position = label.position;
+    json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": file },
+            "region": {
+                "startLine": position.line,
+                "startColumn": position.column,
+                "endColumn": position.column as usize + position.end.saturating_sub(position.start),
+                "charOffset": position.start,
+                "charLength": position.end.saturating_sub(position.start),
+            }
+        },
+        "message": { "text": label.message },
+    })
+}
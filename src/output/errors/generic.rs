@@ -4,7 +4,44 @@ use super::*;
 pub enum ParsingError{
     Lexiacal(LexicalError),
     Syntactic(SyntacticError),
-    Semantic(SemanticErrorType)
+    Semantic(SemanticErrorType),
+    Plan(PlanError)
+}
+
+impl ParsingError {
+    // name of the error variant, for machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Lexiacal(error) => match error.error_type {
+                LexicalErrorType::InvalidIdentifier => "InvalidIdentifier",
+                LexicalErrorType::InvalidKeyword => "InvalidKeyword",
+            },
+            Self::Syntactic(_) => "SyntacticError",
+            Self::Semantic(error) => error.kind(),
+            Self::Plan(error) => error.error_type.kind(),
+        }
+    }
+
+    // source locations to be shown along with the error message
+    pub fn labels(&self) -> Vec<Label> {
+        match self {
+            Self::Lexiacal(error) => {
+                let message = match error.error_type {
+                    LexicalErrorType::InvalidIdentifier => "invalid identifier",
+                    LexicalErrorType::InvalidKeyword => "invalid keyword",
+                };
+                vec![Label::primary(error.position, message)]
+            }
+            Self::Syntactic(error) => {
+                vec![Label::primary(error.position, &format!("expected {}", error.expected))]
+            }
+            Self::Semantic(error) => error.labels(),
+            Self::Plan(error) => match error.position {
+                Some(position) => vec![Label::primary(position, "invalid step")],
+                None => vec![],
+            },
+        }
+    }
 }
 
 impl From<LexicalError> for ParsingError {
@@ -19,6 +56,12 @@ impl From<SyntacticError> for ParsingError {
     }
 }
 
+impl From<PlanError> for ParsingError {
+    fn from(value: PlanError) -> Self {
+        ParsingError::Plan(value)
+    }
+}
+
 impl From<SemanticErrorType> for ParsingError {
     fn from(value: SemanticErrorType) -> Self {
         ParsingError::Semantic(value)
@@ -30,7 +73,8 @@ impl std::fmt::Display for ParsingError {
         match self {
             Self::Lexiacal(error) => write!(f, "{}", error),
             Self::Syntactic(error) => write!(f, "{}", error),
-            Self::Semantic(error) => write!(f, "{}", error)
+            Self::Semantic(error) => write!(f, "{}", error),
+            Self::Plan(error) => write!(f, "{}", error)
         }
     }
 }
@@ -2,11 +2,14 @@ mod lexical;
 mod syntactic;
 mod generic;
 mod semantic;
+mod plan;
 
 pub use lexical::*;
 pub use syntactic::*;
 pub use generic::*;
 pub use semantic::*;
+pub use plan::*;
 
 
-use crate::lexical_analyzer::{Token, TokenPosition};
\ No newline at end of file
+use crate::lexical_analyzer::{Token, TokenPosition};
+use super::Label;
\ No newline at end of file
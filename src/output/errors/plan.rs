@@ -0,0 +1,179 @@
+use crate::lexical_analyzer::TokenPosition;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum PlanErrorType {
+    // Plan Format Errors
+    MissingPlanMarker,
+    MalformedLine(String),
+    DuplicateStepId(usize),
+    UndefinedStepId(usize),
+    // Undefined Entities
+    UndefinedAction(String),
+    UndefinedTask(String),
+    UndefinedMethod(String),
+    UndefinedObject(String),
+    UndefinedParameter(String, String), // (method, parameter)
+    // Inconsistency Errors
+    InconsistentArity(Box<PlanArityError>),
+    InconsistentArgType(Box<PlanTypeError>),
+    InconsistentMethodTask(String, String), // (method, task)
+    InconsistentMethodArgument(String, String), // (method, parameter)
+    InconsistentSubtask(String), // method
+    // Decomposition Errors
+    MultipleParents(usize),
+    UnreachableStep(usize),
+    RootMismatch(String),
+    ViolatedConstraint(String), // method
+    ViolatedOrdering(usize, usize), // (step that should come first, step that should come later)
+    // Execution Errors
+    UnsatisfiedMethodPrecondition(String), // method
+    UnsatisfiedPrecondition(String, Vec<String>), // (action, failing literals)
+    UnsatisfiedGoal(Vec<String>), // failing literals
+}
+
+// an error in a plan, located at the step (and line of the plan file) that breaks
+#[derive(Debug)]
+pub struct PlanError {
+    pub error_type: PlanErrorType,
+    pub step: Option<usize>,
+    pub position: Option<TokenPosition>,
+}
+
+impl PlanErrorType {
+    // name of the error variant, for machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PlanErrorType::MissingPlanMarker => "MissingPlanMarker",
+            PlanErrorType::MalformedLine(_) => "MalformedLine",
+            PlanErrorType::DuplicateStepId(_) => "DuplicateStepId",
+            PlanErrorType::UndefinedStepId(_) => "UndefinedStepId",
+            PlanErrorType::UndefinedAction(_) => "UndefinedAction",
+            PlanErrorType::UndefinedTask(_) => "UndefinedTask",
+            PlanErrorType::UndefinedMethod(_) => "UndefinedMethod",
+            PlanErrorType::UndefinedObject(_) => "UndefinedObject",
+            PlanErrorType::UndefinedParameter(_, _) => "UndefinedParameter",
+            PlanErrorType::InconsistentArity(_) => "InconsistentArity",
+            PlanErrorType::InconsistentArgType(_) => "InconsistentArgType",
+            PlanErrorType::InconsistentMethodTask(_, _) => "InconsistentMethodTask",
+            PlanErrorType::InconsistentMethodArgument(_, _) => "InconsistentMethodArgument",
+            PlanErrorType::InconsistentSubtask(_) => "InconsistentSubtask",
+            PlanErrorType::MultipleParents(_) => "MultipleParents",
+            PlanErrorType::UnreachableStep(_) => "UnreachableStep",
+            PlanErrorType::RootMismatch(_) => "RootMismatch",
+            PlanErrorType::ViolatedConstraint(_) => "ViolatedConstraint",
+            PlanErrorType::ViolatedOrdering(_, _) => "ViolatedOrdering",
+            PlanErrorType::UnsatisfiedMethodPrecondition(_) => "UnsatisfiedMethodPrecondition",
+            PlanErrorType::UnsatisfiedPrecondition(_, _) => "UnsatisfiedPrecondition",
+            PlanErrorType::UnsatisfiedGoal(_) => "UnsatisfiedGoal",
+        }
+    }
+}
+
+impl fmt::Display for PlanErrorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanErrorType::MissingPlanMarker => {
+                write!(f, "the plan does not start with '==>'.")
+            }
+            PlanErrorType::MalformedLine(line) => {
+                write!(f, "'{}' is neither a primitive step, a root, nor a decomposition.", line)
+            }
+            PlanErrorType::DuplicateStepId(id) => {
+                write!(f, "step id {} is used more than once.", id)
+            }
+            PlanErrorType::UndefinedStepId(id) => {
+                write!(f, "step {} is not defined.", id)
+            }
+            PlanErrorType::UndefinedAction(action) => {
+                write!(f, "action {} is not defined.", action)
+            }
+            PlanErrorType::UndefinedTask(task) => {
+                write!(f, "compound task {} is not defined.", task)
+            }
+            PlanErrorType::UndefinedMethod(method) => {
+                write!(f, "method {} is not defined.", method)
+            }
+            PlanErrorType::UndefinedObject(object) => {
+                write!(f, "object {} is not defined.", object)
+            }
+            PlanErrorType::UndefinedParameter(method, parameter) => {
+                write!(f, "method {} has no parameter {}.", method, parameter)
+            }
+            PlanErrorType::InconsistentArity(ar_error) => {
+                write!(
+                    f,
+                    "{} takes {} parameters, but {} are given.",
+                    ar_error.symbol, ar_error.expected_arity, ar_error.found_arity
+                )
+            }
+            PlanErrorType::InconsistentArgType(type_error) => {
+                write!(
+                    f,
+                    "object {} is given for parameter {} of {}, but it is not of type '{}'.",
+                    type_error.object, type_error.parameter, type_error.symbol, type_error.expected
+                )
+            }
+            PlanErrorType::InconsistentMethodTask(method, task) => {
+                write!(f, "method {} does not decompose task {}.", method, task)
+            }
+            PlanErrorType::InconsistentMethodArgument(method, parameter) => {
+                write!(f, "parameter {} of method {} is bound to different objects.", parameter, method)
+            }
+            PlanErrorType::InconsistentSubtask(method) => {
+                write!(f, "the subtasks do not match the task network of method {}.", method)
+            }
+            PlanErrorType::MultipleParents(id) => {
+                write!(f, "step {} is a subtask of more than one decomposition.", id)
+            }
+            PlanErrorType::UnreachableStep(id) => {
+                write!(f, "step {} is not reachable from the root.", id)
+            }
+            PlanErrorType::RootMismatch(reason) => {
+                write!(f, "the root does not match the initial task network: {}.", reason)
+            }
+            PlanErrorType::ViolatedConstraint(method) => {
+                write!(f, "the constraints of method {} are violated.", method)
+            }
+            PlanErrorType::ViolatedOrdering(first, second) => {
+                write!(f, "step {} must be done before step {}.", first, second)
+            }
+            PlanErrorType::UnsatisfiedMethodPrecondition(method) => {
+                write!(f, "the precondition of method {} does not hold.", method)
+            }
+            PlanErrorType::UnsatisfiedPrecondition(action, literals) => {
+                write!(f, "the precondition of action {} does not hold: {}", action, literals.join(", "))
+            }
+            PlanErrorType::UnsatisfiedGoal(literals) => {
+                write!(f, "the goal does not hold at the end of the plan: {}", literals.join(", "))
+            }
+        }
+    }
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(position) = self.position {
+            write!(f, "line {}: ", position.line)?;
+        }
+        if let Some(step) = self.step {
+            write!(f, "step {}: ", step)?;
+        }
+        write!(f, "{}", self.error_type)
+    }
+}
+
+#[derive(Debug)]
+pub struct PlanArityError {
+    pub symbol: String,
+    pub expected_arity: u32,
+    pub found_arity: u32,
+}
+
+#[derive(Debug)]
+pub struct PlanTypeError {
+    pub symbol: String,
+    pub parameter: String,
+    pub object: String,
+    pub expected: String,
+}
@@ -1,4 +1,5 @@
 use crate::lexical_analyzer::{RequirementType, TokenPosition};
+use crate::output::Label;
 use std::fmt;
 
 #[derive(Debug)]
@@ -32,6 +33,100 @@ pub enum SemanticErrorType {
     ComplementaryMethodPrecondition(TokenPosition)
 }
 
+impl SemanticErrorType {
+    // name of the error variant, for machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SemanticErrorType::DuplicateObjectDeclaration(_) => "DuplicateObjectDeclaration",
+            SemanticErrorType::DuplicateRequirementDeclaration(_) => "DuplicateRequirementDeclaration",
+            SemanticErrorType::DuplicatePredicateDeclaration(_) => "DuplicatePredicateDeclaration",
+            SemanticErrorType::DuplicateActionDeclaration(_) => "DuplicateActionDeclaration",
+            SemanticErrorType::DuplicateCompoundTaskDeclaration(_) => "DuplicateCompoundTaskDeclaration",
+            SemanticErrorType::DuplicateMethodDeclaration(_) => "DuplicateMethodDeclaration",
+            SemanticErrorType::DuplicateParameterDeclaration(_) => "DuplicateParameterDeclaration",
+            SemanticErrorType::UndefinedPredicate(_) => "UndefinedPredicate",
+            SemanticErrorType::UndefinedType(_) => "UndefinedType",
+            SemanticErrorType::UndefinedSubtask(_) => "UndefinedSubtask",
+            SemanticErrorType::UndefinedTask(_) => "UndefinedTask",
+            SemanticErrorType::UndefinedParameter(_) => "UndefinedParameter",
+            SemanticErrorType::UndefinedObject(_) => "UndefinedObject",
+            SemanticErrorType::InconsistentPredicateArity(_) => "InconsistentPredicateArity",
+            SemanticErrorType::InconsistentTaskArity(_) => "InconsistentTaskArity",
+            SemanticErrorType::InconsistentPredicateArgType(_) => "InconsistentPredicateArgType",
+            SemanticErrorType::InconsistentTaskArgType(_) => "InconsistentTaskArgType",
+            SemanticErrorType::CyclicTypeDeclaration => "CyclicTypeDeclaration",
+            SemanticErrorType::CyclicOrderingDeclaration(_) => "CyclicOrderingDeclaration",
+            SemanticErrorType::ComplementaryActionEffect(_) => "ComplementaryActionEffect",
+            SemanticErrorType::ComplementaryActionPrecondition(_) => "ComplementaryActionPrecondition",
+            SemanticErrorType::ComplementaryMethodPrecondition(_) => "ComplementaryMethodPrecondition",
+        }
+    }
+
+    // source locations to be shown along with the error message
+    pub fn labels(&self) -> Vec<Label> {
+        match self {
+            // Duplicate Errors
+            SemanticErrorType::DuplicateObjectDeclaration(duplicate)
+            | SemanticErrorType::DuplicatePredicateDeclaration(duplicate)
+            | SemanticErrorType::DuplicateActionDeclaration(duplicate)
+            | SemanticErrorType::DuplicateCompoundTaskDeclaration(duplicate)
+            | SemanticErrorType::DuplicateMethodDeclaration(duplicate)
+            | SemanticErrorType::DuplicateParameterDeclaration(duplicate) => {
+                vec![
+                    Label::secondary(duplicate.first_pos, "first defined here"),
+                    Label::primary(duplicate.second_pos, "redefined here"),
+                ]
+            }
+            SemanticErrorType::DuplicateRequirementDeclaration(_) => vec![],
+            // Undefined Entities
+            SemanticErrorType::UndefinedPredicate(undefined) => {
+                vec![Label::primary(undefined.position, "undefined predicate")]
+            }
+            SemanticErrorType::UndefinedType(undefined) => {
+                vec![Label::primary(undefined.position, "undefined type")]
+            }
+            SemanticErrorType::UndefinedSubtask(undefined) => {
+                vec![Label::primary(undefined.position, "undefined subtask")]
+            }
+            SemanticErrorType::UndefinedTask(undefined) => {
+                vec![Label::primary(undefined.position, "undefined task")]
+            }
+            SemanticErrorType::UndefinedParameter(undefined) => {
+                vec![Label::primary(undefined.position, "undefined parameter")]
+            }
+            SemanticErrorType::UndefinedObject(undefined) => {
+                vec![Label::primary(undefined.position, "undefined object")]
+            }
+            // Inconsistency Error
+            SemanticErrorType::InconsistentPredicateArity(ar_error)
+            | SemanticErrorType::InconsistentTaskArity(ar_error) => {
+                let message = format!(
+                    "expected {} arguments, found {}",
+                    ar_error.expected_arity, ar_error.found_arity
+                );
+                vec![Label::primary(ar_error.position, &message)]
+            }
+            SemanticErrorType::InconsistentPredicateArgType(type_error)
+            | SemanticErrorType::InconsistentTaskArgType(type_error) => {
+                vec![Label::primary(type_error.position, "mismatched type")]
+            }
+            // Ordering Errors
+            SemanticErrorType::CyclicTypeDeclaration => vec![],
+            SemanticErrorType::CyclicOrderingDeclaration(pos) => {
+                vec![Label::primary(*pos, "cyclic ordering")]
+            }
+            // Complementary Errors
+            SemanticErrorType::ComplementaryActionEffect(pos) => {
+                vec![Label::primary(*pos, "contradictory effects")]
+            }
+            SemanticErrorType::ComplementaryActionPrecondition(pos)
+            | SemanticErrorType::ComplementaryMethodPrecondition(pos) => {
+                vec![Label::primary(*pos, "contradictory preconditions")]
+            }
+        }
+    }
+}
+
 impl fmt::Display for SemanticErrorType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
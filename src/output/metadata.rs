@@ -1,5 +1,9 @@
 use std::fmt::{Display, Formatter, Error};
 
+use serde_json::{json, Value};
+
+use super::Diagnostics;
+
 #[derive(PartialEq, Eq, Debug)]
 pub enum RecursionType {
     NonRecursive,
@@ -7,6 +11,33 @@ pub enum RecursionType {
     EmptyRecursion(Vec<(String, String)>), // (task_name, method_name) 
     GrowingEmptyPrefixRecursion(Vec<(String, String)>), // (task_name, method_name) 
     GrowAndShrinkRecursion(Vec<(String, String)>), // (task_name, method_name) 
+    TailRecursion(Vec<(String, String)>), // (task_name, method_name) 
+}
+
+impl RecursionType {
+    // name of the recursion class, for machine-readable output
+    pub fn class_name(&self) -> &'static str {
+        match self {
+            RecursionType::NonRecursive => "NonRecursive",
+            RecursionType::Recursive(_) => "Recursive",
+            RecursionType::EmptyRecursion(_) => "EmptyRecursion",
+            RecursionType::GrowingEmptyPrefixRecursion(_) => "GrowingEmptyPrefixRecursion",
+            RecursionType::GrowAndShrinkRecursion(_) => "GrowAndShrinkRecursion",
+            RecursionType::TailRecursion(_) => "TailRecursion",
+        }
+    }
+
+    // the (task, method) pairs of the witnessing cycle, if any
+    pub fn cycle(&self) -> Option<&Vec<(String, String)>> {
+        match self {
+            RecursionType::NonRecursive => None,
+            RecursionType::Recursive(pairs)
+            | RecursionType::EmptyRecursion(pairs)
+            | RecursionType::GrowingEmptyPrefixRecursion(pairs)
+            | RecursionType::GrowAndShrinkRecursion(pairs)
+            | RecursionType::TailRecursion(pairs) => Some(pairs),
+        }
+    }
 }
 
 impl Display for RecursionType {
@@ -33,6 +64,11 @@ impl Display for RecursionType {
                 write!(f, "\tCycle: ")?;
                 format_task_pairs(pairs, f)
             }
+            RecursionType::TailRecursion(pairs) => {
+                writeln!(f, "Tail recursion")?;
+                write!(f, "\tCycle: ")?;
+                format_task_pairs(pairs, f)
+            }
         }
     }
 }
@@ -50,19 +86,237 @@ fn format_task_pairs(pairs: &[(String, String)], f: &mut Formatter<'_>) -> std::
 }
 
 
+// a strongly connected component of the TDG with at least one cycle
+#[derive(PartialEq, Eq, Debug)]
+pub struct RecursiveComponent {
+    pub tasks: Vec<String>,
+    // the worst kind of recursion in the component, with a shortest cycle of that kind
+    pub recursion: RecursionType,
+}
+
+// classes of the hierarchy. Acyclic and regular hierarchies are both tail-recursive, but
+// neither of the two classes contains the other. A hierarchy in none of them is general.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum HierarchyClass {
+    Acyclic,
+    // every method has at most one compound subtask, which is ordered last
+    Regular,
+    // recursive subtasks are only followed by nullable tasks
+    TailRecursive,
+}
+
+impl HierarchyClass {
+    // name of the class, for machine-readable output
+    pub fn name(&self) -> &'static str {
+        match self {
+            HierarchyClass::Acyclic => "Acyclic",
+            HierarchyClass::Regular => "Regular",
+            HierarchyClass::TailRecursive => "TailRecursive",
+        }
+    }
+}
+
+impl Display for HierarchyClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HierarchyClass::Acyclic => write!(f, "acyclic"),
+            HierarchyClass::Regular => write!(f, "regular"),
+            HierarchyClass::TailRecursive => write!(f, "tail-recursive"),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct HierarchyClassification {
+    // the classes that the hierarchy belongs to (acyclic, regular, tail-recursive, in this order)
+    pub classes: Vec<HierarchyClass>,
+    // a method that keeps the hierarchy out of each of the other classes
+    pub violations: Vec<(HierarchyClass, String)>,
+    // a method whose subtasks are not totally ordered, if there is one
+    pub partially_ordered_method: Option<String>,
+}
+
+impl HierarchyClassification {
+    pub fn is_totally_ordered(&self) -> bool {
+        self.partially_ordered_method.is_none()
+    }
+
+    pub fn is_general(&self) -> bool {
+        self.classes.is_empty()
+    }
+
+    pub fn belongs_to(&self, class: HierarchyClass) -> bool {
+        self.classes.contains(&class)
+    }
+
+    // the method that keeps the hierarchy out of the class, if there is one
+    pub fn violating_method(&self, class: HierarchyClass) -> Option<&str> {
+        self.violations
+            .iter()
+            .find(|(violated, _)| *violated == class)
+            .map(|(_, method)| method.as_str())
+    }
+}
+
+impl Display for HierarchyClassification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_general() {
+            write!(f, "general")?;
+        } else {
+            let classes: Vec<String> = self.classes.iter().map(|class| class.to_string()).collect();
+            write!(f, "{}", classes.join(", "))?;
+        }
+        let violations: Vec<String> = self
+            .violations
+            .iter()
+            .map(|(class, method)| format!("not {} because of method {}", class, method))
+            .collect();
+        if !violations.is_empty() {
+            write!(f, " ({})", violations.join(", "))?;
+        }
+        match &self.partially_ordered_method {
+            None => write!(f, ", totally ordered"),
+            Some(method) => write!(f, ", partially ordered (method {})", method),
+        }
+    }
+}
+
+// the cheapest refinements of a compound task, with the method chosen at the top of each
+#[derive(PartialEq, Eq, Debug)]
+pub struct TaskBounds {
+    pub task: String,
+    // minimum number of primitive tasks in a refinement
+    pub min_plan_length: u64,
+    pub length_method: String,
+    // minimum number of decompositions on the longest path of a refinement
+    pub min_depth: u64,
+    pub depth_method: String,
+}
+
+impl Display for TaskBounds {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let noun = match self.min_plan_length {
+            1 => "primitive task",
+            _ => "primitive tasks",
+        };
+        write!(
+            f,
+            "{}: {} {} ({}), depth {} ({})",
+            self.task, self.min_plan_length, noun, self.length_method, self.min_depth, self.depth_method
+        )
+    }
+}
+
 pub struct MetaData {
     pub recursion: RecursionType,
+    pub hierarchy_class: HierarchyClassification,
+    pub recursive_components: Vec<RecursiveComponent>,
     pub nullables: Vec<String>,
+    // bounds of the compound tasks that have a refinement, in declaration order
+    pub task_bounds: Vec<TaskBounds>,
     pub domain_name: String,
     pub n_actions: u32,
     pub n_tasks: u32,
     pub n_methods: u32
 }
 
+impl MetaData {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.json_value()).unwrap()
+    }
+
+    // a SARIF log without results, carrying the metadata as run properties
+    pub fn to_sarif(&self) -> String {
+        let mut log = Diagnostics::new().sarif_log("", None, None);
+        log["runs"][0]["properties"] = self.json_value();
+        serde_json::to_string_pretty(&log).unwrap()
+    }
+
+    fn json_value(&self) -> Value {
+        let components: Vec<Value> = self
+            .recursive_components
+            .iter()
+            .map(|component| {
+                json!({
+                    "tasks": component.tasks,
+                    "class": component.recursion.class_name(),
+                    "cycle": cycle_json(&component.recursion),
+                })
+            })
+            .collect();
+        let bounds: Vec<Value> = self
+            .task_bounds
+            .iter()
+            .map(|bounds| {
+                json!({
+                    "task": bounds.task,
+                    "min_plan_length": bounds.min_plan_length,
+                    "length_method": bounds.length_method,
+                    "min_depth": bounds.min_depth,
+                    "depth_method": bounds.depth_method,
+                })
+            })
+            .collect();
+        let classes: Vec<&str> = self.hierarchy_class.classes.iter().map(|class| class.name()).collect();
+        let violating_methods: serde_json::Map<String, Value> = self
+            .hierarchy_class
+            .violations
+            .iter()
+            .map(|(class, method)| (class.name().to_string(), json!(method)))
+            .collect();
+        json!({
+            "domain": self.domain_name,
+            "recursion": {
+                "class": self.recursion.class_name(),
+                "cycle": cycle_json(&self.recursion),
+            },
+            "hierarchy_class": {
+                "classes": classes,
+                "violating_methods": violating_methods,
+                "totally_ordered": self.hierarchy_class.is_totally_ordered(),
+                "partially_ordered_method": self.hierarchy_class.partially_ordered_method,
+            },
+            "recursive_components": components,
+            "nullables": self.nullables,
+            "task_bounds": bounds,
+            "counts": {
+                "actions": self.n_actions,
+                "tasks": self.n_tasks,
+                "methods": self.n_methods,
+            }
+        })
+    }
+}
+
+// as in the text output, the last task closes the cycle
+fn cycle_json(recursion: &RecursionType) -> Option<Vec<Value>> {
+    recursion.cycle().map(|pairs| {
+        pairs
+            .iter()
+            .enumerate()
+            .map(|(i, (task, method))| match i == pairs.len() - 1 {
+                true => json!({ "task": task }),
+                false => json!({ "task": task, "method": method }),
+            })
+            .collect()
+    })
+}
+
 impl Display for MetaData {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), Error> {
         writeln!(f, "Description")?;
         writeln!(f, "\tHierarchy type: {}", self.recursion)?;
+        writeln!(f, "\tHierarchy class: {}", self.hierarchy_class)?;
+        if self.recursive_components.is_empty() {
+            writeln!(f, "\tRecursive Components: None")?;
+        } else {
+            writeln!(f, "\tRecursive Components:")?;
+            for component in self.recursive_components.iter() {
+                // the cycle is indented along with the component
+                let recursion = component.recursion.to_string().replace('\t', "\t\t\t");
+                writeln!(f, "\t\t{{{}}}: {}", component.tasks.join(", "), recursion)?;
+            }
+        }
         if self.nullables.len() == 0 {
             writeln!(f, "\tNullable Tasks: None")?;
         } else {
@@ -71,6 +325,14 @@ impl Display for MetaData {
                 writeln!(f, "\t\t{}", nullable)?
             }
         }
+        if self.task_bounds.is_empty() {
+            writeln!(f, "\tMinimum Refinements: None")?;
+        } else {
+            writeln!(f, "\tMinimum Refinements:")?;
+            for bounds in self.task_bounds.iter() {
+                writeln!(f, "\t\t{}", bounds)?;
+            }
+        }
         writeln!(f, "\tNumber of actions: {}", self.n_actions)?;
         writeln!(f, "\tNumber of abstract tasks: {}", self.n_tasks)?;
         writeln!(f, "\tNumber of methods: {}", self.n_methods)?;
@@ -1,7 +1,11 @@
 mod errors;
 mod warnings;
 mod metadata;
+mod diagnostics;
+mod snippet;
 
 pub use errors::*;
 pub use warnings::*;
-pub use metadata::*;
\ No newline at end of file
+pub use metadata::*;
+pub use diagnostics::*;
+pub use snippet::*;
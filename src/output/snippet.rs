@@ -0,0 +1,121 @@
+use std::borrow::Cow;
+
+use crate::lexical_analyzer::TokenPosition;
+
+// a source location annotated with a short message
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub position: TokenPosition,
+    pub message: String,
+    // primary labels point at the offending code (^^^),
+    // secondary ones give context (---)
+    pub primary: bool,
+}
+
+impl Label {
+    pub fn primary(position: TokenPosition, message: &str) -> Label {
+        Label {
+            position,
+            message: message.to_string(),
+            primary: true,
+        }
+    }
+
+    pub fn secondary(position: TokenPosition, message: &str) -> Label {
+        Label {
+            position,
+            message: message.to_string(),
+            primary: false,
+        }
+    }
+}
+
+// renders labels as annotated source lines, in the style of rustc
+pub struct Snippet<'a> {
+    source: &'a [u8],
+    file_name: &'a str,
+}
+
+impl<'a> Snippet<'a> {
+    pub fn new(source: &'a [u8], file_name: &'a str) -> Snippet<'a> {
+        Snippet { source, file_name }
+    }
+
+    pub fn render(&self, labels: &Vec<Label>) -> String {
+        let mut labels: Vec<&Label> = labels
+            .iter()
+            .filter(|label| label.position.line != 0 && label.position.start <= self.source.len())
+            .collect();
+        if labels.is_empty() {
+            return String::new();
+        }
+        labels.sort_by_key(|label| (label.position.line, label.position.start));
+        let header = labels
+            .iter()
+            .find(|label| label.primary)
+            .unwrap_or(&labels[0])
+            .position;
+        let width = labels.last().unwrap().position.line.to_string().len();
+        let mut output = format!("{:width$}--> {}:{}\n", "", self.file_name, header);
+        output.push_str(&format!("{:width$} |\n", ""));
+        let mut last_line = None;
+        for label in labels {
+            let position = label.position;
+            let (line_start, line_end) = self.line_bounds(position.start);
+            if last_line != Some(position.line) {
+                if let Some(last_line) = last_line {
+                    if position.line > last_line + 1 {
+                        output.push_str("...\n");
+                    }
+                }
+                output.push_str(&format!(
+                    "{:>width$} | {}\n",
+                    position.line,
+                    self.text(line_start, line_end)
+                ));
+                last_line = Some(position.line);
+            }
+            // keep tabs so that the underline stays aligned
+            let padding: String = self
+                .text(line_start, position.start)
+                .chars()
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let end = position.end.min(line_end);
+            let length = if end > position.start {
+                self.text(position.start, end).chars().count()
+            } else {
+                1
+            };
+            let marker = if label.primary { "^" } else { "-" };
+            output.push_str(&format!(
+                "{:width$} | {}{} {}\n",
+                "",
+                padding,
+                marker.repeat(length),
+                label.message
+            ));
+        }
+        output
+    }
+
+    // offsets of the line containing the given offset (excluding the line break)
+    fn line_bounds(&self, offset: usize) -> (usize, usize) {
+        let start = self.source[..offset]
+            .iter()
+            .rposition(|c| *c == b'\n')
+            .map_or(0, |i| i + 1);
+        let mut end = self.source[offset..]
+            .iter()
+            .position(|c| *c == b'\n')
+            .map_or(self.source.len(), |i| offset + i);
+        if end > start && self.source[end - 1] == b'\r' {
+            end -= 1;
+        }
+        (start, end)
+    }
+
+    fn text(&self, start: usize, end: usize) -> Cow<'a, str> {
+        String::from_utf8_lossy(&self.source[start..end.max(start)])
+    }
+}
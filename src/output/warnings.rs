@@ -1,45 +1,177 @@
 use crate::lexical_analyzer::TokenPosition;
+use super::Label;
 
 #[derive(Debug, Clone)]
 pub enum WarningType {
     // Action Errors
-    // TODO: implement
-    ImmutablePredicate(String),
+    ImmutablePredicate(WarningInfo),
     // Compound Task errors
-    NoPrimitiveRefinement(WarningInfo),
+    NoPrimitiveRefinement(RefinementInfo),
     // Redundant Elements
-    // TODO: implement
-    UnusedType(String),
-    // TODO: implement
-    UnusedPredicate(String),
-    // TODO: implement
-    UnusedParameter(String),
-    // TODO: implement
-    RedundantEffect
+    UnusedType(WarningInfo),
+    UnusedTask(WarningInfo),
+    UnusedAction(WarningInfo),
+    UnusedPredicate(WarningInfo),
+    UnusedParameter(ParameterInfo),
+    UnusedRequirement(WarningInfo),
+    RedundantEffect(EffectInfo),
+    // Elements the initial task network of the problem can not reach
+    UnreachableTask(WarningInfo),
+    UnreachableMethod(WarningInfo),
+    UnreachableAction(WarningInfo),
+    // Requirement Inconsistencies
+    UndeclaredRequirement(WarningInfo),
+    UndeclaredProblemRequirement(WarningInfo),
+    UndeclaredProblemFeature(WarningInfo),
+    // Checks that could not be carried out
+    UndecidedSatisfiability(FormulaInfo),
+}
+
+impl WarningType {
+    // name of the warning variant, for machine-readable output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::ImmutablePredicate(_) => "ImmutablePredicate",
+            Self::NoPrimitiveRefinement(_) => "NoPrimitiveRefinement",
+            Self::UnusedType(_) => "UnusedType",
+            Self::UnusedTask(_) => "UnusedTask",
+            Self::UnusedAction(_) => "UnusedAction",
+            Self::UnusedPredicate(_) => "UnusedPredicate",
+            Self::UnusedParameter(_) => "UnusedParameter",
+            Self::UnusedRequirement(_) => "UnusedRequirement",
+            Self::UndeclaredRequirement(_) => "UndeclaredRequirement",
+            Self::UndeclaredProblemRequirement(_) => "UndeclaredProblemRequirement",
+            Self::UndeclaredProblemFeature(_) => "UndeclaredProblemFeature",
+            Self::RedundantEffect(_) => "RedundantEffect",
+            Self::UnreachableTask(_) => "UnreachableTask",
+            Self::UnreachableMethod(_) => "UnreachableMethod",
+            Self::UnreachableAction(_) => "UnreachableAction",
+            Self::UndecidedSatisfiability(_) => "UndecidedSatisfiability",
+        }
+    }
+
+    /// Whether the warning is a modeling smell (Sleath & Bercher), i.e. a
+    /// declaration that does not change the solutions, or a requirement that
+    /// does not match the features in use. Smells are reported as notes, and
+    /// only on request.
+    pub fn is_smell(&self) -> bool {
+        matches!(
+            self,
+            Self::ImmutablePredicate(_)
+                | Self::UnusedType(_)
+                | Self::UnusedPredicate(_)
+                | Self::UnusedParameter(_)
+                | Self::RedundantEffect(_)
+                | Self::UnusedRequirement(_)
+                | Self::UndeclaredRequirement(_)
+                | Self::UndeclaredProblemRequirement(_)
+                | Self::UndeclaredProblemFeature(_)
+        )
+    }
+
+    // whether the warning is located in the problem file (rather than the domain file)
+    pub fn is_in_problem(&self) -> bool {
+        matches!(self, Self::UndeclaredProblemRequirement(_) | Self::UndeclaredProblemFeature(_))
+    }
+
+    // source locations to be shown along with the warning message
+    pub fn labels(&self) -> Vec<Label> {
+        match self {
+            Self::NoPrimitiveRefinement(info) => {
+                vec![Label::primary(info.position, "no primitive refinement")]
+            }
+            Self::ImmutablePredicate(info) => {
+                vec![Label::primary(info.position, "never changed by an action")]
+            }
+            Self::UnusedType(info) | Self::UnusedPredicate(info) | Self::UnusedRequirement(info) => {
+                vec![Label::primary(info.position, "never used")]
+            }
+            Self::UnusedParameter(info) => {
+                vec![Label::primary(info.position, "never used")]
+            }
+            Self::UnusedTask(info) | Self::UnusedAction(info) => {
+                vec![Label::primary(info.position, "never used as a subtask")]
+            }
+            Self::UnreachableTask(info) | Self::UnreachableMethod(info) | Self::UnreachableAction(info) => {
+                vec![Label::primary(info.position, "unreachable from the initial task network")]
+            }
+            Self::UndeclaredRequirement(info) | Self::UndeclaredProblemFeature(info) => {
+                vec![Label::primary(info.position, &format!("requires {}", info.symbol))]
+            }
+            Self::UndeclaredProblemRequirement(info) => {
+                vec![Label::primary(info.position, "not declared in the domain")]
+            }
+            Self::UndecidedSatisfiability(info) => {
+                vec![Label::primary(info.position, "not checked for contradictions")]
+            }
+            Self::RedundantEffect(info) => match info.redundancy {
+                EffectRedundancy::RequiredByPrecondition(position) => vec![
+                    Label::primary(info.position, "redundant effect"),
+                    Label::secondary(position, "already required here"),
+                ],
+                EffectRedundancy::Duplicate(position) => vec![
+                    Label::primary(info.position, "redundant effect"),
+                    Label::secondary(position, "first occurrence"),
+                ],
+            },
+        }
+    }
 }
 
 impl std::fmt::Display for WarningType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
         match self {
-            Self::ImmutablePredicate(predicate) => {
-                write!(f, "Predicate {} does not appear in the effect of any action", predicate)
+            Self::ImmutablePredicate(info) => {
+                write!(f, "line {}: predicate {} does not appear in the effect of any action", info.position.line, info.symbol)
             }
             Self::NoPrimitiveRefinement(info) => {
-                write!(f, "line {}: compound task {} does not have a primitive refinement", info.position.line, info.symbol)
+                write!(f, "line {}: compound task {} does not have a primitive refinement", info.position.line, info.symbol)?;
+                write_blocking_cause(f, &info.symbol, &info.cause, 1)
+            }
+            Self::UnusedType(info) => {
+                write!(f, "line {}: type {} is declared, but never used", info.position.line, info.symbol)
+            }
+            Self::UnusedPredicate(info) => {
+                write!(f, "line {}: predicate {} is declared, but never used", info.position.line, info.symbol)
+            }
+            Self::UnusedTask(info) => {
+                write!(f, "line {}: compound task {} is declared, but never used as a subtask", info.position.line, info.symbol)
+            }
+            Self::UnusedAction(info) => {
+                write!(f, "line {}: action {} is declared, but never used as a subtask", info.position.line, info.symbol)
             }
-            Self::UnusedType(type_name) => {
-                write!(f, "Type {} is declared, but never used", type_name)
+            Self::UnreachableTask(info) => {
+                write!(f, "line {}: compound task {} can not be reached from the initial task network", info.position.line, info.symbol)
             }
-            Self::UnusedPredicate(predicate) => {
-                write!(f, "Predicate {} is declared, but never used", predicate)
+            Self::UnreachableMethod(info) => {
+                write!(f, "line {}: method {} can not be reached from the initial task network", info.position.line, info.symbol)
             }
-            Self::UnusedParameter(parameter) => {
-                write!(f, "Parameter {} is declared, but never used", parameter)
+            Self::UnreachableAction(info) => {
+                write!(f, "line {}: action {} can not be reached from the initial task network", info.position.line, info.symbol)
             }
-            Self::RedundantEffect => {
-                // TODO:
-                todo!()
+            Self::UnusedParameter(info) => {
+                write!(f, "line {}: parameter ?{} of {} is declared, but never used", info.position.line, info.parameter, info.owner)
             }
+            Self::UnusedRequirement(info) => {
+                write!(f, "line {}: requirement {} is declared, but never used", info.position.line, info.symbol)
+            }
+            Self::UndeclaredRequirement(info) | Self::UndeclaredProblemFeature(info) => {
+                write!(f, "line {}: this feature requires {}, but it is not declared", info.position.line, info.symbol)
+            }
+            Self::UndeclaredProblemRequirement(info) => {
+                write!(f, "line {}: requirement {} is not declared in the domain", info.position.line, info.symbol)
+            }
+            Self::UndecidedSatisfiability(info) => {
+                write!(f, "line {}: could not decide whether the {} of {} is satisfiable, so it was not checked for contradictions", info.position.line, info.formula, info.owner)
+            }
+            Self::RedundantEffect(info) => match info.redundancy {
+                EffectRedundancy::RequiredByPrecondition(_) => {
+                    write!(f, "line {}: effect {} of action {} is already required by its precondition", info.position.line, info.effect, info.action)
+                }
+                EffectRedundancy::Duplicate(_) => {
+                    write!(f, "line {}: effect {} of action {} is repeated", info.position.line, info.effect, info.action)
+                }
+            },
         }
     }
 }
@@ -48,4 +180,115 @@ impl std::fmt::Display for WarningType {
 pub struct WarningInfo {
     pub symbol: String,
     pub position: TokenPosition,
-}
\ No newline at end of file
+}
+
+// a compound task without a primitive refinement, along with why it has none
+#[derive(Debug, Clone)]
+pub struct RefinementInfo {
+    pub symbol: String,
+    pub position: TokenPosition,
+    pub cause: BlockingCause,
+}
+
+// why a task can not be refined, where tasks without methods and tasks that
+// only lead back to themselves are the root causes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockingCause {
+    NoMethods,
+    // every method is blocked by a task that leads back to this one
+    OnlyCycles(Vec<BlockedMethod>),
+    // every method is blocked by some task that can not be refined
+    Blocked(Vec<BlockedMethod>),
+    // the task is one that is already being explained further up the chain
+    LoopsBack,
+    // the task was already explained earlier in the chain
+    ExplainedAbove,
+}
+
+impl BlockingCause {
+    pub fn name(&self) -> &'static str {
+        match self {
+            BlockingCause::NoMethods => "NoMethods",
+            BlockingCause::OnlyCycles(_) => "OnlyCycles",
+            BlockingCause::Blocked(_) => "Blocked",
+            BlockingCause::LoopsBack => "LoopsBack",
+            BlockingCause::ExplainedAbove => "ExplainedAbove",
+        }
+    }
+
+    pub fn methods(&self) -> &[BlockedMethod] {
+        match self {
+            BlockingCause::OnlyCycles(methods) | BlockingCause::Blocked(methods) => methods,
+            _ => &[],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedMethod {
+    pub method: String,
+    // the subtasks of the method that can not be refined
+    pub blocked_subtasks: Vec<BlockedTask>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedTask {
+    pub task: String,
+    pub cause: BlockingCause,
+}
+
+// one line for the task, followed by its blocked methods (and their subtasks) indented below it
+fn write_blocking_cause(
+    f: &mut std::fmt::Formatter<'_>,
+    task: &str,
+    cause: &BlockingCause,
+    depth: usize,
+) -> std::fmt::Result {
+    let indentation = "    ".repeat(depth);
+    match cause {
+        BlockingCause::NoMethods => write!(f, "\n{}{} has no methods", indentation, task)?,
+        BlockingCause::OnlyCycles(_) => write!(f, "\n{}{} only has methods that lead back to it", indentation, task)?,
+        BlockingCause::Blocked(_) => write!(f, "\n{}{} has no refinable method", indentation, task)?,
+        BlockingCause::LoopsBack => write!(f, "\n{}{} loops back to a task above", indentation, task)?,
+        BlockingCause::ExplainedAbove => write!(f, "\n{}{} is explained above", indentation, task)?,
+    }
+    for method in cause.methods() {
+        let subtasks: Vec<&str> = method.blocked_subtasks.iter().map(|subtask| subtask.task.as_str()).collect();
+        write!(f, "\n{}    method {} is blocked by {}", indentation, method.method, subtasks.join(", "))?;
+        for subtask in method.blocked_subtasks.iter() {
+            write_blocking_cause(f, &subtask.task, &subtask.cause, depth + 2)?;
+        }
+    }
+    Ok(())
+}
+
+// a parameter of an action, a method, or a compound task
+#[derive(Debug, Clone)]
+pub struct ParameterInfo {
+    pub parameter: String,
+    pub owner: String,
+    pub position: TokenPosition,
+}
+
+// a precondition or effect (the formula) of an action or a method (the owner)
+#[derive(Debug, Clone)]
+pub struct FormulaInfo {
+    pub formula: &'static str,
+    pub owner: String,
+    pub position: TokenPosition,
+}
+
+#[derive(Debug, Clone)]
+pub struct EffectInfo {
+    pub action: String,
+    pub effect: String,
+    pub position: TokenPosition,
+    pub redundancy: EffectRedundancy,
+}
+
+// why an effect does not change the state, with the position of the literal it repeats
+#[derive(Debug, Clone)]
+pub enum EffectRedundancy {
+    RequiredByPrecondition(TokenPosition),
+    Duplicate(TokenPosition),
+}
@@ -0,0 +1,303 @@
+use std::collections::HashSet;
+
+use super::*;
+use super::verifier::{bind_term, is_satisfied};
+
+// a task of the current task network
+#[derive(Debug, Clone)]
+pub struct OpenTask<'a> {
+    pub id: usize,
+    pub name: &'a str,
+    pub arguments: Vec<&'a str>,
+    pub is_primitive: bool,
+}
+
+// a compound task that has been decomposed, in the terms of the IPC plan format
+#[derive(Debug, Clone)]
+struct Refined<'a> {
+    task: OpenTask<'a>,
+    method: &'a str,
+    subtasks: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct Snapshot<'a> {
+    tasks: Vec<OpenTask<'a>>,
+    // (before, after) pairs between tasks of the current task network
+    orderings: HashSet<(usize, usize)>,
+    state: State<'a>,
+    executed: Vec<OpenTask<'a>>,
+    refined: Vec<Refined<'a>>,
+    next_id: usize,
+}
+
+// steps through the decomposition of a problem, one method or action at a time
+pub struct Explorer<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    universe: Universe<'a>,
+    current: Snapshot<'a>,
+    history: Vec<Snapshot<'a>>,
+    root: Vec<usize>,
+}
+
+impl<'a> Explorer<'a> {
+    pub fn new(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> Explorer<'a> {
+        let universe = Universe::new(domain, problem);
+        let mut explorer = Explorer {
+            domain,
+            problem,
+            universe,
+            current: Snapshot {
+                tasks: vec![],
+                orderings: HashSet::new(),
+                state: State::new(&problem.init_state),
+                executed: vec![],
+                refined: vec![],
+                next_id: 0,
+            },
+            history: vec![],
+            root: vec![],
+        };
+        if let Some(init_tn) = &problem.init_tn {
+            explorer.root = explorer.insert_network(&init_tn.tn, &Binding::new());
+        }
+        explorer
+    }
+
+    // tasks of the current task network, in the order they were introduced
+    pub fn tasks(&self) -> &[OpenTask<'a>] {
+        &self.current.tasks
+    }
+
+    pub fn state(&self) -> &State<'a> {
+        &self.current.state
+    }
+
+    // tasks that have to be done before the given one
+    pub fn predecessors(&self, id: usize) -> Vec<usize> {
+        let mut predecessors: Vec<usize> = self
+            .current
+            .orderings
+            .iter()
+            .filter(|(_, after)| *after == id)
+            .map(|(before, _)| *before)
+            .collect();
+        predecessors.sort();
+        predecessors
+    }
+
+    // the methods of the domain that refine the given task
+    pub fn methods(&self, id: usize) -> Result<Vec<&'a Method<'a>>, PlanErrorType> {
+        let task = self.find(id)?;
+        Ok(self.domain.methods.iter().filter(|m| m.task.name == task.name).collect())
+    }
+
+    // replaces a compound task with the subtasks of a method, where the parameters that are
+    // neither given nor bound by the task are chosen to satisfy the constraints and the precondition
+    pub fn decompose(&mut self, id: usize, method: &str, bindings: &[(&str, &str)]) -> Result<(), PlanErrorType> {
+        let task = self.find(id)?.clone();
+        if task.is_primitive {
+            return Err(PlanErrorType::UndefinedTask(task.name.to_string()));
+        }
+        let Some(method) = self.domain.methods.iter().find(|m| m.name.name == method) else {
+            return Err(PlanErrorType::UndefinedMethod(method.to_string()));
+        };
+        let method_name = method.name.name;
+        if method.task.name != task.name {
+            return Err(PlanErrorType::InconsistentMethodTask(method_name.to_string(), task.name.to_string()));
+        }
+        let mut binding = Binding::new();
+        for (term, object) in method.task_terms.iter().zip(task.arguments.iter()) {
+            if !bind_term(term.name, object, &method.params, &mut binding) {
+                return Err(PlanErrorType::InconsistentMethodArgument(
+                    method_name.to_string(),
+                    term.name.to_string(),
+                ));
+            }
+        }
+        for (name, object) in bindings.iter() {
+            let name = name.trim_start_matches('?');
+            let Some(parameter) = method.params.iter().find(|p| p.name == name) else {
+                return Err(PlanErrorType::UndefinedParameter(method_name.to_string(), name.to_string()));
+            };
+            self.universe.check_object(method_name, parameter, object)?;
+            let object = self.universe.get(object).unwrap();
+            if !bind_term(parameter.name, object, &method.params, &mut binding) {
+                return Err(PlanErrorType::InconsistentMethodArgument(
+                    method_name.to_string(),
+                    parameter.name.to_string(),
+                ));
+            }
+        }
+        let no_constraints = vec![];
+        let constraints = method.tn.constraints.as_ref().unwrap_or(&no_constraints);
+        let free: Vec<&Symbol> = method
+            .params
+            .iter()
+            .filter(|parameter| !binding.contains_key(parameter.name))
+            .collect();
+        let is_consistent = |b: &Binding<'a>| constraints.iter().all(|constraint| is_satisfied(constraint, b));
+        let mut found = None;
+        self.universe.find_binding(&free, &mut binding.clone(), &mut |b| {
+            let is_applicable = is_consistent(b)
+                && method
+                    .precondition
+                    .as_ref()
+                    .is_none_or(|precondition| self.current.state.holds(precondition, b, &self.universe));
+            if is_applicable {
+                found = Some(b.clone());
+            }
+            is_applicable
+        });
+        let Some(binding) = found else {
+            let is_violated = !self.universe.find_binding(&free, &mut binding, &mut |b| is_consistent(b));
+            return Err(match is_violated || method.precondition.is_none() {
+                true => PlanErrorType::ViolatedConstraint(method_name.to_string()),
+                false => PlanErrorType::UnsatisfiedMethodPrecondition(method_name.to_string()),
+            });
+        };
+        self.history.push(self.current.clone());
+        let subtasks = self.insert_network(&method.tn, &binding);
+        // the subtasks inherit the orderings of the decomposed task
+        let inherited: Vec<(usize, usize)> = self.current.orderings.iter().copied().collect();
+        for (before, after) in inherited {
+            for subtask in subtasks.iter() {
+                if after == id {
+                    self.current.orderings.insert((before, *subtask));
+                }
+                if before == id {
+                    self.current.orderings.insert((*subtask, after));
+                }
+            }
+        }
+        self.remove(id);
+        self.current.refined.push(Refined {
+            task,
+            method: method_name,
+            subtasks,
+        });
+        Ok(())
+    }
+
+    // applies a primitive task that has no predecessors to the current state
+    pub fn execute(&mut self, id: usize) -> Result<(), PlanErrorType> {
+        let task = self.find(id)?.clone();
+        let Some(action) = self.domain.actions.iter().find(|a| a.name == task.name) else {
+            return Err(PlanErrorType::UndefinedAction(task.name.to_string()));
+        };
+        if let Some(before) = self.predecessors(id).first() {
+            return Err(PlanErrorType::ViolatedOrdering(*before, id));
+        }
+        let binding = self.universe.bind(action.name, &action.parameters, &task.arguments)?;
+        let state = self.current.state.progress(action, &binding, &self.universe)?;
+        self.history.push(self.current.clone());
+        self.current.state = state;
+        self.remove(id);
+        self.current.executed.push(task);
+        Ok(())
+    }
+
+    // reverts the last decomposition or execution, returning false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(snapshot) => {
+                self.current = snapshot;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // literals of the goal that do not hold in the current state
+    pub fn unsatisfied_goal(&self) -> Vec<String> {
+        match &self.problem.goal {
+            Some(goal) => self.current.state.unsatisfied(goal, &Binding::new(), &self.universe),
+            None => vec![],
+        }
+    }
+
+    // the plan so far in the IPC format, where tasks that are not refined yet are listed as comments
+    pub fn partial_plan(&self) -> String {
+        let arguments = |task: &OpenTask| {
+            task.arguments.iter().map(|argument| format!(" {}", argument)).collect::<String>()
+        };
+        let mut lines = vec!["==>".to_string()];
+        for task in self.current.executed.iter() {
+            lines.push(format!("{} {}{}", task.id, task.name, arguments(task)));
+        }
+        let root: Vec<String> = self.root.iter().map(|id| id.to_string()).collect();
+        lines.push(format!("root {}", root.join(" ")).trim_end().to_string());
+        for refined in self.current.refined.iter() {
+            let subtasks: Vec<String> = refined.subtasks.iter().map(|id| id.to_string()).collect();
+            lines.push(
+                format!(
+                    "{} {}{} -> {} {}",
+                    refined.task.id,
+                    refined.task.name,
+                    arguments(&refined.task),
+                    refined.method,
+                    subtasks.join(" ")
+                )
+                .trim_end()
+                .to_string(),
+            );
+        }
+        for task in self.current.tasks.iter() {
+            lines.push(format!("; open: {} {}{}", task.id, task.name, arguments(task)));
+        }
+        lines.push("<==".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    fn find(&self, id: usize) -> Result<&OpenTask<'a>, PlanErrorType> {
+        self.current
+            .tasks
+            .iter()
+            .find(|task| task.id == id)
+            .ok_or(PlanErrorType::UndefinedStepId(id))
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.current.tasks.retain(|task| task.id != id);
+        self.current.orderings.retain(|(before, after)| *before != id && *after != id);
+    }
+
+    // adds the (grounded) subtasks of a task network, returning their ids
+    fn insert_network(&mut self, tn: &'a HTN<'a>, binding: &Binding<'a>) -> Vec<usize> {
+        let first = self.current.next_id;
+        for subtask in tn.subtasks.iter() {
+            self.current.tasks.push(OpenTask {
+                id: self.current.next_id,
+                name: subtask.task.name,
+                arguments: subtask.terms.iter().map(|term| resolve(term.name, binding)).collect(),
+                is_primitive: self.domain.actions.iter().any(|a| a.name == subtask.task.name),
+            });
+            self.current.next_id += 1;
+        }
+        let ids: Vec<usize> = (first..self.current.next_id).collect();
+        match &tn.orderings {
+            TaskOrdering::Total => {
+                for i in 0..ids.len() {
+                    for j in i + 1..ids.len() {
+                        self.current.orderings.insert((ids[i], ids[j]));
+                    }
+                }
+            }
+            TaskOrdering::Partial(orderings) => {
+                let id_of = |label: &str| {
+                    tn.subtasks
+                        .iter()
+                        .position(|subtask| subtask.id.as_ref().is_some_and(|s| s.name == label))
+                        .map(|index| ids[index])
+                };
+                for (before, after) in orderings.iter() {
+                    if let (Some(before), Some(after)) = (id_of(before), id_of(after)) {
+                        self.current.orderings.insert((before, after));
+                    }
+                }
+            }
+        }
+        ids
+    }
+}
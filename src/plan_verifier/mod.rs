@@ -0,0 +1,18 @@
+mod explorer;
+mod plan;
+mod simulator;
+mod state;
+mod verifier;
+#[cfg(test)]
+mod tests;
+
+use crate::syntactic_analyzer::*;
+use crate::output::*;
+use crate::lexical_analyzer::TokenPosition;
+use crate::semantic_analyzer::TypeChecker;
+
+pub use explorer::{Explorer, OpenTask};
+pub use plan::*;
+pub use simulator::Simulator;
+pub use state::*;
+pub use verifier::PlanVerifier;
@@ -0,0 +1,162 @@
+use super::*;
+
+// a primitive step of the plan, e.g. "3 move truck loc1 loc2"
+#[derive(Debug)]
+pub struct PlanStep<'p> {
+    pub id: usize,
+    pub name: &'p str,
+    pub arguments: Vec<&'p str>,
+    pub position: TokenPosition,
+}
+
+// a decomposition line, e.g. "5 deliver p1 loc2 -> m_deliver 2 3 7"
+#[derive(Debug)]
+pub struct Decomposition<'p> {
+    pub id: usize,
+    pub task: &'p str,
+    pub arguments: Vec<&'p str>,
+    pub method: &'p str,
+    pub subtasks: Vec<usize>,
+    pub position: TokenPosition,
+}
+
+// a plan in the IPC 2020 HTN format
+#[derive(Debug)]
+pub struct Plan<'p> {
+    // primitive steps in their execution order
+    pub primitives: Vec<PlanStep<'p>>,
+    pub root: Vec<usize>,
+    pub root_position: Option<TokenPosition>,
+    pub decompositions: Vec<Decomposition<'p>>,
+}
+
+impl<'p> Plan<'p> {
+    pub fn parse(program: &'p str) -> Result<Plan<'p>, PlanError> {
+        let mut plan = Plan {
+            primitives: vec![],
+            root: vec![],
+            root_position: None,
+            decompositions: vec![],
+        };
+        let mut is_started = false;
+        let mut offset = 0;
+        for (index, line) in program.split('\n').enumerate() {
+            let position = TokenPosition {
+                line: index as u32 + 1,
+                column: 1,
+                start: offset,
+                end: offset + line.trim_end().len(),
+            };
+            offset += line.len() + 1;
+            let line = line.trim();
+            // planners may print anything before the plan starts
+            if !is_started {
+                is_started = line == "==>";
+                continue;
+            }
+            if line == "<==" {
+                break;
+            }
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            let malformed = || PlanError {
+                error_type: PlanErrorType::MalformedLine(line.to_string()),
+                step: None,
+                position: Some(position),
+            };
+            let mut tokens = line.split_whitespace();
+            let head = tokens.next().unwrap();
+            if head == "root" {
+                for id in tokens {
+                    plan.root.push(id.parse().map_err(|_| malformed())?);
+                }
+                plan.root_position = Some(position);
+                continue;
+            }
+            let id: usize = head.parse().map_err(|_| malformed())?;
+            let tokens: Vec<&str> = tokens.collect();
+            match tokens.iter().position(|token| *token == "->") {
+                Some(arrow) => {
+                    if arrow == 0 || arrow + 1 == tokens.len() {
+                        return Err(malformed());
+                    }
+                    let mut subtasks = vec![];
+                    for subtask in tokens[arrow + 2..].iter() {
+                        subtasks.push(subtask.parse().map_err(|_| malformed())?);
+                    }
+                    plan.decompositions.push(Decomposition {
+                        id,
+                        task: tokens[0],
+                        arguments: tokens[1..arrow].to_vec(),
+                        method: tokens[arrow + 1],
+                        subtasks,
+                        position,
+                    });
+                }
+                None => {
+                    if tokens.is_empty() {
+                        return Err(malformed());
+                    }
+                    plan.primitives.push(PlanStep {
+                        id,
+                        name: tokens[0],
+                        arguments: tokens[1..].to_vec(),
+                        position,
+                    });
+                }
+            }
+        }
+        if !is_started {
+            return Err(PlanError {
+                error_type: PlanErrorType::MissingPlanMarker,
+                step: None,
+                position: None,
+            });
+        }
+        Ok(plan)
+    }
+
+    // a sequence of primitive actions, one per line, e.g. "(move truck loc1 loc2)", or
+    // the primitive steps of a plan in the IPC 2020 HTN format
+    pub fn parse_actions(program: &'p str) -> Result<Vec<PlanStep<'p>>, PlanError> {
+        if program.lines().any(|line| line.trim() == "==>") {
+            return Ok(Plan::parse(program)?.primitives);
+        }
+        let mut steps = vec![];
+        let mut offset = 0;
+        for (index, line) in program.split('\n').enumerate() {
+            let position = TokenPosition {
+                line: index as u32 + 1,
+                column: 1,
+                start: offset,
+                end: offset + line.trim_end().len(),
+            };
+            offset += line.len() + 1;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+            let malformed = || PlanError {
+                error_type: PlanErrorType::MalformedLine(line.to_string()),
+                step: None,
+                position: Some(position),
+            };
+            let action = match line.strip_prefix('(') {
+                Some(rest) => rest.strip_suffix(')').ok_or_else(malformed)?,
+                None => line,
+            };
+            let tokens: Vec<&str> = action.split_whitespace().collect();
+            if tokens.is_empty() {
+                return Err(malformed());
+            }
+            steps.push(PlanStep {
+                id: steps.len() + 1,
+                name: tokens[0],
+                arguments: tokens[1..].to_vec(),
+                position,
+            });
+        }
+        Ok(steps)
+    }
+}
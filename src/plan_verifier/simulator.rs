@@ -0,0 +1,44 @@
+use super::*;
+
+// executes primitive actions one at a time, starting from the initial state of a problem
+pub struct Simulator<'a> {
+    domain: &'a DomainAST<'a>,
+    universe: Universe<'a>,
+    state: State<'a>,
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> Simulator<'a> {
+        Simulator {
+            domain,
+            universe: Universe::new(domain, problem),
+            state: State::new(&problem.init_state),
+        }
+    }
+
+    pub fn state(&self) -> &State<'a> {
+        &self.state
+    }
+
+    // applies a ground action to the current state, which is left unchanged if the action is not applicable
+    pub fn step(&mut self, action: &str, arguments: &[&'a str]) -> Result<&State<'a>, PlanErrorType> {
+        let Some(action) = self.domain.actions.iter().find(|a| a.name == action) else {
+            return Err(PlanErrorType::UndefinedAction(action.to_string()));
+        };
+        let binding = self.universe.bind(action.name, &action.parameters, arguments)?;
+        self.state = self.state.progress(action, &binding, &self.universe)?;
+        Ok(&self.state)
+    }
+
+    // applies the steps in order, stopping at the first one that can not be executed
+    pub fn run(&mut self, steps: &[PlanStep<'a>]) -> Result<&State<'a>, PlanError> {
+        for step in steps.iter() {
+            self.step(step.name, &step.arguments).map_err(|error_type| PlanError {
+                error_type,
+                step: Some(step.id),
+                position: Some(step.position),
+            })?;
+        }
+        Ok(&self.state)
+    }
+}
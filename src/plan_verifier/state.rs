@@ -0,0 +1,352 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::*;
+
+// maps parameters (and quantified variables) to objects
+pub type Binding<'a> = HashMap<&'a str, &'a str>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GroundAtom<'a> {
+    pub predicate: &'a str,
+    pub arguments: Vec<&'a str>,
+}
+
+impl<'a> GroundAtom<'a> {
+    pub fn new(predicate: &'a Predicate<'a>, binding: &Binding<'a>) -> GroundAtom<'a> {
+        GroundAtom {
+            predicate: predicate.name,
+            arguments: predicate
+                .variables
+                .iter()
+                .map(|var| resolve(var.name, binding))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for GroundAtom<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}", self.predicate)?;
+        for argument in self.arguments.iter() {
+            write!(f, " {}", argument)?;
+        }
+        write!(f, ")")
+    }
+}
+
+// a term is either a bound variable or a constant
+pub fn resolve<'a>(term: &'a str, binding: &Binding<'a>) -> &'a str {
+    match binding.get(term) {
+        Some(object) => object,
+        None => term,
+    }
+}
+
+// a formula in HDDL syntax, with its bound variables replaced by objects
+pub fn ground<'a>(formula: &'a Formula<'a>, binding: &Binding<'a>) -> String {
+    ground_in_scope(formula, binding, &[])
+}
+
+// variables in scope are quantified inside the formula, so they are kept as variables
+fn ground_in_scope<'a>(formula: &'a Formula<'a>, binding: &Binding<'a>, scope: &[&'a str]) -> String {
+    let term = |name: &'a str| match scope.contains(&name) {
+        true => format!("?{}", name),
+        false => resolve(name, binding).to_string(),
+    };
+    let list = |formulas: &'a Vec<Box<Formula<'a>>>| {
+        formulas
+            .iter()
+            .map(|f| ground_in_scope(f, binding, scope))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    match formula {
+        Formula::Empty => "()".to_string(),
+        Formula::Atom(predicate) => {
+            let mut text = format!("({}", predicate.name);
+            for var in predicate.variables.iter() {
+                text.push(' ');
+                text.push_str(&term(var.name));
+            }
+            text + ")"
+        }
+        Formula::Not(f) => format!("(not {})", ground_in_scope(f, binding, scope)),
+        Formula::And(fs) => format!("(and {})", list(fs)),
+        Formula::Or(fs) => format!("(or {})", list(fs)),
+        Formula::Xor(fs) => format!("(oneof {})", list(fs)),
+        Formula::Imply(antecedents, consequences) => {
+            format!("(imply (and {}) (and {}))", list(antecedents), list(consequences))
+        }
+        Formula::Exists(vars, f) | Formula::ForAll(vars, f) => {
+            let quantifier = match formula {
+                Formula::Exists(_, _) => "exists",
+                _ => "forall",
+            };
+            let mut inner = scope.to_vec();
+            inner.extend(vars.iter().map(|var| var.name));
+            let vars: Vec<String> = vars
+                .iter()
+                .map(|var| format!("?{} - {}", var.name, var.symbol_type.unwrap_or("object")))
+                .collect();
+            format!("({} ({}) {})", quantifier, vars.join(" "), ground_in_scope(f, binding, &inner))
+        }
+        Formula::Equals(a, b) => format!("(= {} {})", term(a.name), term(b.name)),
+    }
+}
+
+// typed objects of a problem (including the constants of its domain)
+pub struct Universe<'a> {
+    objects: Vec<(&'a str, Option<&'a str>)>,
+    type_checker: TypeChecker<'a>,
+}
+
+impl<'a> Universe<'a> {
+    pub fn new(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> Universe<'a> {
+        Universe::with_type_checker(domain, problem, TypeChecker::new(&domain.types))
+    }
+
+    pub fn with_type_checker(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        type_checker: TypeChecker<'a>,
+    ) -> Universe<'a> {
+        let mut objects = vec![];
+        if let Some(constants) = &domain.constants {
+            objects.extend(constants.iter().map(|c| (c.name, c.symbol_type)));
+        }
+        objects.extend(problem.objects.iter().map(|o| (o.name, o.symbol_type)));
+        Universe {
+            objects,
+            type_checker,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        self.objects
+            .iter()
+            .find(|(object, _)| *object == name)
+            .map(|(object, _)| *object)
+    }
+
+    pub fn is_of_type(&self, object: &str, expected: Option<&'a str>) -> bool {
+        let Some((_, found)) = self.objects.iter().find(|(o, _)| *o == object) else {
+            return false;
+        };
+        match expected {
+            // untyped parameters accept every object
+            None | Some("object") => true,
+            _ => self.type_checker.is_var_type_consistent(*found, expected),
+        }
+    }
+
+    pub fn objects_of_type(&self, expected: Option<&'a str>) -> Vec<&'a str> {
+        self.objects
+            .iter()
+            .filter(|(object, _)| self.is_of_type(object, expected))
+            .map(|(object, _)| *object)
+            .collect()
+    }
+
+    // binds the parameters of an action or a task to the objects of a step
+    pub fn bind(
+        &self,
+        symbol: &str,
+        parameters: &'a Vec<Symbol<'a>>,
+        arguments: &[&'a str],
+    ) -> Result<Binding<'a>, PlanErrorType> {
+        if parameters.len() != arguments.len() {
+            return Err(PlanErrorType::InconsistentArity(Box::new(PlanArityError {
+                symbol: symbol.to_string(),
+                expected_arity: parameters.len() as u32,
+                found_arity: arguments.len() as u32,
+            })));
+        }
+        let mut binding = Binding::new();
+        for (parameter, object) in parameters.iter().zip(arguments.iter()) {
+            self.check_object(symbol, parameter, object)?;
+            binding.insert(parameter.name, object);
+        }
+        Ok(binding)
+    }
+
+    pub fn check_object(&self, symbol: &str, parameter: &Symbol<'a>, object: &str) -> Result<(), PlanErrorType> {
+        if self.get(object).is_none() {
+            return Err(PlanErrorType::UndefinedObject(object.to_string()));
+        }
+        if !self.is_of_type(object, parameter.symbol_type) {
+            return Err(PlanErrorType::InconsistentArgType(Box::new(PlanTypeError {
+                symbol: symbol.to_string(),
+                parameter: parameter.name.to_string(),
+                object: object.to_string(),
+                expected: parameter.symbol_type.unwrap_or("object").to_string(),
+            })));
+        }
+        Ok(())
+    }
+
+    // every extension of the binding to the given variables that satisfies the predicate
+    pub fn find_binding(
+        &self,
+        variables: &[&Symbol<'a>],
+        binding: &mut Binding<'a>,
+        is_valid: &mut dyn FnMut(&Binding<'a>) -> bool,
+    ) -> bool {
+        match variables.split_first() {
+            None => is_valid(binding),
+            Some((variable, rest)) => {
+                for object in self.objects_of_type(variable.symbol_type) {
+                    binding.insert(variable.name, object);
+                    if self.find_binding(rest, binding, is_valid) {
+                        return true;
+                    }
+                }
+                binding.remove(variable.name);
+                false
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct State<'a> {
+    pub atoms: HashSet<GroundAtom<'a>>,
+}
+
+impl<'a> State<'a> {
+    pub fn new(init_state: &'a Vec<Predicate<'a>>) -> State<'a> {
+        let binding = Binding::new();
+        State {
+            atoms: init_state
+                .iter()
+                .map(|predicate| GroundAtom::new(predicate, &binding))
+                .collect(),
+        }
+    }
+
+    pub fn holds(&self, formula: &'a Formula<'a>, binding: &Binding<'a>, universe: &Universe<'a>) -> bool {
+        match formula {
+            Formula::Empty => true,
+            Formula::Atom(predicate) => self.atoms.contains(&GroundAtom::new(predicate, binding)),
+            Formula::Not(f) => !self.holds(f, binding, universe),
+            Formula::And(fs) => fs.iter().all(|f| self.holds(f, binding, universe)),
+            Formula::Or(fs) => fs.iter().any(|f| self.holds(f, binding, universe)),
+            // exactly one of the formulas holds
+            Formula::Xor(fs) => fs.iter().filter(|f| self.holds(f, binding, universe)).count() == 1,
+            Formula::Imply(antecedents, consequences) => {
+                !antecedents.iter().all(|f| self.holds(f, binding, universe))
+                    || consequences.iter().all(|f| self.holds(f, binding, universe))
+            }
+            Formula::Exists(vars, f) => {
+                let vars: Vec<&Symbol> = vars.iter().collect();
+                let mut binding = binding.clone();
+                universe.find_binding(&vars, &mut binding, &mut |b| self.holds(f, b, universe))
+            }
+            Formula::ForAll(vars, f) => {
+                let vars: Vec<&Symbol> = vars.iter().collect();
+                let mut binding = binding.clone();
+                // a universal statement holds iff there is no counterexample
+                !universe.find_binding(&vars, &mut binding, &mut |b| !self.holds(f, b, universe))
+            }
+            Formula::Equals(a, b) => resolve(a.name, binding) == resolve(b.name, binding),
+        }
+    }
+
+    // the (grounded) literals of a formula that do not hold
+    pub fn unsatisfied(&self, formula: &'a Formula<'a>, binding: &Binding<'a>, universe: &Universe<'a>) -> Vec<String> {
+        match formula {
+            Formula::And(fs) => fs
+                .iter()
+                .flat_map(|f| self.unsatisfied(f, binding, universe))
+                .collect(),
+            Formula::Atom(predicate) if !self.holds(formula, binding, universe) => {
+                vec![GroundAtom::new(predicate, binding).to_string()]
+            }
+            // a universal statement is reported by the literals of its first counterexample
+            Formula::ForAll(vars, f) => {
+                let vars: Vec<&Symbol> = vars.iter().collect();
+                let mut literals = vec![];
+                universe.find_binding(&vars, &mut binding.clone(), &mut |b| {
+                    literals = self.unsatisfied(f, b, universe);
+                    !literals.is_empty()
+                });
+                literals
+            }
+            _ if !self.holds(formula, binding, universe) => vec![ground(formula, binding)],
+            _ => vec![],
+        }
+    }
+
+    // executes a ground action, failing with the literals of its precondition that do not hold
+    pub fn progress(
+        &self,
+        action: &'a Action<'a>,
+        binding: &Binding<'a>,
+        universe: &Universe<'a>,
+    ) -> Result<State<'a>, PlanErrorType> {
+        if let Some(precondition) = &action.preconditions {
+            let literals = self.unsatisfied(precondition, binding, universe);
+            if !literals.is_empty() {
+                return Err(PlanErrorType::UnsatisfiedPrecondition(action.name.to_string(), literals));
+            }
+        }
+        Ok(match &action.effects {
+            Some(effects) => self.apply(effects, binding, universe),
+            None => self.clone(),
+        })
+    }
+
+    // the successor state, where delete effects are applied before add effects
+    pub fn apply(&self, effect: &'a Formula<'a>, binding: &Binding<'a>, universe: &Universe<'a>) -> State<'a> {
+        let mut added = vec![];
+        let mut deleted = vec![];
+        self.collect_effects(effect, binding, universe, &mut added, &mut deleted);
+        let mut atoms = self.atoms.clone();
+        for atom in deleted.iter() {
+            atoms.remove(atom);
+        }
+        atoms.extend(added);
+        State { atoms }
+    }
+
+    fn collect_effects(
+        &self,
+        effect: &'a Formula<'a>,
+        binding: &Binding<'a>,
+        universe: &Universe<'a>,
+        added: &mut Vec<GroundAtom<'a>>,
+        deleted: &mut Vec<GroundAtom<'a>>,
+    ) {
+        match effect {
+            Formula::Atom(predicate) => added.push(GroundAtom::new(predicate, binding)),
+            Formula::Not(f) => {
+                if let Formula::Atom(predicate) = &**f {
+                    deleted.push(GroundAtom::new(predicate, binding));
+                }
+            }
+            Formula::And(fs) => {
+                for f in fs.iter() {
+                    self.collect_effects(f, binding, universe, added, deleted);
+                }
+            }
+            Formula::ForAll(vars, f) => {
+                let vars: Vec<&Symbol> = vars.iter().collect();
+                let mut binding = binding.clone();
+                universe.find_binding(&vars, &mut binding, &mut |b| {
+                    self.collect_effects(f, b, universe, added, deleted);
+                    // keep enumerating
+                    false
+                });
+            }
+            // conditional effects are conditioned on the state before the action
+            Formula::Imply(antecedents, consequences) => {
+                if antecedents.iter().all(|f| self.holds(f, binding, universe)) {
+                    for f in consequences.iter() {
+                        self.collect_effects(f, binding, universe, added, deleted);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use super::*;
+use crate::test_fixtures::*;
+
+fn with_explorer(f: impl Fn(&mut Explorer)) {
+    let problem = delivery_problem(
+        "p1 p2 - package a b c - location",
+        ":subtasks (and (task0 (deliver_both p1 p2 c)))",
+        "(at p1 a) (at p2 b) (road a c) (road b c)",
+        "(at p1 c) (at p2 c)",
+    );
+    with_delivery(&problem, |d, p| f(&mut Explorer::new(d, p)))
+}
+
+fn task_names(explorer: &Explorer) -> Vec<String> {
+    explorer
+        .tasks()
+        .iter()
+        .map(|task| format!("{} {} {}", task.id, task.name, task.arguments.join(" ")))
+        .collect()
+}
+
+#[test]
+pub fn explorer_decomposition_test() {
+    with_explorer(|explorer| {
+        assert_eq!(task_names(explorer), vec!["0 deliver_both p1 p2 c"]);
+        assert_eq!(explorer.methods(0).unwrap().len(), 1);
+        explorer.decompose(0, "m_both", &[]).unwrap();
+        assert_eq!(task_names(explorer), vec!["1 deliver p1 c", "2 deliver p2 c"]);
+        assert_eq!(explorer.predecessors(2), vec![1]);
+        // the free parameter ?from is chosen by the precondition
+        explorer.decompose(1, "m_direct", &[]).unwrap();
+        assert_eq!(task_names(explorer), vec!["2 deliver p2 c", "3 move p1 a c"]);
+        // the subtasks inherit the orderings of the decomposed task
+        assert_eq!(explorer.predecessors(2), vec![3]);
+        assert!(matches!(explorer.execute(2), Err(PlanErrorType::UndefinedAction(_))));
+        explorer.execute(3).unwrap();
+        explorer.decompose(2, "m_direct", &[("?from", "b")]).unwrap();
+        assert_eq!(explorer.unsatisfied_goal(), vec!["(at p2 c)"]);
+        explorer.execute(4).unwrap();
+        assert!(explorer.tasks().is_empty());
+        assert!(explorer.unsatisfied_goal().is_empty());
+        assert_eq!(
+            explorer.partial_plan(),
+            "==>\n3 move p1 a c\n4 move p2 b c\nroot 0\n0 deliver_both p1 p2 c -> m_both 1 2\n\
+             1 deliver p1 c -> m_direct 3\n2 deliver p2 c -> m_direct 4\n<==\n"
+        );
+        // the partial plan can be read back as an IPC plan
+        let plan = explorer.partial_plan();
+        assert_eq!(Plan::parse(&plan).unwrap().decompositions.len(), 3);
+    });
+}
+
+#[test]
+pub fn explorer_errors_test() {
+    with_explorer(|explorer| {
+        assert!(matches!(explorer.execute(0), Err(PlanErrorType::UndefinedAction(_))));
+        assert!(matches!(explorer.decompose(7, "m_both", &[]), Err(PlanErrorType::UndefinedStepId(7))));
+        assert!(matches!(explorer.decompose(0, "m_none", &[]), Err(PlanErrorType::UndefinedMethod(_))));
+        assert!(matches!(explorer.decompose(0, "m_direct", &[]), Err(PlanErrorType::InconsistentMethodTask(_, _))));
+        explorer.decompose(0, "m_both", &[]).unwrap();
+        assert!(matches!(
+            explorer.decompose(1, "m_direct", &[("?x", "a")]),
+            Err(PlanErrorType::UndefinedParameter(_, _))
+        ));
+        assert!(matches!(
+            explorer.decompose(1, "m_direct", &[("?from", "p2")]),
+            Err(PlanErrorType::InconsistentArgType(_))
+        ));
+        assert!(matches!(
+            explorer.decompose(1, "m_direct", &[("?to", "a")]),
+            Err(PlanErrorType::InconsistentMethodArgument(_, _))
+        ));
+        assert!(matches!(
+            explorer.decompose(1, "m_direct", &[("?from", "b")]),
+            Err(PlanErrorType::UnsatisfiedMethodPrecondition(_))
+        ));
+        assert!(matches!(explorer.decompose(1, "m_stay", &[]), Err(PlanErrorType::ViolatedConstraint(_))));
+        // failed steps do not change the task network
+        assert_eq!(task_names(explorer), vec!["1 deliver p1 c", "2 deliver p2 c"]);
+        explorer.decompose(2, "m_direct", &[]).unwrap();
+        assert!(matches!(explorer.execute(3), Err(PlanErrorType::ViolatedOrdering(1, 3))));
+        assert!(explorer.undo());
+        assert!(explorer.undo());
+        assert!(!explorer.undo());
+        assert_eq!(task_names(explorer), vec!["0 deliver_both p1 p2 c"]);
+    });
+}
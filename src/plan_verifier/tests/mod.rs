@@ -0,0 +1,7 @@
+mod plan_parsing_tests;
+mod verifier_tests;
+mod simulator_tests;
+mod explorer_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
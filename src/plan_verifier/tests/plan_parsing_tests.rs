@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+pub fn plan_parsing_test() {
+    let program = "some planner output\n\
+                   ==>\n\
+                   0 move p1 a c\n\
+                   ; a comment\n\
+                   root 2\n\
+                   2 deliver p1 c -> m_direct 0\n\
+                   <==\n\
+                   ignored\n";
+    let plan = Plan::parse(program).unwrap();
+    assert_eq!(plan.primitives.len(), 1);
+    assert_eq!(plan.primitives[0].id, 0);
+    assert_eq!(plan.primitives[0].name, "move");
+    assert_eq!(plan.primitives[0].arguments, vec!["p1", "a", "c"]);
+    assert_eq!(plan.primitives[0].position.line, 3);
+    assert_eq!(plan.root, vec![2]);
+    assert_eq!(plan.decompositions.len(), 1);
+    let decomposition = &plan.decompositions[0];
+    assert_eq!(decomposition.task, "deliver");
+    assert_eq!(decomposition.arguments, vec!["p1", "c"]);
+    assert_eq!(decomposition.method, "m_direct");
+    assert_eq!(decomposition.subtasks, vec![0]);
+    assert_eq!(decomposition.position.line, 6);
+}
+
+#[test]
+pub fn malformed_plan_test() {
+    match Plan::parse("0 move p1 a c\n") {
+        Err(PlanError { error_type: PlanErrorType::MissingPlanMarker, .. }) => {}
+        result => panic!("expected missing marker, found {:?}", result),
+    }
+    match Plan::parse("==>\nmove p1 a c\n") {
+        Err(PlanError { error_type: PlanErrorType::MalformedLine(line), position: Some(position), .. }) => {
+            assert_eq!(line, "move p1 a c");
+            assert_eq!(position.line, 2);
+        }
+        result => panic!("expected malformed line, found {:?}", result),
+    }
+    match Plan::parse("==>\n1 deliver p1 c -> m_direct x\n") {
+        Err(PlanError { error_type: PlanErrorType::MalformedLine(_), .. }) => {}
+        result => panic!("expected malformed line, found {:?}", result),
+    }
+}
@@ -0,0 +1,158 @@
+use super::*;
+
+const DOMAIN: &str = "(define (domain lights)
+    (:requirements :typing :negative-preconditions :disjunctive-preconditions :equality
+        :universal-preconditions :existential-preconditions :conditional-effects)
+    (:types room lamp - object)
+    (:predicates
+        (in ?l - lamp ?r - room)
+        (on ?l - lamp)
+        (at ?r - room)
+        (door ?a ?b - room)
+        (open ?a ?b - room)
+    )
+    (:action walk
+     :parameters (?from ?to - room)
+     :precondition (and (at ?from) (not (= ?from ?to)) (or (door ?from ?to) (open ?from ?to)))
+     :effect (and (not (at ?from)) (at ?to))
+    )
+    (:action switch_all
+     :parameters (?r - room)
+     :precondition (and (at ?r) (exists (?l - lamp) (in ?l ?r)))
+     :effect (forall (?l - lamp) (when (in ?l ?r) (on ?l)))
+    )
+    (:action leave
+     :parameters (?r - room)
+     :precondition (and (at ?r) (forall (?l - lamp) (or (not (in ?l ?r)) (on ?l))))
+     :effect (not (at ?r))
+    )
+    (:action inspect
+     :parameters (?a ?b ?c - lamp)
+     :precondition (oneof (on ?a) (on ?b) (on ?c))
+     :effect (on ?a)
+    )
+)";
+
+const PROBLEM: &str = "(define (problem p) (:domain lights)
+    (:objects hall kitchen attic - room l1 l2 l3 - lamp)
+    (:init (at hall) (door hall kitchen) (in l1 kitchen) (in l2 kitchen) (in l3 hall))
+)";
+
+fn simulate(actions: &str) -> Result<Vec<String>, PlanError> {
+    let domain = DOMAIN.as_bytes().to_vec();
+    let problem = PROBLEM.as_bytes().to_vec();
+    let domain_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let steps = Plan::parse_actions(actions)?;
+            let mut simulator = Simulator::new(&d, &p);
+            let mut facts: Vec<String> = simulator.run(&steps)?.atoms.iter().map(|a| a.to_string()).collect();
+            facts.sort();
+            Ok(facts)
+        }
+        _ => panic!("expected a domain and a problem"),
+    }
+}
+
+#[test]
+pub fn simulate_effects_test() {
+    let facts = simulate("(walk hall kitchen)\n(switch_all kitchen)\n(leave kitchen)\n").unwrap();
+    assert_eq!(
+        facts,
+        vec![
+            "(door hall kitchen)",
+            "(in l1 kitchen)",
+            "(in l2 kitchen)",
+            "(in l3 hall)",
+            "(on l1)",
+            "(on l2)",
+        ]
+    );
+    // the primitive steps of an IPC plan are accepted as well
+    let facts = simulate("==>\n0 walk hall kitchen\n1 switch_all kitchen\nroot\n<==\n").unwrap();
+    assert!(facts.contains(&"(on l1)".to_string()));
+    assert!(!facts.contains(&"(on l3)".to_string()));
+}
+
+#[test]
+pub fn simulate_unsatisfied_precondition_test() {
+    let error = simulate("; first step\n(walk hall kitchen)\n(walk hall kitchen)\n").unwrap_err();
+    assert_eq!(error.step, Some(2));
+    assert_eq!(error.position.unwrap().line, 3);
+    match error.error_type {
+        PlanErrorType::UnsatisfiedPrecondition(action, literals) => {
+            assert_eq!(action, "walk");
+            assert_eq!(literals, vec!["(at hall)"]);
+        }
+        error => panic!("{:?}", error),
+    }
+    // doors are one-way
+    let error = simulate("(walk hall kitchen)\n(walk kitchen hall)\n").unwrap_err();
+    match error.error_type {
+        PlanErrorType::UnsatisfiedPrecondition(_, literals) => {
+            assert_eq!(literals, vec!["(or (door kitchen hall) (open kitchen hall))"]);
+        }
+        error => panic!("{:?}", error),
+    }
+    // the lamps of the kitchen are off
+    let error = simulate("(walk hall kitchen)\n(leave kitchen)\n").unwrap_err();
+    assert_eq!(error.step, Some(2));
+    match error.error_type {
+        PlanErrorType::UnsatisfiedPrecondition(_, literals) => {
+            assert_eq!(literals, vec!["(or (not (in l1 kitchen)) (on l1))"]);
+        }
+        error => panic!("{:?}", error),
+    }
+    let error = simulate("(switch_all attic)").unwrap_err();
+    match error.error_type {
+        PlanErrorType::UnsatisfiedPrecondition(_, literals) => {
+            assert_eq!(literals, vec!["(at attic)", "(exists (?l - lamp) (in ?l attic))"]);
+        }
+        error => panic!("{:?}", error),
+    }
+    let error = simulate("(walk hall hall)").unwrap_err();
+    match error.error_type {
+        PlanErrorType::UnsatisfiedPrecondition(_, literals) => {
+            assert_eq!(literals, vec!["(not (= hall hall))", "(or (door hall hall) (open hall hall))"]);
+        }
+        error => panic!("{:?}", error),
+    }
+    assert!(simulate("(switch_all kitchen)").is_err());
+    assert!(simulate("(switch_all hall)\n(leave hall)").is_ok());
+}
+
+#[test]
+pub fn simulator_step_test() {
+    let domain = DOMAIN.as_bytes().to_vec();
+    let problem = PROBLEM.as_bytes().to_vec();
+    let domain_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let mut simulator = Simulator::new(&d, &p);
+            assert!(matches!(simulator.step("fly", &["hall"]), Err(PlanErrorType::UndefinedAction(_))));
+            assert!(matches!(simulator.step("walk", &["hall"]), Err(PlanErrorType::InconsistentArity(_))));
+            assert!(matches!(simulator.step("walk", &["hall", "l1"]), Err(PlanErrorType::InconsistentArgType(_))));
+            assert!(matches!(simulator.step("walk", &["hall", "cellar"]), Err(PlanErrorType::UndefinedObject(_))));
+            // failed steps leave the state unchanged
+            assert!(matches!(simulator.step("leave", &["kitchen"]), Err(PlanErrorType::UnsatisfiedPrecondition(_, _))));
+            assert_eq!(simulator.state().atoms.len(), 5);
+            let state = simulator.step("walk", &["hall", "kitchen"]).unwrap();
+            assert!(state.atoms.iter().any(|atom| atom.to_string() == "(at kitchen)"));
+        }
+        _ => panic!("expected a domain and a problem"),
+    }
+}
+
+#[test]
+pub fn simulate_oneof_test() {
+    // l1 and l2 are on, l3 is off
+    let switched = "(walk hall kitchen)\n(switch_all kitchen)\n";
+    assert!(simulate(&format!("{}(inspect l3 l1 l3)\n", switched)).is_ok());
+    for lamps in ["l3 l3 l3", "l1 l2 l3", "l1 l2 l1"] {
+        let error = simulate(&format!("{}(inspect {})\n", switched, lamps)).unwrap_err();
+        assert_eq!(error.step, Some(3));
+        assert!(matches!(error.error_type, PlanErrorType::UnsatisfiedPrecondition(_, _)));
+    }
+}
@@ -0,0 +1,230 @@
+use super::*;
+use crate::test_fixtures::*;
+
+fn problem(init: &str, goal: &str) -> String {
+    delivery_problem(
+        "p1 p2 - package a b c - location",
+        ":subtasks (and (task0 (deliver_both p1 p2 c)))",
+        init,
+        goal,
+    )
+}
+
+const INIT: &str = "(at p1 a) (at p2 b) (road a c) (road b c)";
+const GOAL: &str = "(at p1 c) (at p2 c)";
+
+const PLAN: &str = "==>
+1 move p1 a c
+2 move p2 b c
+root 3
+3 deliver_both p1 p2 c -> m_both 4 5
+4 deliver p1 c -> m_direct 1
+5 deliver p2 c -> m_direct 2
+<==
+";
+
+fn verify(problem: &str, plan: &str) -> Result<(), PlanError> {
+    let plan = Plan::parse(plan)?;
+    with_delivery(problem, |d, p| PlanVerifier::new(d, p).verify(&plan))
+}
+
+#[test]
+pub fn valid_plan_test() {
+    verify(&problem(INIT, GOAL), PLAN).unwrap();
+    // subtasks may be listed in any order
+    let plan = PLAN.replace("m_both 4 5", "m_both 5 4");
+    verify(&problem(INIT, GOAL), &plan).unwrap();
+}
+
+#[test]
+pub fn unsatisfied_precondition_test() {
+    let init = "(at p1 a) (at p2 b) (road a c)";
+    match verify(&problem(init, GOAL), PLAN) {
+        Err(PlanError {
+            error_type: PlanErrorType::UnsatisfiedPrecondition(action, literals),
+            step,
+            position,
+        }) => {
+            assert_eq!(action, "move");
+            assert_eq!(literals, vec!["(road b c)"]);
+            assert_eq!(step, Some(2));
+            assert_eq!(position.unwrap().line, 3);
+        }
+        result => panic!("expected unsatisfied precondition, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn unsatisfied_method_precondition_test() {
+    let plan = PLAN.replace("1 move p1 a c", "1 move p1 b c");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::UnsatisfiedMethodPrecondition(method),
+            step,
+            ..
+        }) => {
+            assert_eq!(method, "m_direct");
+            assert_eq!(step, Some(4));
+        }
+        result => panic!("expected unsatisfied method precondition, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn violated_ordering_test() {
+    let plan = PLAN.replace("1 move p1 a c\n2 move p2 b c", "2 move p2 b c\n1 move p1 a c");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::ViolatedOrdering(first, second),
+            step,
+            ..
+        }) => {
+            assert_eq!((first, second), (4, 5));
+            assert_eq!(step, Some(3));
+        }
+        result => panic!("expected violated ordering, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn unsatisfied_goal_test() {
+    match verify(&problem(INIT, "(at p1 c) (at p2 a)"), PLAN) {
+        Err(PlanError {
+            error_type: PlanErrorType::UnsatisfiedGoal(literals),
+            ..
+        }) => {
+            assert_eq!(literals, vec!["(at p2 a)"]);
+        }
+        result => panic!("expected unsatisfied goal, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn inconsistent_decomposition_test() {
+    let plan = PLAN.replace("4 deliver p1 c -> m_direct 1", "4 deliver p1 c -> m_both 1");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::InconsistentMethodTask(method, task),
+            step,
+            ..
+        }) => {
+            assert_eq!((method.as_str(), task.as_str()), ("m_both", "deliver"));
+            assert_eq!(step, Some(4));
+        }
+        result => panic!("expected inconsistent method, found {:?}", result),
+    }
+    let plan = PLAN.replace("4 deliver p1 c -> m_direct 1", "4 deliver p1 c -> m_direct 2");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::InconsistentSubtask(_),
+            ..
+        }) => {}
+        result => panic!("expected inconsistent subtask, found {:?}", result),
+    }
+    let plan = PLAN.replace("root 3", "root 3 4");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::MultipleParents(4),
+            ..
+        }) => {}
+        result => panic!("expected multiple parents, found {:?}", result),
+    }
+    let plan = PLAN.replace("root 3", "6 move p1 c a\nroot 3");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::UnreachableStep(6),
+            step: Some(6),
+            ..
+        }) => {}
+        result => panic!("expected unreachable step, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn undefined_plan_elements_test() {
+    let plan = PLAN.replace("2 move p2 b c", "2 fly p2 b c");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::UndefinedAction(action),
+            step: Some(2),
+            ..
+        }) => assert_eq!(action, "fly"),
+        result => panic!("expected undefined action, found {:?}", result),
+    }
+    let plan = PLAN.replace("2 move p2 b c", "2 move p2 b d");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::UndefinedObject(object),
+            ..
+        }) => assert_eq!(object, "d"),
+        result => panic!("expected undefined object, found {:?}", result),
+    }
+    let plan = PLAN.replace("2 move p2 b c", "2 move b p2 c");
+    match verify(&problem(INIT, GOAL), &plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::InconsistentArgType(type_error),
+            ..
+        }) => {
+            assert_eq!(type_error.object, "b");
+            assert_eq!(type_error.expected, "package");
+        }
+        result => panic!("expected inconsistent type, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn identical_subtasks_test() {
+    // the two deliveries of p1 are listed against the order of the method
+    let init = "(at p1 a) (road a c) (road c c)";
+    let plan = "==>
+1 move p1 a c
+2 move p1 c c
+root 3
+3 deliver_both p1 p1 c -> m_both 5 4
+4 deliver p1 c -> m_direct 1
+5 deliver p1 c -> m_direct 2
+<==
+";
+    let problem = problem(init, "(at p1 c)").replace("deliver_both p1 p2 c", "deliver_both p1 p1 c");
+    verify(&problem, plan).unwrap();
+}
+
+#[test]
+pub fn method_without_actions_test() {
+    let plan = "==>
+1 move p1 a c
+root 3
+3 deliver_both p1 p1 c -> m_both 4 5
+4 deliver p1 c -> m_direct 1
+5 deliver p1 c -> m_already
+<==
+";
+    let twice = problem("(at p1 a) (road a c)", "(at p1 c)").replace("deliver_both p1 p2 c", "deliver_both p1 p1 c");
+    verify(&twice, plan).unwrap();
+    // p1 is at c in the final state, but not before the delivery of p2
+    let ordered = problem(INIT, GOAL).replace(
+        ":subtasks (and (task0 (deliver_both p1 p2 c)))",
+        ":ordered-subtasks (and (task0 (deliver_both p1 p2 c)) (task1 (deliver p1 c)))",
+    );
+    let plan = "==>
+1 move p2 b c
+2 move p1 a c
+root 3 6
+3 deliver_both p1 p2 c -> m_both 4 5
+4 deliver p1 c -> m_already
+5 deliver p2 c -> m_direct 1
+6 deliver p1 c -> m_direct 2
+<==
+";
+    match verify(&ordered, plan) {
+        Err(PlanError {
+            error_type: PlanErrorType::UnsatisfiedMethodPrecondition(method),
+            step,
+            ..
+        }) => {
+            assert_eq!(method, "m_already");
+            assert_eq!(step, Some(4));
+        }
+        result => panic!("expected unsatisfied method precondition, found {:?}", result),
+    }
+}
@@ -0,0 +1,624 @@
+use std::collections::{HashMap, HashSet};
+
+use super::*;
+
+// a step of the plan is either an action or a decomposed compound task
+#[derive(Clone, Copy)]
+enum Node<'a> {
+    Primitive(usize, &'a PlanStep<'a>),
+    Compound(&'a Decomposition<'a>),
+}
+
+impl<'a> Node<'a> {
+    fn name(&self) -> &'a str {
+        match self {
+            Node::Primitive(_, step) => step.name,
+            Node::Compound(decomposition) => decomposition.task,
+        }
+    }
+
+    fn arguments(&self) -> &'a [&'a str] {
+        match self {
+            Node::Primitive(_, step) => &step.arguments,
+            Node::Compound(decomposition) => &decomposition.arguments,
+        }
+    }
+}
+
+// the result of checking a decomposition against its method
+struct Refinement<'a> {
+    decomposition: &'a Decomposition<'a>,
+    method: &'a Method<'a>,
+    binding: Binding<'a>,
+    // the plan step that refines each subtask of the method
+    children: Vec<usize>,
+}
+
+pub struct PlanVerifier<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    universe: Universe<'a>,
+}
+
+impl<'a> PlanVerifier<'a> {
+    pub fn new(domain: &'a DomainAST<'a>, problem: &'a ProblemAST<'a>) -> PlanVerifier<'a> {
+        PlanVerifier {
+            domain,
+            problem,
+            universe: Universe::new(domain, problem),
+        }
+    }
+
+    pub fn verify(&self, plan: &'a Plan<'a>) -> Result<(), PlanError> {
+        let nodes = self.index_steps(plan)?;
+        let mut actions = vec![];
+        for step in plan.primitives.iter() {
+            actions.push(self.check_primitive(step)?);
+        }
+        let leaves = compute_leaves(plan, &nodes);
+        let mut parents: HashMap<usize, usize> = HashMap::new();
+        let mut refinements = vec![];
+        for decomposition in plan.decompositions.iter() {
+            let refinement = self.check_decomposition(decomposition, &nodes, &leaves)?;
+            for child in decomposition.subtasks.iter() {
+                if parents.insert(*child, decomposition.id).is_some() {
+                    return Err(error(PlanErrorType::MultipleParents(*child), decomposition));
+                }
+            }
+            refinements.push(refinement);
+        }
+        let root = self.check_root(plan, &nodes, &parents, &leaves)?;
+        for refinement in refinements.iter() {
+            let decomposition = refinement.decomposition;
+            check_orderings(&refinement.method.tn, &refinement.children, &leaves)
+                .map_err(|e| error(e, decomposition))?;
+        }
+        if let (Some(init_tn), Some(root)) = (&self.problem.init_tn, &root) {
+            check_orderings(&init_tn.tn, root, &leaves).map_err(|error_type| PlanError {
+                error_type,
+                step: None,
+                position: plan.root_position,
+            })?;
+        }
+        let tree = Tree {
+            parents: &parents,
+            refinements: refinements.iter().map(|refinement| (refinement.decomposition.id, refinement)).collect(),
+            root: self.problem.init_tn.as_ref().map(|init_tn| &init_tn.tn).zip(root.as_deref()),
+            leaves: &leaves,
+            length: plan.primitives.len(),
+        };
+        let state = self.execute(plan, &actions, &tree)?;
+        if let Some(goal) = &self.problem.goal {
+            let literals = state.unsatisfied(goal, &Binding::new(), &self.universe);
+            if !literals.is_empty() {
+                return Err(PlanError {
+                    error_type: PlanErrorType::UnsatisfiedGoal(literals),
+                    step: None,
+                    position: None,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn index_steps(&self, plan: &'a Plan<'a>) -> Result<HashMap<usize, Node<'a>>, PlanError> {
+        let mut nodes = HashMap::new();
+        for (index, step) in plan.primitives.iter().enumerate() {
+            if nodes.insert(step.id, Node::Primitive(index, step)).is_some() {
+                return Err(error(PlanErrorType::DuplicateStepId(step.id), step));
+            }
+        }
+        for decomposition in plan.decompositions.iter() {
+            if nodes.insert(decomposition.id, Node::Compound(decomposition)).is_some() {
+                return Err(error(PlanErrorType::DuplicateStepId(decomposition.id), decomposition));
+            }
+        }
+        Ok(nodes)
+    }
+
+    // the action of a primitive step, along with the binding of its parameters
+    fn check_primitive(&self, step: &'a PlanStep<'a>) -> Result<(&'a Action<'a>, Binding<'a>), PlanError> {
+        let Some(action) = self.domain.actions.iter().find(|a| a.name == step.name) else {
+            return Err(error(PlanErrorType::UndefinedAction(step.name.to_string()), step));
+        };
+        let binding = self.universe.bind(action.name, &action.parameters, &step.arguments)
+            .map_err(|e| error(e, step))?;
+        Ok((action, binding))
+    }
+
+    fn check_decomposition(
+        &self,
+        decomposition: &'a Decomposition<'a>,
+        nodes: &HashMap<usize, Node<'a>>,
+        leaves: &HashMap<usize, Vec<usize>>,
+    ) -> Result<Refinement<'a>, PlanError> {
+        let to_error = |error_type| error(error_type, decomposition);
+        let Some(task) = self.domain.compound_tasks.iter().find(|t| t.name == decomposition.task) else {
+            return Err(to_error(PlanErrorType::UndefinedTask(decomposition.task.to_string())));
+        };
+        self.universe.bind(task.name, &task.parameters, &decomposition.arguments)
+            .map_err(to_error)?;
+        let Some(method) = self.domain.methods.iter().find(|m| m.name.name == decomposition.method) else {
+            return Err(to_error(PlanErrorType::UndefinedMethod(decomposition.method.to_string())));
+        };
+        if method.task.name != task.name {
+            return Err(to_error(PlanErrorType::InconsistentMethodTask(
+                method.name.name.to_string(),
+                task.name.to_string(),
+            )));
+        }
+        // bind the method parameters through the decomposed task
+        let mut binding = Binding::new();
+        for (term, object) in method.task_terms.iter().zip(decomposition.arguments.iter()) {
+            if !bind_term(term.name, object, &method.params, &mut binding) {
+                return Err(to_error(PlanErrorType::InconsistentMethodArgument(
+                    method.name.name.to_string(),
+                    term.name.to_string(),
+                )));
+            }
+        }
+        let mut children = vec![];
+        for child in decomposition.subtasks.iter() {
+            match nodes.get(child) {
+                Some(node) => children.push(*node),
+                None => return Err(to_error(PlanErrorType::UndefinedStepId(*child))),
+            }
+        }
+        let inconsistent_subtask = || to_error(PlanErrorType::InconsistentSubtask(method.name.name.to_string()));
+        if children.len() != method.tn.subtasks.len() {
+            return Err(inconsistent_subtask());
+        }
+        let mut assignment = vec![None; children.len()];
+        let mut is_ordered = |assignment: &[Option<usize>]| {
+            let children: Vec<usize> = assignment.iter().map(|child| decomposition.subtasks[child.unwrap()]).collect();
+            check_orderings(&method.tn, &children, leaves).is_ok()
+        };
+        // identical subtasks are assigned in the order of the method if possible, and otherwise
+        // in any order, so that the violated ordering is reported
+        let is_matched = match_subtasks(&method.tn.subtasks, &children, &method.params, &mut binding, &mut assignment, &mut is_ordered)
+            || match_subtasks(&method.tn.subtasks, &children, &method.params, &mut binding, &mut assignment, &mut |_| true);
+        if !is_matched {
+            return Err(inconsistent_subtask());
+        }
+        for parameter in method.params.iter() {
+            if let Some(object) = binding.get(parameter.name) {
+                self.universe.check_object(method.name.name, parameter, object).map_err(to_error)?;
+            }
+        }
+        // constraints on parameters that are not bound yet are checked with the precondition
+        if let Some(constraints) = &method.tn.constraints {
+            for constraint in constraints.iter() {
+                if is_bound(constraint, &method.params, &binding) && !is_satisfied(constraint, &binding) {
+                    return Err(to_error(PlanErrorType::ViolatedConstraint(method.name.name.to_string())));
+                }
+            }
+        }
+        Ok(Refinement {
+            decomposition,
+            method,
+            binding,
+            children: assignment
+                .into_iter()
+                .map(|child| decomposition.subtasks[child.unwrap()])
+                .collect(),
+        })
+    }
+
+    // returns the plan step that refines each subtask of the initial task network
+    fn check_root(
+        &self,
+        plan: &'a Plan<'a>,
+        nodes: &HashMap<usize, Node<'a>>,
+        parents: &HashMap<usize, usize>,
+        leaves: &HashMap<usize, Vec<usize>>,
+    ) -> Result<Option<Vec<usize>>, PlanError> {
+        let root_error = |error_type| PlanError {
+            error_type,
+            step: None,
+            position: plan.root_position,
+        };
+        let mut roots = vec![];
+        for id in plan.root.iter() {
+            match nodes.get(id) {
+                Some(node) => roots.push(*node),
+                None => return Err(root_error(PlanErrorType::UndefinedStepId(*id))),
+            }
+            if parents.contains_key(id) {
+                return Err(root_error(PlanErrorType::MultipleParents(*id)));
+            }
+        }
+        let Some(init_tn) = &self.problem.init_tn else {
+            if roots.is_empty() {
+                // without a hierarchy, the plan is a plain sequence of actions
+                return Ok(None);
+            }
+            return Err(root_error(PlanErrorType::RootMismatch(
+                "the problem has no initial task network".to_string(),
+            )));
+        };
+        // every step must be part of the decomposition tree
+        let mut reachable: HashSet<usize> = HashSet::new();
+        let mut stack = plan.root.clone();
+        while let Some(id) = stack.pop() {
+            if reachable.insert(id) {
+                if let Some(Node::Compound(decomposition)) = nodes.get(&id) {
+                    stack.extend(decomposition.subtasks.iter());
+                }
+            }
+        }
+        for step in plan.primitives.iter() {
+            if !reachable.contains(&step.id) {
+                return Err(error(PlanErrorType::UnreachableStep(step.id), step));
+            }
+        }
+        for decomposition in plan.decompositions.iter() {
+            if !reachable.contains(&decomposition.id) {
+                return Err(error(PlanErrorType::UnreachableStep(decomposition.id), decomposition));
+            }
+        }
+        let subtasks = &init_tn.tn.subtasks;
+        if subtasks.len() != roots.len() {
+            return Err(root_error(PlanErrorType::RootMismatch(format!(
+                "expected {} tasks, found {}",
+                subtasks.len(),
+                roots.len()
+            ))));
+        }
+        let no_parameters = vec![];
+        let parameters = init_tn.parameters.as_ref().unwrap_or(&no_parameters);
+        let mut binding = Binding::new();
+        let mut assignment = vec![None; roots.len()];
+        let mut is_ordered = |assignment: &[Option<usize>]| {
+            let roots: Vec<usize> = assignment.iter().map(|root| plan.root[root.unwrap()]).collect();
+            check_orderings(&init_tn.tn, &roots, leaves).is_ok()
+        };
+        let is_matched = match_subtasks(subtasks, &roots, parameters, &mut binding, &mut assignment, &mut is_ordered)
+            || match_subtasks(subtasks, &roots, parameters, &mut binding, &mut assignment, &mut |_| true);
+        if !is_matched {
+            return Err(root_error(PlanErrorType::RootMismatch(
+                "the root tasks are not the tasks of the problem".to_string(),
+            )));
+        }
+        if let Some(constraints) = &init_tn.tn.constraints {
+            if !constraints.iter().all(|constraint| is_satisfied(constraint, &binding)) {
+                return Err(root_error(PlanErrorType::RootMismatch(
+                    "the constraints of the initial task network are violated".to_string(),
+                )));
+            }
+        }
+        Ok(Some(
+            assignment
+                .into_iter()
+                .map(|root| plan.root[root.unwrap()])
+                .collect(),
+        ))
+    }
+
+    // runs the primitive steps from the initial state, returning the final state
+    fn execute(
+        &self,
+        plan: &'a Plan<'a>,
+        actions: &[(&'a Action<'a>, Binding<'a>)],
+        tree: &Tree<'_, 'a>,
+    ) -> Result<State<'a>, PlanError> {
+        // method preconditions must hold right before the first action of their subtree
+        let mut pending: HashMap<usize, Vec<&Refinement<'a>>> = HashMap::new();
+        // methods without actions in their subtree must hold in some state of their range
+        let mut unplaced = vec![];
+        for refinement in tree.refinements.values() {
+            match tree.leaves[&refinement.decomposition.id].iter().min() {
+                Some(first) => pending.entry(*first).or_default().push(refinement),
+                None => {
+                    let (first, last) = tree.range(refinement.decomposition.id);
+                    unplaced.push((*refinement, first, last));
+                }
+            }
+        }
+        // reported in the order of the plan
+        unplaced.sort_by_key(|(refinement, _, _)| refinement.decomposition.id);
+        let mut state = State::new(&self.problem.init_state);
+        for (index, (step, (action, binding))) in plan.primitives.iter().zip(actions.iter()).enumerate() {
+            for refinement in pending.get(&index).into_iter().flatten() {
+                self.check_method_precondition(&state, refinement)?;
+            }
+            self.place_methods(&state, index, &mut unplaced)?;
+            state = state
+                .progress(action, binding, &self.universe)
+                .map_err(|e| error(e, step))?;
+        }
+        self.place_methods(&state, plan.primitives.len(), &mut unplaced)?;
+        Ok(state)
+    }
+
+    // drops the methods (without actions) whose preconditions hold in the state at the given
+    // position, and fails for those whose range ends there
+    fn place_methods(
+        &self,
+        state: &State<'a>,
+        index: usize,
+        unplaced: &mut Vec<(&Refinement<'a>, usize, usize)>,
+    ) -> Result<(), PlanError> {
+        let mut remaining = vec![];
+        for (refinement, first, last) in unplaced.drain(..) {
+            if index < first {
+                remaining.push((refinement, first, last));
+                continue;
+            }
+            match self.check_method_precondition(state, refinement) {
+                Ok(()) => {}
+                Err(error) if index >= last => return Err(error),
+                Err(_) => remaining.push((refinement, first, last)),
+            }
+        }
+        *unplaced = remaining;
+        Ok(())
+    }
+
+    fn check_method_precondition(&self, state: &State<'a>, refinement: &Refinement<'a>) -> Result<(), PlanError> {
+        let method = refinement.method;
+        let no_constraints = vec![];
+        let constraints = method.tn.constraints.as_ref().unwrap_or(&no_constraints);
+        let is_checked = |binding: &Binding<'a>| {
+            constraints.iter().all(|constraint| is_satisfied(constraint, binding))
+                && method
+                    .precondition
+                    .as_ref()
+                    .map_or(true, |precondition| state.holds(precondition, binding, &self.universe))
+        };
+        // parameters that occur only in the precondition are existentially quantified
+        let free: Vec<&Symbol> = method
+            .params
+            .iter()
+            .filter(|parameter| !refinement.binding.contains_key(parameter.name))
+            .collect();
+        let mut binding = refinement.binding.clone();
+        if self.universe.find_binding(&free, &mut binding, &mut |b| is_checked(b)) {
+            return Ok(());
+        }
+        let method_name = method.name.name.to_string();
+        let error_type = match method.precondition {
+            Some(_) => PlanErrorType::UnsatisfiedMethodPrecondition(method_name),
+            None => PlanErrorType::ViolatedConstraint(method_name),
+        };
+        Err(error(error_type, refinement.decomposition))
+    }
+}
+
+// the decomposition tree of a plan, with the plan step that refines each subtask
+struct Tree<'t, 'a> {
+    parents: &'t HashMap<usize, usize>,
+    refinements: HashMap<usize, &'t Refinement<'a>>,
+    // the initial task network, along with the plan step that refines each of its subtasks
+    root: Option<(&'a HTN<'a>, &'t [usize])>,
+    leaves: &'t HashMap<usize, Vec<usize>>,
+    // number of actions in the plan
+    length: usize,
+}
+
+impl<'t, 'a> Tree<'t, 'a> {
+    // the first and last positions (in the execution order, where the state at position i is
+    // the one before action i) at which a plan step may happen, given the orderings
+    fn range(&self, id: usize) -> (usize, usize) {
+        let leaves = &self.leaves[&id];
+        if let (Some(first), Some(last)) = (leaves.iter().min(), leaves.iter().max()) {
+            return (*first, *last + 1);
+        }
+        let (tn, siblings, (mut first, mut last)) = match (self.parents.get(&id), self.root) {
+            (Some(parent), _) => (&self.refinements[parent].method.tn, &self.refinements[parent].children[..], self.range(*parent)),
+            (None, Some((tn, roots))) => (tn, roots, (0, self.length)),
+            (None, None) => return (0, self.length),
+        };
+        let Some(i) = siblings.iter().position(|sibling| *sibling == id) else {
+            return (first, last);
+        };
+        let before = ordering_matrix(tn);
+        for (j, sibling) in siblings.iter().enumerate() {
+            if before[j][i] {
+                if let Some(sibling_last) = self.leaves[sibling].iter().max() {
+                    first = first.max(*sibling_last + 1);
+                }
+            }
+            if before[i][j] {
+                if let Some(sibling_first) = self.leaves[sibling].iter().min() {
+                    last = last.min(*sibling_first);
+                }
+            }
+        }
+        (first, last.max(first))
+    }
+}
+
+trait Located {
+    fn id(&self) -> usize;
+    fn position(&self) -> TokenPosition;
+}
+
+impl<'a> Located for PlanStep<'a> {
+    fn id(&self) -> usize {
+        self.id
+    }
+    fn position(&self) -> TokenPosition {
+        self.position
+    }
+}
+
+impl<'a> Located for Decomposition<'a> {
+    fn id(&self) -> usize {
+        self.id
+    }
+    fn position(&self) -> TokenPosition {
+        self.position
+    }
+}
+
+fn error(error_type: PlanErrorType, step: &dyn Located) -> PlanError {
+    PlanError {
+        error_type,
+        step: Some(step.id()),
+        position: Some(step.position()),
+    }
+}
+
+// binds a term of a task to an object, where terms that are not variables are constants
+pub(super) fn bind_term<'a>(term: &'a str, object: &'a str, variables: &[Symbol<'a>], binding: &mut Binding<'a>) -> bool {
+    if !variables.iter().any(|variable| variable.name == term) {
+        return term == object;
+    }
+    match binding.get(term) {
+        Some(bound) => *bound == object,
+        None => {
+            binding.insert(term, object);
+            true
+        }
+    }
+}
+
+// assigns a distinct plan step to every subtask, such that names and arguments agree, and the
+// complete assignment is accepted
+fn match_subtasks<'a>(
+    subtasks: &'a [Subtask<'a>],
+    children: &[Node<'a>],
+    variables: &[Symbol<'a>],
+    binding: &mut Binding<'a>,
+    assignment: &mut Vec<Option<usize>>,
+    accept: &mut dyn FnMut(&[Option<usize>]) -> bool,
+) -> bool {
+    let Some((subtask, rest)) = subtasks.split_first() else {
+        return accept(assignment);
+    };
+    let index = assignment.len() - subtasks.len();
+    for (child_index, child) in children.iter().enumerate() {
+        if assignment.contains(&Some(child_index)) || child.name() != subtask.task.name {
+            continue;
+        }
+        if subtask.terms.len() != child.arguments().len() {
+            continue;
+        }
+        let mut extended = binding.clone();
+        let is_consistent = subtask
+            .terms
+            .iter()
+            .zip(child.arguments().iter())
+            .all(|(term, object)| bind_term(term.name, object, variables, &mut extended));
+        if !is_consistent {
+            continue;
+        }
+        assignment[index] = Some(child_index);
+        if match_subtasks(rest, children, variables, &mut extended, assignment, accept) {
+            *binding = extended;
+            return true;
+        }
+        assignment[index] = None;
+    }
+    false
+}
+
+fn is_bound(constraint: &Constraint, variables: &[Symbol], binding: &Binding) -> bool {
+    let (Constraint::Equal(a, b) | Constraint::NotEqual(a, b)) = constraint;
+    [a, b].iter().all(|term| {
+        binding.contains_key(term.name) || !variables.iter().any(|variable| variable.name == term.name)
+    })
+}
+
+pub(super) fn is_satisfied<'a>(constraint: &Constraint<'a>, binding: &Binding<'a>) -> bool {
+    match constraint {
+        Constraint::Equal(a, b) => resolve(a.name, binding) == resolve(b.name, binding),
+        Constraint::NotEqual(a, b) => resolve(a.name, binding) != resolve(b.name, binding),
+    }
+}
+
+// positions (in the execution order) of the actions below every plan step
+fn compute_leaves(plan: &Plan, nodes: &HashMap<usize, Node>) -> HashMap<usize, Vec<usize>> {
+    let mut leaves = HashMap::new();
+    for id in nodes.keys() {
+        collect_leaves(*id, nodes, &mut leaves);
+    }
+    for step in plan.primitives.iter() {
+        leaves.entry(step.id).or_default();
+    }
+    leaves
+}
+
+fn collect_leaves(id: usize, nodes: &HashMap<usize, Node>, leaves: &mut HashMap<usize, Vec<usize>>) {
+    if leaves.contains_key(&id) {
+        return;
+    }
+    // guards against cycles, which are reported as multiple parents
+    leaves.insert(id, vec![]);
+    let result = match nodes.get(&id) {
+        Some(Node::Primitive(index, _)) => vec![*index],
+        Some(Node::Compound(decomposition)) => {
+            let mut result = vec![];
+            for child in decomposition.subtasks.iter() {
+                collect_leaves(*child, nodes, leaves);
+                result.extend(leaves[child].iter());
+            }
+            result
+        }
+        None => vec![],
+    };
+    leaves.insert(id, result);
+}
+
+// every ordered pair of subtasks must have all actions of the first before those of the second
+fn check_orderings(
+    tn: &HTN,
+    children: &[usize],
+    leaves: &HashMap<usize, Vec<usize>>,
+) -> Result<(), PlanErrorType> {
+    let n = tn.subtasks.len();
+    let before = ordering_matrix(tn);
+    for i in 0..n {
+        for j in 0..n {
+            if !before[i][j] {
+                continue;
+            }
+            let last = leaves[&children[i]].iter().max();
+            let first = leaves[&children[j]].iter().min();
+            if let (Some(last), Some(first)) = (last, first) {
+                if last > first {
+                    return Err(PlanErrorType::ViolatedOrdering(children[i], children[j]));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// whether the subtask at the first index is ordered before the one at the second index
+fn ordering_matrix(tn: &HTN) -> Vec<Vec<bool>> {
+    let n = tn.subtasks.len();
+    let mut before = vec![vec![false; n]; n];
+    match &tn.orderings {
+        TaskOrdering::Total => {
+            for i in 0..n {
+                for j in i + 1..n {
+                    before[i][j] = true;
+                }
+            }
+        }
+        TaskOrdering::Partial(orderings) => {
+            let index_of = |id: &str| {
+                tn.subtasks
+                    .iter()
+                    .position(|subtask| subtask.id.as_ref().map_or(false, |s| s.name == id))
+            };
+            for (first, second) in orderings.iter() {
+                if let (Some(i), Some(j)) = (index_of(first), index_of(second)) {
+                    before[i][j] = true;
+                }
+            }
+            // transitive closure, since subtasks without actions do not separate others
+            for k in 0..n {
+                for i in 0..n {
+                    for j in 0..n {
+                        if before[i][k] && before[k][j] {
+                            before[i][j] = true;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    before
+}
@@ -0,0 +1,9 @@
+mod search;
+#[cfg(test)]
+mod tests;
+
+use crate::grounder::*;
+use crate::plan_verifier::{Plan, PlanVerifier};
+use crate::syntactic_analyzer::*;
+
+pub use search::{Planner, SearchResult};
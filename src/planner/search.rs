@@ -0,0 +1,257 @@
+use std::collections::HashSet;
+
+use super::*;
+
+/// The outcome of a bounded search for a plan.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SearchResult {
+    /// A plan in the IPC 2020 HTN format.
+    Solution(String),
+    /// The search space was exhausted without reaching a bound.
+    Unsolvable,
+    /// There is no plan with at most this many decompositions and actions.
+    NoSolutionUpToDepth(usize),
+    /// The search stopped after expanding this many nodes.
+    NodeBoundReached(usize),
+}
+
+// a task of the task network, with the id of its step in the plan
+#[derive(Debug, Clone, Copy)]
+struct Step {
+    id: usize,
+    task: usize,
+}
+
+#[derive(Debug, Clone)]
+struct SearchNode {
+    state: HashSet<usize>,
+    tasks: Vec<Step>,
+    // (before, after) pairs between the ids of open tasks
+    orderings: HashSet<(usize, usize)>,
+    executed: Vec<Step>,
+    // decomposed tasks with their method and the ids of their subtasks
+    refined: Vec<(Step, usize, Vec<usize>)>,
+    root: Vec<usize>,
+    next_id: usize,
+}
+
+/// A progression search over the ground model, meant for checking that small
+/// problems are solvable rather than for speed: every task without predecessors
+/// is either executed or decomposed with every method, with iterative deepening
+/// on the number of decompositions and actions.
+pub struct Planner<'a> {
+    domain: &'a DomainAST<'a>,
+    problem: &'a ProblemAST<'a>,
+    model: GroundModel<'a>,
+    max_depth: usize,
+    max_nodes: usize,
+    nodes: usize,
+    is_depth_bounded: bool,
+}
+
+impl<'a> Planner<'a> {
+    pub fn new(
+        domain: &'a DomainAST<'a>,
+        problem: &'a ProblemAST<'a>,
+        model: GroundModel<'a>,
+        max_depth: usize,
+        max_nodes: usize,
+    ) -> Planner<'a> {
+        Planner {
+            domain,
+            problem,
+            model,
+            max_depth,
+            max_nodes,
+            nodes: 0,
+            is_depth_bounded: false,
+        }
+    }
+
+    pub fn search(&mut self) -> SearchResult {
+        let mut roots = vec![];
+        let init = SearchNode {
+            state: self.model.init_state.iter().copied().collect(),
+            tasks: vec![],
+            orderings: HashSet::new(),
+            executed: vec![],
+            refined: vec![],
+            root: vec![],
+            next_id: 0,
+        };
+        match self.model.top {
+            // the methods of the top task are the groundings of the initial task network
+            Some(top) => {
+                for method in self.model.methods.iter().filter(|method| method.task == top) {
+                    let mut root = init.clone();
+                    root.root = root.insert_subtasks(method);
+                    roots.push(root);
+                }
+            }
+            None => roots.push(init),
+        }
+        for depth in 0..=self.max_depth {
+            self.is_depth_bounded = false;
+            for root in roots.iter() {
+                if let Some(plan) = self.expand(root, depth, None) {
+                    return SearchResult::Solution(plan);
+                }
+            }
+            if self.nodes >= self.max_nodes {
+                return SearchResult::NodeBoundReached(self.nodes);
+            }
+            if !self.is_depth_bounded {
+                return SearchResult::Unsolvable;
+            }
+        }
+        SearchResult::NoSolutionUpToDepth(self.max_depth)
+    }
+
+    // decompositions between two actions are applied to the same state, so they are only
+    // done in the order of their task ids (a subtask has a larger id than its parent)
+    fn expand(&mut self, node: &SearchNode, remaining: usize, last_decomposed: Option<usize>) -> Option<String> {
+        if node.tasks.is_empty() {
+            return self.check_solution(node);
+        }
+        if remaining == 0 {
+            self.is_depth_bounded = true;
+            return None;
+        }
+        let first: Vec<Step> = node
+            .tasks
+            .iter()
+            .filter(|step| !node.orderings.iter().any(|(_, after)| *after == step.id))
+            .copied()
+            .collect();
+        for step in first {
+            let mut children = vec![];
+            if self.model.tasks[step.task].is_primitive {
+                if let Some(child) = self.execute(node, step) {
+                    children.push((child, None));
+                }
+            } else if last_decomposed.is_none_or(|last| step.id > last) {
+                for (index, method) in self.model.methods.iter().enumerate() {
+                    if method.task == step.task && holds(&method.preconditions, &node.state) {
+                        children.push((node.decompose(step, index, method), Some(step.id)));
+                    }
+                }
+            }
+            for (child, decomposed) in children {
+                if self.nodes >= self.max_nodes {
+                    return None;
+                }
+                self.nodes += 1;
+                if let Some(plan) = self.expand(&child, remaining - 1, decomposed) {
+                    return Some(plan);
+                }
+            }
+        }
+        None
+    }
+
+    fn execute(&self, node: &SearchNode, step: Step) -> Option<SearchNode> {
+        let action = self
+            .model
+            .actions_of(step.task)
+            .into_iter()
+            .find(|action| holds(&action.preconditions, &node.state))?;
+        let mut child = node.clone();
+        // conditional effects are conditioned on the state before the action, and
+        // delete effects are applied before add effects
+        let effects: Vec<Literal> = action
+            .effects
+            .iter()
+            .filter(|effect| holds(&effect.conditions, &node.state))
+            .map(|effect| effect.effect)
+            .collect();
+        for effect in effects.iter().filter(|effect| !effect.is_positive) {
+            child.state.remove(&effect.fact);
+        }
+        child.state.extend(effects.iter().filter(|effect| effect.is_positive).map(|effect| effect.fact));
+        child.remove(step.id);
+        child.executed.push(step);
+        Some(child)
+    }
+
+    // method preconditions are checked when their task is decomposed rather than before
+    // the first action of the task, so a plan is only accepted if the verifier accepts it
+    fn check_solution(&self, node: &SearchNode) -> Option<String> {
+        let is_goal = self.model.goal.iter().any(|conjunction| holds(conjunction, &node.state));
+        if !is_goal {
+            return None;
+        }
+        let text = self.format(node);
+        let plan = Plan::parse(&text).ok()?;
+        PlanVerifier::new(self.domain, self.problem).verify(&plan).ok()?;
+        Some(text)
+    }
+
+    // the plan in the IPC 2020 HTN format
+    fn format(&self, node: &SearchNode) -> String {
+        let ids = |ids: &Vec<usize>| ids.iter().map(|id| format!(" {}", id)).collect::<String>();
+        let mut lines = vec!["==>".to_string()];
+        for step in node.executed.iter() {
+            lines.push(format!("{} {}", step.id, self.task_text(step.task)));
+        }
+        lines.push(format!("root{}", ids(&node.root)));
+        for (step, method, subtasks) in node.refined.iter() {
+            let method = self.model.methods[*method].name;
+            lines.push(format!("{} {} -> {}{}", step.id, self.task_text(step.task), method, ids(subtasks)));
+        }
+        lines.push("<==".to_string());
+        lines.join("\n") + "\n"
+    }
+
+    fn task_text(&self, task: usize) -> String {
+        let task = &self.model.tasks[task];
+        let mut text = task.name.to_string();
+        for argument in task.arguments.iter() {
+            text.push(' ');
+            text.push_str(argument);
+        }
+        text
+    }
+}
+
+impl SearchNode {
+    fn decompose(&self, step: Step, index: usize, method: &GroundMethod) -> SearchNode {
+        let mut child = self.clone();
+        let subtasks = child.insert_subtasks(method);
+        // the subtasks inherit the orderings of the decomposed task
+        for (before, after) in self.orderings.iter() {
+            for subtask in subtasks.iter() {
+                if *after == step.id {
+                    child.orderings.insert((*before, *subtask));
+                }
+                if *before == step.id {
+                    child.orderings.insert((*subtask, *after));
+                }
+            }
+        }
+        child.remove(step.id);
+        child.refined.push((step, index, subtasks));
+        child
+    }
+
+    // adds the subtasks of a method to the task network, returning their ids
+    fn insert_subtasks(&mut self, method: &GroundMethod) -> Vec<usize> {
+        let ids: Vec<usize> = (self.next_id..self.next_id + method.subtasks.len()).collect();
+        self.next_id += method.subtasks.len();
+        for (id, task) in ids.iter().zip(method.subtasks.iter()) {
+            self.tasks.push(Step { id: *id, task: *task });
+        }
+        for (before, after) in method.orderings.iter() {
+            self.orderings.insert((ids[*before], ids[*after]));
+        }
+        ids
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.tasks.retain(|step| step.id != id);
+        self.orderings.retain(|(before, after)| *before != id && *after != id);
+    }
+}
+
+fn holds(literals: &[Literal], state: &HashSet<usize>) -> bool {
+    literals.iter().all(|literal| state.contains(&literal.fact) == literal.is_positive)
+}
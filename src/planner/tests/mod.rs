@@ -0,0 +1,3 @@
+mod search_tests;
+
+use super::*;
@@ -0,0 +1,54 @@
+use super::*;
+use crate::semantic_analyzer::DomainSemanticAnalyzer;
+use crate::test_fixtures::*;
+
+fn problem_with(tasks: &str, roads: &str, goal: &str) -> String {
+    let htn = format!(":subtasks (and {})", tasks);
+    delivery_problem("p1 - package a b c d - location", &htn, &format!("(at p1 a) {}", roads), goal)
+}
+
+fn solve(problem: &str, max_depth: usize, max_nodes: usize) -> SearchResult {
+    with_delivery(problem, |d, p| {
+        let symbol_table = DomainSemanticAnalyzer::new(d).verify_domain().unwrap();
+        let model = Grounder::new(d, p, &symbol_table).ground();
+        Planner::new(d, p, model, max_depth, max_nodes).search()
+    })
+}
+
+#[test]
+pub fn planner_solution_test() {
+    let problem = problem_with("(task0 (deliver p1 c))", "(road a b) (road b c) (road a d)", "(at p1 c)");
+    // the shortest plan goes through b
+    assert_eq!(
+        solve(&problem, 10, 1000),
+        SearchResult::Solution(
+            "==>\n1 move p1 a b\n3 move p1 b c\nroot 0\n0 deliver p1 c -> m_via 1 2\n\
+             2 deliver p1 c -> m_direct 3\n<==\n"
+                .to_string()
+        )
+    );
+    // unordered tasks are done in the order that makes the plan executable
+    let problem = problem_with(
+        "(task0 (mark p1 b)) (task1 (deliver p1 b))",
+        "(road a b)",
+        "(delivered p1)",
+    );
+    match solve(&problem, 10, 1000) {
+        SearchResult::Solution(plan) => assert!(plan.starts_with("==>\n2 move p1 a b\n0 mark p1 b\n")),
+        result => panic!("{:?}", result),
+    }
+}
+
+#[test]
+pub fn planner_bounds_test() {
+    // c can not be reached at all
+    let problem = problem_with("(task0 (deliver p1 c))", "(road a b)", "");
+    assert_eq!(solve(&problem, 10, 1000), SearchResult::Unsolvable);
+    // going back and forth between a and b never ends
+    let problem = problem_with("(task0 (deliver p1 c))", "(road a b) (road b a) (road b c)", "(at p1 d)");
+    assert_eq!(solve(&problem, 6, 100000), SearchResult::NoSolutionUpToDepth(6));
+    let problem = problem_with("(task0 (deliver p1 c))", "(road a b) (road b c)", "");
+    assert_eq!(solve(&problem, 3, 1000), SearchResult::NoSolutionUpToDepth(3));
+    assert!(matches!(solve(&problem, 4, 1000), SearchResult::Solution(_)));
+    assert_eq!(solve(&problem, 10, 2), SearchResult::NodeBoundReached(2));
+}
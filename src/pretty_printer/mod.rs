@@ -0,0 +1,8 @@
+mod printer;
+#[cfg(test)]
+mod tests;
+
+use crate::syntactic_analyzer::*;
+use crate::lexical_analyzer::RequirementType;
+
+pub use printer::PrettyPrinter;
@@ -0,0 +1,381 @@
+use super::*;
+
+const INDENTATION: &str = "  ";
+
+// prints syntax trees as canonical HDDL, where sections always come in the same
+// order and every nested block is indented one level deeper than its parent
+pub struct PrettyPrinter {
+    output: String,
+    level: usize,
+    // effects are conditioned with "when", while conditions use "imply"
+    implication: &'static str,
+}
+
+impl PrettyPrinter {
+    pub fn print_domain(domain: &DomainAST) -> String {
+        let mut printer = PrettyPrinter::new();
+        printer.line(&format!("(define (domain {})", domain.name));
+        printer.indent();
+        if !domain.requirements.is_empty() {
+            printer.line(&format!("(:requirements {})", requirements(&domain.requirements)));
+        }
+        if let Some(types) = &domain.types {
+            printer.symbol_section(":types", types);
+        }
+        if let Some(constants) = &domain.constants {
+            printer.symbol_section(":constants", constants);
+        }
+        if !domain.predicates.is_empty() {
+            printer.separator();
+            printer.line("(:predicates");
+            printer.indent();
+            for predicate in domain.predicates.iter() {
+                let parameters = typed_list(&predicate.variables, true);
+                printer.line(&format!("({})", join(predicate.name, &parameters)));
+            }
+            printer.dedent();
+            printer.line(")");
+        }
+        for task in domain.compound_tasks.iter() {
+            printer.separator();
+            printer.line(&format!("(:task {}", task.name));
+            printer.indent();
+            printer.line(&format!(":parameters ({})", typed_list(&task.parameters, true)));
+            printer.dedent();
+            printer.line(")");
+        }
+        for method in domain.methods.iter() {
+            printer.separator();
+            printer.method(method);
+        }
+        for action in domain.actions.iter() {
+            printer.separator();
+            printer.action(action);
+        }
+        printer.dedent();
+        printer.line(")");
+        printer.output
+    }
+
+    pub fn print_problem(problem: &ProblemAST) -> String {
+        let mut printer = PrettyPrinter::new();
+        printer.line(&format!("(define (problem {})", problem.name));
+        printer.indent();
+        printer.line(&format!("(:domain {})", problem.domain_name));
+        if !problem.requirements.is_empty() {
+            printer.line(&format!("(:requirements {})", requirements(&problem.requirements)));
+        }
+        if !problem.objects.is_empty() {
+            printer.symbol_section(":objects", &problem.objects);
+        }
+        if let Some(init_tn) = &problem.init_tn {
+            printer.separator();
+            printer.line("(:htn");
+            printer.indent();
+            let mut scope = vec![];
+            if let Some(parameters) = &init_tn.parameters {
+                printer.line(&format!(":parameters ({})", typed_list(parameters, true)));
+                scope.extend(parameters.iter().map(|parameter| parameter.name));
+            }
+            printer.htn(&init_tn.tn, &scope);
+            printer.dedent();
+            printer.line(")");
+        }
+        printer.separator();
+        printer.line("(:init");
+        printer.indent();
+        for fact in problem.init_state.iter() {
+            printer.line(&atom(fact, &[]));
+        }
+        printer.dedent();
+        printer.line(")");
+        // the goal closes the problem definition, so it must come last
+        if let Some(goal) = &problem.goal {
+            printer.separator();
+            printer.line("(:goal");
+            printer.indent();
+            printer.formula("", goal, &[]);
+            printer.dedent();
+            printer.line(")");
+        }
+        printer.dedent();
+        printer.line(")");
+        printer.output
+    }
+
+    fn new() -> PrettyPrinter {
+        PrettyPrinter {
+            output: String::new(),
+            level: 0,
+            implication: "imply",
+        }
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.level {
+            self.output.push_str(INDENTATION);
+        }
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    // an empty line between top-level sections
+    fn separator(&mut self) {
+        self.output.push('\n');
+    }
+
+    fn indent(&mut self) {
+        self.level += 1;
+    }
+
+    fn dedent(&mut self) {
+        self.level -= 1;
+    }
+
+    // types, constants, and objects, with one line for each type
+    fn symbol_section(&mut self, keyword: &str, symbols: &Vec<Symbol>) {
+        self.line(&format!("({}", keyword));
+        self.indent();
+        for group in group_by_type(symbols) {
+            self.line(&typed_list(group, false));
+        }
+        self.dedent();
+        self.line(")");
+    }
+
+    fn method(&mut self, method: &Method) {
+        let scope: Vec<&str> = method.params.iter().map(|parameter| parameter.name).collect();
+        self.line(&format!("(:method {}", method.name.name));
+        self.indent();
+        self.line(&format!(":parameters ({})", typed_list(&method.params, true)));
+        let terms = terms(&method.task_terms, &scope);
+        self.line(&format!(":task ({})", join(method.task.name, &terms)));
+        if let Some(precondition) = &method.precondition {
+            self.formula(":precondition ", precondition, &scope);
+        }
+        self.htn(&method.tn, &scope);
+        self.dedent();
+        self.line(")");
+    }
+
+    fn action(&mut self, action: &Action) {
+        let scope: Vec<&str> = action.parameters.iter().map(|parameter| parameter.name).collect();
+        self.line(&format!("(:action {}", action.name));
+        self.indent();
+        self.line(&format!(":parameters ({})", typed_list(&action.parameters, true)));
+        if let Some(precondition) = &action.preconditions {
+            self.formula(":precondition ", precondition, &scope);
+        }
+        if let Some(effect) = &action.effects {
+            self.implication = "when";
+            self.formula(":effect ", effect, &scope);
+            self.implication = "imply";
+        }
+        self.dedent();
+        self.line(")");
+    }
+
+    fn htn(&mut self, tn: &HTN, scope: &[&str]) {
+        let keyword = match tn.orderings {
+            TaskOrdering::Total => ":ordered-subtasks",
+            TaskOrdering::Partial(_) => ":subtasks",
+        };
+        let subtasks: Vec<String> = tn
+            .subtasks
+            .iter()
+            .map(|subtask| {
+                let task = format!("({})", join(subtask.task.name, &terms(&subtask.terms, scope)));
+                match &subtask.id {
+                    Some(id) => format!("({} {})", id.name, task),
+                    None => task,
+                }
+            })
+            .collect();
+        self.list(keyword, &subtasks);
+        if let TaskOrdering::Partial(orderings) = &tn.orderings {
+            // an explicitly empty ordering is kept
+            if !orderings.is_empty() || tn.ordering_pos.is_some() {
+                let orderings: Vec<String> = orderings
+                    .iter()
+                    .map(|(first, second)| format!("(< {} {})", first, second))
+                    .collect();
+                self.list(":ordering", &orderings);
+            }
+        }
+        if let Some(constraints) = &tn.constraints {
+            let constraints: Vec<String> = constraints
+                .iter()
+                .map(|constraint| match constraint {
+                    Constraint::Equal(a, b) => format!("(= {} {})", term(a.name, scope), term(b.name, scope)),
+                    Constraint::NotEqual(a, b) => format!("(not (= {} {}))", term(a.name, scope), term(b.name, scope)),
+                })
+                .collect();
+            self.list(":constraints", &constraints);
+        }
+    }
+
+    // a conjunction of items, one per line
+    fn list(&mut self, keyword: &str, items: &Vec<String>) {
+        if items.is_empty() {
+            self.line(&format!("{} ()", keyword));
+            return;
+        }
+        self.line(&format!("{} (and", keyword));
+        self.indent();
+        for item in items.iter() {
+            self.line(item);
+        }
+        self.dedent();
+        self.line(")");
+    }
+
+    // connectives and quantifiers span multiple lines, while literals fit in one
+    fn formula(&mut self, prefix: &str, formula: &Formula, scope: &[&str]) {
+        match formula {
+            Formula::And(formulas) | Formula::Or(formulas) | Formula::Xor(formulas) if !formulas.is_empty() => {
+                self.line(&format!("{}({}", prefix, connective(formula)));
+                self.indent();
+                for f in formulas.iter() {
+                    self.formula("", f, scope);
+                }
+                self.dedent();
+                self.line(")");
+            }
+            Formula::ForAll(variables, f) | Formula::Exists(variables, f) => {
+                let quantifier = match formula {
+                    Formula::ForAll(_, _) => "forall",
+                    _ => "exists",
+                };
+                self.line(&format!("{}({} ({})", prefix, quantifier, typed_list(variables, true)));
+                self.indent();
+                let mut scope = scope.to_vec();
+                scope.extend(variables.iter().map(|variable| variable.name));
+                self.formula("", f, &scope);
+                self.dedent();
+                self.line(")");
+            }
+            Formula::Imply(antecedents, consequences) => {
+                self.line(&format!("{}({}", prefix, self.implication));
+                self.indent();
+                self.formula("", &conjunction(antecedents), scope);
+                self.formula("", &conjunction(consequences), scope);
+                self.dedent();
+                self.line(")");
+            }
+            _ => self.line(&format!("{}{}", prefix, inline(formula, scope))),
+        }
+    }
+}
+
+fn inline(formula: &Formula, scope: &[&str]) -> String {
+    match formula {
+        Formula::Empty => "()".to_string(),
+        Formula::Atom(predicate) => atom(predicate, scope),
+        Formula::Not(f) => format!("(not {})", inline(f, scope)),
+        Formula::And(formulas) | Formula::Or(formulas) | Formula::Xor(formulas) => {
+            let formulas: Vec<String> = formulas.iter().map(|f| inline(f, scope)).collect();
+            format!("({})", join(connective(formula), &formulas.join(" ")))
+        }
+        Formula::ForAll(variables, f) | Formula::Exists(variables, f) => {
+            let quantifier = match formula {
+                Formula::ForAll(_, _) => "forall",
+                _ => "exists",
+            };
+            let mut scope = scope.to_vec();
+            scope.extend(variables.iter().map(|variable| variable.name));
+            format!("({} ({}) {})", quantifier, typed_list(variables, true), inline(f, &scope))
+        }
+        Formula::Imply(antecedents, consequences) => format!(
+            "(imply {} {})",
+            inline(&conjunction(antecedents), scope),
+            inline(&conjunction(consequences), scope)
+        ),
+        Formula::Equals(a, b) => format!("(= {} {})", term(a.name, scope), term(b.name, scope)),
+    }
+}
+
+fn connective(formula: &Formula) -> &'static str {
+    match formula {
+        Formula::Or(_) => "or",
+        Formula::Xor(_) => "oneof",
+        _ => "and",
+    }
+}
+
+// a single formula stays as is, while several are conjoined
+fn conjunction<'a>(formulas: &Vec<Box<Formula<'a>>>) -> Formula<'a> {
+    match formulas.as_slice() {
+        [formula] => (**formula).clone(),
+        _ => Formula::And(formulas.clone()),
+    }
+}
+
+fn atom(predicate: &Predicate, scope: &[&str]) -> String {
+    format!("({})", join(predicate.name, &terms(&predicate.variables, scope)))
+}
+
+// variables lose their '?' in the syntax tree, so the scope tells them apart from constants
+fn term(name: &str, scope: &[&str]) -> String {
+    if scope.contains(&name) {
+        format!("?{}", name)
+    } else {
+        name.to_string()
+    }
+}
+
+fn terms(symbols: &Vec<Symbol>, scope: &[&str]) -> String {
+    symbols
+        .iter()
+        .map(|symbol| term(symbol.name, scope))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// consecutive symbols of the same type share a single type annotation
+fn typed_list(symbols: &[Symbol], is_variable: bool) -> String {
+    group_by_type(symbols)
+        .iter()
+        .map(|group| {
+            let names: Vec<String> = group
+                .iter()
+                .map(|symbol| match is_variable {
+                    true => format!("?{}", symbol.name),
+                    false => symbol.name.to_string(),
+                })
+                .collect();
+            match group[0].symbol_type {
+                Some(symbol_type) => format!("{} - {}", names.join(" "), symbol_type),
+                None => names.join(" "),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn group_by_type<'s, 'a>(symbols: &'s [Symbol<'a>]) -> Vec<&'s [Symbol<'a>]> {
+    let mut groups = vec![];
+    let mut start = 0;
+    for i in 1..=symbols.len() {
+        if i == symbols.len() || symbols[i].symbol_type != symbols[start].symbol_type {
+            groups.push(&symbols[start..i]);
+            start = i;
+        }
+    }
+    groups
+}
+
+fn requirements(requirements: &Vec<RequirementType>) -> String {
+    requirements
+        .iter()
+        .map(|requirement| requirement.to_string())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn join(head: &str, rest: &str) -> String {
+    if rest.is_empty() {
+        head.to_string()
+    } else {
+        format!("{} {}", head, rest)
+    }
+}
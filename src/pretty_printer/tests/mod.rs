@@ -0,0 +1,37 @@
+mod printer_tests;
+mod round_trip_tests;
+
+use super::*;
+use crate::lexical_analyzer::*;
+
+// the debug representation of a syntax tree without source positions, so
+// that the trees of a file and its printed version can be compared
+pub fn strip_positions(tree: String) -> String {
+    let mut stripped = String::new();
+    let mut rest = tree.as_str();
+    while let Some(start) = rest.find("TokenPosition {") {
+        stripped.push_str(&rest[..start]);
+        let end = rest[start..].find('}').unwrap();
+        rest = &rest[start + end + 1..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+// prints a domain or a problem, and checks that parsing the output gives back the same tree
+pub fn assert_round_trip(program: &Vec<u8>) -> String {
+    let parser = Parser::new(LexicalAnalyzer::new(program));
+    let (printed, tree) = match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => (PrettyPrinter::print_domain(&d), format!("{:?}", d)),
+        AbstractSyntaxTree::Problem(p) => (PrettyPrinter::print_problem(&p), format!("{:?}", p)),
+    };
+    let printed_program = printed.clone().into_bytes();
+    let parser = Parser::new(LexicalAnalyzer::new(&printed_program));
+    let (reprinted, printed_tree) = match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => (PrettyPrinter::print_domain(&d), format!("{:?}", d)),
+        AbstractSyntaxTree::Problem(p) => (PrettyPrinter::print_problem(&p), format!("{:?}", p)),
+    };
+    assert_eq!(strip_positions(tree), strip_positions(printed_tree));
+    assert_eq!(printed, reprinted);
+    printed
+}
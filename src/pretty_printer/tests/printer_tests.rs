@@ -0,0 +1,163 @@
+use super::*;
+
+#[test]
+pub fn domain_printing_test() {
+    let domain = String::from(
+        "(define (domain bal)
+        (:requirements :typing :hierarchy :method-preconditions)
+        (:types truck car - vehicle location)
+        (:constants depot - location)
+        (:predicates (at ?v - vehicle ?l - location) (road ?a ?b - location) (ready))
+        (:task go :parameters (?v - vehicle ?l - location))
+        (:method m_go
+          :parameters (?v - vehicle ?from ?to - location)
+          :task (go ?v ?to)
+          :precondition (and (at ?v ?from) (not (= ?from ?to))
+            (or (road ?from ?to) (forall (?l - location) (road ?l depot))))
+          :subtasks (and (t1 (drive ?v ?from ?to)) (t2 (go ?v depot)))
+          :ordering (and (< t1 t2))
+          :constraints (and (not (= ?to depot))))
+        (:method m_stay
+          :parameters (?v - vehicle ?l - location)
+          :task (go ?v ?l)
+          :ordered-subtasks (and (noop))
+          :constraints (= ?l ?l))
+        (:action drive
+          :parameters (?v - vehicle ?from ?to - location)
+          :precondition (and (at ?v ?from) (exists (?x - location) (road ?x ?to)) (oneof (ready) (road ?to ?from)))
+          :effect (and (not (at ?v ?from)) (at ?v ?to)))
+        (:action noop :parameters () :precondition () :effect ()))",
+    )
+    .into_bytes();
+    let printed = assert_round_trip(&domain);
+    let expected = "\
+(define (domain bal)
+  (:requirements :typing :hierarchy :method-preconditions)
+  (:types
+    truck car - vehicle
+    location
+  )
+  (:constants
+    depot - location
+  )
+
+  (:predicates
+    (at ?v - vehicle ?l - location)
+    (road ?a ?b - location)
+    (ready)
+  )
+
+  (:task go
+    :parameters (?v - vehicle ?l - location)
+  )
+
+  (:method m_go
+    :parameters (?v - vehicle ?from ?to - location)
+    :task (go ?v ?to)
+    :precondition (and
+      (at ?v ?from)
+      (not (= ?from ?to))
+      (or
+        (road ?from ?to)
+        (forall (?l - location)
+          (road ?l depot)
+        )
+      )
+    )
+    :subtasks (and
+      (t1 (drive ?v ?from ?to))
+      (t2 (go ?v depot))
+    )
+    :ordering (and
+      (< t1 t2)
+    )
+    :constraints (and
+      (not (= ?to depot))
+    )
+  )
+
+  (:method m_stay
+    :parameters (?v - vehicle ?l - location)
+    :task (go ?v ?l)
+    :ordered-subtasks (and
+      (noop)
+    )
+    :constraints (and
+      (= ?l ?l)
+    )
+  )
+
+  (:action drive
+    :parameters (?v - vehicle ?from ?to - location)
+    :precondition (and
+      (at ?v ?from)
+      (exists (?x - location)
+        (road ?x ?to)
+      )
+      (oneof
+        (ready)
+        (road ?to ?from)
+      )
+    )
+    :effect (and
+      (not (at ?v ?from))
+      (at ?v ?to)
+    )
+  )
+
+  (:action noop
+    :parameters ()
+    :precondition ()
+    :effect ()
+  )
+)
+";
+    assert_eq!(printed, expected);
+}
+
+#[test]
+pub fn problem_printing_test() {
+    let problem = String::from(
+        "(define (problem p1) (:domain bal)
+        (:objects t1 t2 - truck home work - location)
+        (:htn :parameters (?l - location) :subtasks (and (task0 (go t1 ?l)) (task1 (go t2 home)))
+          :ordering (and (< task0 task1)))
+        (:init (at t1 home) (at t2 work))
+        (:goal (and (at t1 work) (not (at t2 work)))))",
+    )
+    .into_bytes();
+    let printed = assert_round_trip(&problem);
+    let expected = "\
+(define (problem p1)
+  (:domain bal)
+  (:objects
+    t1 t2 - truck
+    home work - location
+  )
+
+  (:htn
+    :parameters (?l - location)
+    :subtasks (and
+      (task0 (go t1 ?l))
+      (task1 (go t2 home))
+    )
+    :ordering (and
+      (< task0 task1)
+    )
+  )
+
+  (:init
+    (at t1 home)
+    (at t2 work)
+  )
+
+  (:goal
+    (and
+      (at t1 work)
+      (not (at t2 work))
+    )
+  )
+)
+";
+    assert_eq!(printed, expected);
+}
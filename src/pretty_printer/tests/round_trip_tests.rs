@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+pub fn ipc_domains_round_trip_test() {
+    for folder in std::fs::read_dir("tests/ipc").unwrap() {
+        let domain = std::fs::read(folder.unwrap().path().join("domain.hddl")).unwrap();
+        assert_round_trip(&domain);
+    }
+}
+
+// the smallest problem of each domain, to keep the test fast
+#[test]
+pub fn ipc_problems_round_trip_test() {
+    for folder in std::fs::read_dir("tests/ipc").unwrap() {
+        let smallest = std::fs::read_dir(folder.unwrap().path())
+            .unwrap()
+            .map(|file| file.unwrap().path())
+            .filter(|path| path.file_name().unwrap() != "domain.hddl")
+            .min_by_key(|path| std::fs::metadata(path).unwrap().len());
+        if let Some(path) = smallest {
+            assert_round_trip(&std::fs::read(&path).unwrap());
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use super::SatSolver;
+
+// the built-in solver, a DPLL search with unit propagation
+pub struct Dpll;
+
+impl SatSolver for Dpll {
+    fn solve(&self, var_count: u32, clauses: &[Vec<i32>]) -> Option<bool> {
+        let var_count = clauses
+            .iter()
+            .flatten()
+            .map(|literal| literal.unsigned_abs())
+            .max()
+            .unwrap_or(0)
+            .max(var_count);
+        let mut assignment = vec![None; var_count as usize + 1];
+        Some(search(clauses, &mut assignment))
+    }
+}
+
+fn value(literal: i32, assignment: &[Option<bool>]) -> Option<bool> {
+    assignment[literal.unsigned_abs() as usize].map(|value| value == (literal > 0))
+}
+
+fn search(clauses: &[Vec<i32>], assignment: &mut Vec<Option<bool>>) -> bool {
+    let mut trail = vec![];
+    let undo = |trail: &[i32], assignment: &mut Vec<Option<bool>>| {
+        for literal in trail {
+            assignment[literal.unsigned_abs() as usize] = None;
+        }
+    };
+    loop {
+        // the first unit or open literal of a clause that is not satisfied yet
+        let mut unit = None;
+        let mut open = None;
+        for clause in clauses {
+            if clause.iter().any(|literal| value(*literal, assignment) == Some(true)) {
+                continue;
+            }
+            let unassigned: Vec<i32> = clause
+                .iter()
+                .copied()
+                .filter(|literal| value(*literal, assignment).is_none())
+                .collect();
+            match unassigned.len() {
+                0 => {
+                    undo(&trail, assignment);
+                    return false;
+                }
+                1 => {
+                    unit = Some(unassigned[0]);
+                    break;
+                }
+                _ => {
+                    open.get_or_insert(unassigned[0]);
+                }
+            }
+        }
+        if let Some(literal) = unit {
+            assignment[literal.unsigned_abs() as usize] = Some(literal > 0);
+            trail.push(literal);
+            continue;
+        }
+        let Some(literal) = open else {
+            return true;
+        };
+        for decision in [literal, -literal] {
+            assignment[decision.unsigned_abs() as usize] = Some(decision > 0);
+            if search(clauses, assignment) {
+                return true;
+            }
+            assignment[decision.unsigned_abs() as usize] = None;
+        }
+        undo(&trail, assignment);
+        return false;
+    }
+}
@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use super::{to_dimacs, SatSolver};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+// runs a solver binary on a DIMACS file, following the conventions of the SAT competitions
+// (i.e. an "s SATISFIABLE" or "s UNSATISFIABLE" line, or the exit codes 10 and 20)
+pub struct ExternalSolver {
+    pub path: PathBuf,
+    pub arguments: Vec<String>,
+}
+
+impl ExternalSolver {
+    pub fn new(path: impl Into<PathBuf>) -> ExternalSolver {
+        ExternalSolver {
+            path: path.into(),
+            arguments: vec![],
+        }
+    }
+}
+
+impl SatSolver for ExternalSolver {
+    fn solve(&self, var_count: u32, clauses: &[Vec<i32>]) -> Option<bool> {
+        let input = std::env::temp_dir().join(format!(
+            "hddl_analyzer_{}_{}.cnf",
+            std::process::id(),
+            NEXT_ID.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&input, to_dimacs(var_count, clauses, &[])).ok()?;
+        let output = Command::new(&self.path).args(&self.arguments).arg(&input).output();
+        let _ = fs::remove_file(&input);
+        let output = output.ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        for line in stdout.lines() {
+            match line.trim() {
+                "s SATISFIABLE" => return Some(true),
+                "s UNSATISFIABLE" => return Some(false),
+                _ => {}
+            }
+        }
+        match output.status.code() {
+            Some(10) => Some(true),
+            Some(20) => Some(false),
+            _ => None,
+        }
+    }
+}
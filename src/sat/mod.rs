@@ -0,0 +1,29 @@
+mod dpll;
+mod external;
+#[cfg(test)]
+mod tests;
+
+pub use dpll::Dpll;
+pub use external::ExternalSolver;
+
+// decides clauses in the DIMACS notation, i.e. variables are 1..=var_count and negative literals are negated
+pub trait SatSolver {
+    // None if the solver gives no answer
+    fn solve(&self, var_count: u32, clauses: &[Vec<i32>]) -> Option<bool>;
+}
+
+// clauses in the DIMACS CNF format, preceded by comments naming the given variables
+pub fn to_dimacs(var_count: u32, clauses: &[Vec<i32>], symbols: &[String]) -> String {
+    let mut dimacs = String::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        dimacs.push_str(&format!("c {} {}\n", index + 1, symbol));
+    }
+    dimacs.push_str(&format!("p cnf {} {}\n", var_count, clauses.len()));
+    for clause in clauses {
+        for literal in clause {
+            dimacs.push_str(&format!("{} ", literal));
+        }
+        dimacs.push_str("0\n");
+    }
+    dimacs
+}
@@ -0,0 +1 @@
+mod sat_tests;
@@ -0,0 +1,63 @@
+#[test]
+pub fn dpll_test() {
+    use crate::sat::{Dpll, SatSolver};
+
+    // (a v b) ^ (~a v b) ^ (a v ~b)
+    assert_eq!(Dpll.solve(2, &[vec![1, 2], vec![-1, 2], vec![1, -2]]), Some(true));
+    // (a v b) ^ (~a v b) ^ (a v ~b) ^ (~a v ~b)
+    assert_eq!(Dpll.solve(2, &[vec![1, 2], vec![-1, 2], vec![1, -2], vec![-1, -2]]), Some(false));
+    // a ^ (~a v b) ^ (~b v c) ^ ~c
+    assert_eq!(Dpll.solve(3, &[vec![1], vec![-1, 2], vec![-2, 3], vec![-3]]), Some(false));
+    assert_eq!(Dpll.solve(0, &[]), Some(true));
+    assert_eq!(Dpll.solve(1, &[vec![]]), Some(false));
+    // pigeonhole: 3 pigeons in 2 holes, where p_ij (= 2i + j + 1) puts pigeon i in hole j
+    let mut clauses: Vec<Vec<i32>> = (0..3).map(|i| vec![2 * i + 1, 2 * i + 2]).collect();
+    for j in 0..2 {
+        for i in 0..3 {
+            for k in i + 1..3 {
+                clauses.push(vec![-(2 * i + j + 1), -(2 * k + j + 1)]);
+            }
+        }
+    }
+    assert_eq!(Dpll.solve(6, &clauses), Some(false));
+}
+
+#[test]
+pub fn dimacs_test() {
+    use crate::sat::to_dimacs;
+    use crate::syntactic_analyzer::{Formula, Predicate};
+
+    let formula = Formula::And(vec![
+        Box::new(Formula::Atom(Predicate::new_dummy("a"))),
+        Box::new(Formula::Or(vec![
+            Box::new(Formula::Not(Box::new(Formula::Atom(Predicate::new_dummy("a"))))),
+            Box::new(Formula::Atom(Predicate::new_dummy("b"))),
+        ])),
+    ]);
+    let (symbols, clauses) = formula.to_clauses_with_symbols();
+    assert_eq!(
+        to_dimacs(symbols.len() as u32, &clauses, &symbols),
+        "c 1 a()\nc 2 b()\np cnf 2 2\n1 0\n-1 2 0\n"
+    );
+}
+
+#[test]
+#[cfg(unix)]
+pub fn external_solver_test() {
+    use crate::sat::{ExternalSolver, SatSolver};
+
+    // the DIMACS file is the last argument, i.e. $0 of the script
+    let solver = ExternalSolver {
+        path: "sh".into(),
+        arguments: vec!["-c".to_string(), "grep -q '^-1 0$' \"$0\" && exit 20 || exit 10".to_string()],
+    };
+    assert_eq!(solver.solve(1, &[vec![1]]), Some(true));
+    assert_eq!(solver.solve(1, &[vec![1], vec![-1]]), Some(false));
+    let solver = ExternalSolver {
+        path: "sh".into(),
+        arguments: vec!["-c".to_string(), "echo 's UNSATISFIABLE'".to_string()],
+    };
+    assert_eq!(solver.solve(1, &[vec![1]]), Some(false));
+    let missing = ExternalSolver::new("/nonexistent/solver");
+    assert_eq!(missing.solve(1, &[vec![1]]), None);
+}
@@ -0,0 +1,12 @@
+use super::*;
+
+/// The result of analyzing a (valid) domain and, optionally, one of its problems.
+pub struct Analysis<'a> {
+    /// Declarations of the domain, after its semantic checks have passed.
+    pub symbol_table: SymbolTable<'a>,
+    /// Task decomposition graph of the domain.
+    pub tdg: TDG<'a>,
+    /// Warnings of the domain, and of the problem if one is given, including
+    /// the modeling smells.
+    pub warnings: Vec<WarningType>,
+}
@@ -1,11 +1,14 @@
 use std::collections::{HashMap, HashSet};
 
 use super::*;
-use crate::lexical_analyzer::RequirementType;
+use crate::lexical_analyzer::{RequirementType, TokenPosition};
+use crate::sat::{Dpll, SatSolver};
 
 pub struct DomainSemanticAnalyzer<'a> {
     domain: &'a DomainAST<'a>,
     pub type_checker: DomainTypeChecker<'a>,
+    // decides whether preconditions and effects are contradictory
+    solver: &'a dyn SatSolver,
 }
 
 impl<'a> DomainSemanticAnalyzer<'a> {
@@ -13,22 +16,38 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         DomainSemanticAnalyzer {
             domain,
             type_checker: DomainTypeChecker::new(&domain.types),
+            solver: &Dpll,
         }
     }
 
-    pub fn verify_domain(&'a self) -> Result<SymbolTable<'a>, SemanticErrorType> {
-        // Assert there are no duplicate requirements
-        if let Some(duplicate) =
-            DomainSemanticAnalyzer::check_duplicate_requirements(&self.domain.requirements)
-        {
-            return Err(duplicate);
+    pub fn with_solver(mut self, solver: &'a dyn SatSolver) -> DomainSemanticAnalyzer<'a> {
+        self.solver = solver;
+        self
+    }
+
+    pub fn verify_domain(&self) -> Result<SymbolTable<'a>, SemanticErrorType> {
+        let (symbol_table, mut errors) = self.analyze_domain();
+        if errors.is_empty() {
+            Ok(symbol_table)
+        } else {
+            Err(errors.remove(0))
         }
+    }
+
+    // reports every error in the domain instead of stopping at the first one
+    pub fn analyze_domain(&self) -> (SymbolTable<'a>, Vec<SemanticErrorType>) {
+        // Assert there are no duplicate requirements
+        let mut errors =
+            DomainSemanticAnalyzer::check_duplicate_requirements(&self.domain.requirements);
         // Assert type hierarchy is acyclic
-        let _ = self.type_checker.verify_type_hierarchy()?;
-        let mut warnings = vec![];
+        if let Err(error) = self.type_checker.verify_type_hierarchy() {
+            errors.push(error);
+        }
+        // Assert used features are declared as requirements
+        let mut warnings = RequirementAnalyzer::new(self.domain).analyze();
         // Domain declarations
-        let declared_predicates = self.verify_predicates()?;
-        let declared_tasks = self.verify_compound_tasks()?;
+        let declared_predicates = self.verify_predicates(&mut errors);
+        let declared_tasks = self.verify_compound_tasks(&mut errors);
         let mut declared_constants = HashSet::new();
         match &self.domain.constants {
             Some(constants) => {
@@ -44,7 +63,7 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         let mut action_positions = HashMap::new();
         for action in self.domain.actions.iter() {
             if !declared_actions.insert(action) {
-                return Err(SemanticErrorType::DuplicateActionDeclaration(
+                errors.push(SemanticErrorType::DuplicateActionDeclaration(
                     DuplicateError {
                         symbol: action.name.to_string(),
                         first_pos: *action_positions.get(action.name).unwrap(),
@@ -55,36 +74,27 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 action_positions.insert(action.name, action.name_pos);
             }
             // assert there is no duplicate paramter
-            let mut declared_params = HashSet::new();
-            let mut param_positions = HashMap::new();
-            for param in action.parameters.iter() {
-                if !declared_params.insert(param) {
-                    return Err(SemanticErrorType::DuplicateParameterDeclaration(
-                        DuplicateError {
-                            symbol: param.name.to_string(),
-                            first_pos: *param_positions.get(param.name).unwrap(),
-                            second_pos: param.name_pos,
-                        },
-                    ));
-                } else {
-                    param_positions.insert(param.name, param.name_pos);
-                }
-            }
+            DomainSemanticAnalyzer::check_duplicate_parameters(&action.parameters, &mut errors);
+            // assert parameter types are declared
+            errors.extend(self.type_checker.check_type_declarations(&action.parameters));
             // assert precondition predicates are declared
             match &action.preconditions {
                 Some(precondition) => {
-                    check_predicate_declarations(precondition, &self.domain.predicates)?;
-                    let precond_predicates = precondition.get_propositional_predicates();
-                    self.type_checker.check_formula(
-                        &precond_predicates,
+                    let is_valid = self.check_formula(
+                        precondition,
                         &action.parameters,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
-                    if !precondition.is_sat() {
-                        return Err(SemanticErrorType::ComplementaryActionPrecondition(
-                            action.name_pos
-                        ));
+                        &mut errors,
+                    );
+                    if is_valid {
+                        match precondition.check_sat(self.solver) {
+                            Some(false) => errors.push(SemanticErrorType::ComplementaryActionPrecondition(
+                                action.name_pos
+                            )),
+                            None => warnings.push(undecided_satisfiability("precondition", "action", action.name, action.name_pos)),
+                            Some(true) => {}
+                        }
                     }
                 }
                 _ => {}
@@ -92,18 +102,21 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             // assert effect predicates are declared
             match &action.effects {
                 Some(effect) => {
-                    check_predicate_declarations(effect, &self.domain.predicates)?;
-                    let eff_predicates = effect.get_propositional_predicates();
-                    self.type_checker.check_formula(
-                        &eff_predicates,
+                    let is_valid = self.check_formula(
+                        effect,
                         &action.parameters,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
-                    if !effect.is_sat() {
-                        return Err(SemanticErrorType::ComplementaryActionEffect(
-                            action.name_pos
-                        ));
+                        &mut errors,
+                    );
+                    if is_valid {
+                        match effect.check_sat(self.solver) {
+                            Some(false) => errors.push(SemanticErrorType::ComplementaryActionEffect(
+                                action.name_pos
+                            )),
+                            None => warnings.push(undecided_satisfiability("effect", "action", action.name, action.name_pos)),
+                            Some(true) => {}
+                        }
                     }
                 }
                 _ => {}
@@ -115,7 +128,7 @@ impl<'a> DomainSemanticAnalyzer<'a> {
         let mut method_positions = HashMap::new();
         for method in self.domain.methods.iter() {
             if !declared_methods.insert(&method.name) {
-                return Err(SemanticErrorType::DuplicateMethodDeclaration(
+                errors.push(SemanticErrorType::DuplicateMethodDeclaration(
                     DuplicateError {
                         symbol: method.name.name.to_string(),
                         first_pos: *method_positions.get(&method.name).unwrap(),
@@ -126,121 +139,185 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 method_positions.insert(&method.name, method.name.name_pos);
             }
             // assert there is no duplicate paramter
-            let mut declared_params = HashSet::new();
-            let mut param_positions = HashMap::new();
-            for param in method.params.iter() {
-                if !declared_params.insert(param) {
-                    return Err(SemanticErrorType::DuplicateParameterDeclaration(
-                        DuplicateError {
-                            symbol: param.name.to_string(),
-                            first_pos: *param_positions.get(param.name).unwrap(),
-                            second_pos: param.name_pos,
-                        },
-                    ));
-                } else {
-                    param_positions.insert(param.name, param.name_pos);
-                }
-            }
+            DomainSemanticAnalyzer::check_duplicate_parameters(&method.params, &mut errors);
+            // assert parameter types are declared
+            errors.extend(self.type_checker.check_type_declarations(&method.params));
             // Assert preconditions are valid
             match &method.precondition {
                 Some(precondition) => {
-                    check_predicate_declarations(precondition, &self.domain.predicates)?;
-                    let precond_predicates = precondition.get_propositional_predicates();
-                    self.type_checker.check_formula(
-                        &precond_predicates,
+                    let is_valid = self.check_formula(
+                        precondition,
                         &method.params,
                         &declared_constants,
                         &declared_predicates,
-                    )?;
-                    if !precondition.is_sat() {
-                        return Err(SemanticErrorType::ComplementaryMethodPrecondition(
-                            method.name.name_pos
-                        ));
+                        &mut errors,
+                    );
+                    if is_valid {
+                        match precondition.check_sat(self.solver) {
+                            Some(false) => errors.push(SemanticErrorType::ComplementaryMethodPrecondition(
+                                method.name.name_pos
+                            )),
+                            None => warnings.push(undecided_satisfiability("precondition", "method", method.name.name, method.name.name_pos)),
+                            Some(true) => {}
+                        }
                     }
                 }
                 _ => {}
             }
             // Assert task is defined
             if !declared_tasks.contains(method.task.name) {
-                return Err(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
+                errors.push(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
                     symbol: method.task.name.to_string(),
                     position: method.task.name_pos,
                 }));
             } else {
                 // Assert task arity is consistent
+                let mut is_consistent = true;
                 for declared_compound_task in self.domain.compound_tasks.iter() {
                     if method.task.name == declared_compound_task.name {
                         if method.task_terms.len() != declared_compound_task.parameters.len() {
-                            return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
+                            errors.push(SemanticErrorType::InconsistentTaskArity(ArityError {
                                 symbol: method.task.name.to_string(),
                                 expected_arity: method.task_terms.len() as u32,
                                 found_arity: declared_compound_task.parameters.len() as u32,
                                 position: method.task.name_pos,
                             }));
-                        } else {
-                            break;
+                            is_consistent = false;
                         }
+                        break;
                     }
                 }
+                // Assert task type is consistent
+                if is_consistent {
+                    errors.extend(self.type_checker.is_task_consistent(
+                        &method.task,
+                        &method.task_terms,
+                        &method.params,
+                        &declared_constants,
+                        &declared_tasks,
+                        &HashSet::new(),
+                    ));
+                }
             }
 
-            // Assert task type is consistent
-            let _ = self.type_checker.is_task_consistent(
-                &method.task,
-                &method.task_terms,
-                &method.params,
-                &declared_constants,
-                &declared_tasks,
-                &HashSet::new(),
-            )?;
-
             // Assert subtask types are consistent
             for subtask in method.tn.subtasks.iter() {
-                let _ = self.type_checker.is_task_consistent(
+                errors.extend(self.type_checker.is_task_consistent(
                     &subtask.task,
                     &subtask.terms,
                     &method.params,
                     &declared_constants,
                     &declared_tasks,
                     &declared_actions,
-                )?;
+                ));
+            }
+            // Assert constraint terms are defined
+            if let Some(constraints) = &method.tn.constraints {
+                errors.extend(self.type_checker.check_constraints(
+                    constraints,
+                    &method.params,
+                    &declared_constants,
+                ));
             }
             // Assert orderings are acyclic
             if !method.tn.orderings.is_acyclic() {
-                return Err(SemanticErrorType::CyclicOrderingDeclaration(
+                errors.push(SemanticErrorType::CyclicOrderingDeclaration(
                     method.tn.ordering_pos.unwrap(),
                 ));
             }
         }
         // Check whether all compound tasks can be refined to primitive ones
-        let tdg = TDG::new(self.domain);
-        for task in declared_tasks.iter() {
-            let reachables = tdg.reachable(&task.name);
-            if (reachables.primitives.len() == 0) && (reachables.nullable == false) {
-                warnings.push(WarningType::NoPrimitiveRefinement(WarningInfo {
-                    symbol: task.name.to_string(),
-                    position: task.name_pos,
-                }));
+        // (the TDG can only be built when every referenced task is defined)
+        if let Ok(tdg) = TDG::new(self.domain) {
+            let mut visited_tasks = HashSet::new();
+            for task in self.domain.compound_tasks.iter() {
+                // duplicate declarations are only checked once
+                if !visited_tasks.insert(task.name) {
+                    continue;
+                }
+                let reachables = tdg.reachable(task.name).unwrap();
+                if (reachables.primitives.len() == 0) && (reachables.nullable == false) {
+                    warnings.push(WarningType::NoPrimitiveRefinement(RefinementInfo {
+                        symbol: task.name.to_string(),
+                        position: task.name_pos,
+                        cause: tdg.explain_unrefinable(task.name),
+                    }));
+                }
             }
         }
+        // Check for modeling smells
+        warnings.extend(RedundancyAnalyzer::new(self.domain).analyze());
         let type_hierarchy = self.type_checker.get_type_hierarchy();
-        Ok(SymbolTable {
+        let symbol_table = SymbolTable {
             warnings: warnings,
+            requirements: declared_requirements(&self.domain.requirements),
             constants: declared_constants,
             predicates: declared_predicates,
             tasks: declared_tasks,
             actions: declared_actions,
             type_hierarchy: type_hierarchy,
-        })
+        };
+        (symbol_table, errors)
+    }
+
+    // checks the predicates and typing of a formula, and returns whether it is valid
+    fn check_formula(
+        &self,
+        formula: &Formula<'a>,
+        parameters: &Vec<Symbol<'a>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+        errors: &mut Vec<SemanticErrorType>,
+    ) -> bool {
+        let declaration_errors = check_predicate_declarations(formula, &self.domain.predicates);
+        if !declaration_errors.is_empty() {
+            errors.extend(declaration_errors);
+            return false;
+        }
+        let type_errors = self.type_checker.check_formula(
+            formula,
+            parameters,
+            declared_constants,
+            declared_predicates,
+        );
+        if !type_errors.is_empty() {
+            errors.extend(type_errors);
+            return false;
+        }
+        true
     }
 
-    // returns declared predicates (if there is no error)
-    fn verify_predicates(&'a self) -> Result<HashSet<&'a Predicate>, SemanticErrorType> {
+    fn check_duplicate_parameters(
+        parameters: &Vec<Symbol<'a>>,
+        errors: &mut Vec<SemanticErrorType>,
+    ) {
+        let mut declared_params = HashSet::new();
+        let mut param_positions = HashMap::new();
+        for param in parameters.iter() {
+            if !declared_params.insert(param) {
+                errors.push(SemanticErrorType::DuplicateParameterDeclaration(
+                    DuplicateError {
+                        symbol: param.name.to_string(),
+                        first_pos: *param_positions.get(param.name).unwrap(),
+                        second_pos: param.name_pos,
+                    },
+                ));
+            } else {
+                param_positions.insert(param.name, param.name_pos);
+            }
+        }
+    }
+
+    // returns declared predicates
+    fn verify_predicates(
+        &self,
+        errors: &mut Vec<SemanticErrorType>,
+    ) -> HashSet<&'a Predicate<'a>> {
         let mut declared_predicates = HashSet::new();
         let mut predicate_positions = HashMap::new();
         for predicate in self.domain.predicates.iter() {
             if !declared_predicates.insert(predicate) {
-                return Err(SemanticErrorType::DuplicatePredicateDeclaration(
+                errors.push(SemanticErrorType::DuplicatePredicateDeclaration(
                     DuplicateError {
                         symbol: predicate.name.to_string(),
                         first_pos: *predicate_positions.get(predicate.name).unwrap(),
@@ -250,23 +327,24 @@ impl<'a> DomainSemanticAnalyzer<'a> {
             } else {
                 predicate_positions.insert(predicate.name, predicate.name_pos);
             }
-            if let Some(error) = self
-                .type_checker
-                .check_type_declarations(&predicate.variables)
-            {
-                return Err(error);
-            }
+            errors.extend(
+                self.type_checker
+                    .check_type_declarations(&predicate.variables),
+            );
         }
-        Ok(declared_predicates)
+        declared_predicates
     }
 
-    // returns declared compound tasks (if there is no error)
-    fn verify_compound_tasks(&'a self) -> Result<HashSet<&Task<'a>>, SemanticErrorType> {
+    // returns declared compound tasks
+    fn verify_compound_tasks(
+        &self,
+        errors: &mut Vec<SemanticErrorType>,
+    ) -> HashSet<&'a Task<'a>> {
         let mut declared_tasks = HashSet::new();
         let mut task_positions = HashMap::new();
         for task in self.domain.compound_tasks.iter() {
             if !declared_tasks.insert(task) {
-                return Err(SemanticErrorType::DuplicateCompoundTaskDeclaration(
+                errors.push(SemanticErrorType::DuplicateCompoundTaskDeclaration(
                     DuplicateError {
                         symbol: task.name.to_string(),
                         first_pos: *task_positions.get(task.name).unwrap(),
@@ -277,24 +355,30 @@ impl<'a> DomainSemanticAnalyzer<'a> {
                 task_positions.insert(task.name, task.name_pos);
             }
             // assert parameter types are declared
-            if let Some(error) = self.type_checker.check_type_declarations(&task.parameters) {
-                return Err(error);
-            }
+            errors.extend(self.type_checker.check_type_declarations(&task.parameters));
         }
-        Ok(declared_tasks)
+        declared_tasks
     }
 
     pub fn check_duplicate_requirements(
         requirements: &'a Vec<RequirementType>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
         let mut names = HashSet::new();
+        let mut errors = vec![];
         for req in requirements {
             if !names.insert(req) {
-                return Some(SemanticErrorType::DuplicateRequirementDeclaration(*req));
+                errors.push(SemanticErrorType::DuplicateRequirementDeclaration(*req));
             }
         }
-        None
+        errors
     }
+}
 
-    // fn verify_formula(formula: &Formula<'a>, declared_predicates: HashSet<u>)
+// a formula that is too large for the SAT check, or that the SAT solver gives no answer for
+fn undecided_satisfiability(formula: &'static str, kind: &str, name: &str, position: TokenPosition) -> WarningType {
+    WarningType::UndecidedSatisfiability(FormulaInfo {
+        formula,
+        owner: format!("{} {}", kind, name),
+        position,
+    })
 }
@@ -1,10 +1,16 @@
 mod problem_analyzer;
 mod domain_analyzer;
 mod symbol_table;
+mod analysis;
+mod redundancy_analyzer;
+mod requirement_analyzer;
 
 use super::*;
 
 
 pub use domain_analyzer::DomainSemanticAnalyzer;
 pub use symbol_table::SymbolTable;
-pub use problem_analyzer::ProblemSemanticAnalyzer;
\ No newline at end of file
+pub use problem_analyzer::ProblemSemanticAnalyzer;
+pub use analysis::Analysis;
+pub use redundancy_analyzer::RedundancyAnalyzer;
+pub use requirement_analyzer::*;
\ No newline at end of file
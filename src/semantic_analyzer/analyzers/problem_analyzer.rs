@@ -1,36 +1,47 @@
 use super::*;
+use crate::lexical_analyzer::{RequirementType, TokenPosition};
 use std::collections::{HashMap, HashSet};
 
 pub struct ProblemSemanticAnalyzer<'a> {
     problem: &'a ProblemAST<'a>,
+    domain: &'a DomainAST<'a>,
     type_checker: ProblemTypeChecker<'a>,
 }
 
 impl<'a> ProblemSemanticAnalyzer<'a> {
     pub fn new(
         problem: &'a ProblemAST<'a>,
+        domain: &'a DomainAST<'a>,
         domain_symbols: SymbolTable<'a>,
     ) -> ProblemSemanticAnalyzer<'a> {
         ProblemSemanticAnalyzer {
             problem,
+            domain,
             type_checker: ProblemTypeChecker::new(domain_symbols, problem),
         }
     }
 
     pub fn verify_problem(&self) -> Result<Vec<WarningType>, SemanticErrorType> {
-        if let Some(error) = self
-            .type_checker
-            .check_type_declarations(&self.problem.objects)
-        {
-            return Err(error);
+        let (warnings, mut errors) = self.analyze_problem();
+        if errors.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(errors.remove(0))
         }
+    }
+
+    // reports every error in the problem instead of stopping at the first one
+    pub fn analyze_problem(&self) -> (Vec<WarningType>, Vec<SemanticErrorType>) {
+        let mut errors = self
+            .type_checker
+            .check_type_declarations(&self.problem.objects);
 
         // check for duplicate objects
         let mut object_types = HashMap::new();
         let mut object_positions = HashMap::new();
         for obj in self.problem.objects.iter() {
             if object_types.contains_key(obj.name) {
-                return Err(SemanticErrorType::DuplicateObjectDeclaration(
+                errors.push(SemanticErrorType::DuplicateObjectDeclaration(
                     DuplicateError {
                         symbol: obj.name.to_string(),
                         first_pos: *object_positions.get(obj.name).unwrap(),
@@ -45,13 +56,13 @@ impl<'a> ProblemSemanticAnalyzer<'a> {
 
         // check the consistency of init predicates
         for predicate in self.problem.init_state.iter() {
-            let _ = self.type_checker.check_predicate_instantiation(predicate)?;
+            errors.extend(self.type_checker.check_predicate_instantiation(predicate));
         }
 
         // check the initial task network
         if let Some(htn) = &self.problem.init_tn {
             if !htn.tn.orderings.is_acyclic() {
-                return Err(
+                errors.push(
                     SemanticErrorType::CyclicOrderingDeclaration(
                         htn.tn.ordering_pos.unwrap()
                     )
@@ -59,28 +70,109 @@ impl<'a> ProblemSemanticAnalyzer<'a> {
             }
 
             for subtask in htn.tn.subtasks.iter() {
-                let _ = self
-                    .type_checker
-                    .check_subtask_instantiation(subtask, &htn.parameters)?;
+                errors.extend(
+                    self.type_checker
+                        .check_subtask_instantiation(subtask, &htn.parameters),
+                );
             }
         }
 
         // check goal description
         match &self.problem.goal {
             Some(goal) => {
-                for predicate in goal.get_propositional_predicates() {
-                    let _ = self.type_checker.check_predicate_instantiation(predicate)?;
-                }
+                errors.extend(self.type_checker.check_goal(goal, &HashMap::new()));
             }
             None => {}
         }
 
-        Ok(self
-            .type_checker
-            .symbol_table
-            .warnings
+        let mut warnings = self.type_checker.symbol_table.warnings.clone();
+        warnings.extend(RedundancyAnalyzer::new(self.domain).analyze_problem(self.problem));
+        warnings.extend(self.check_requirements());
+        warnings.extend(self.check_reachability());
+        (warnings, errors)
+    }
+
+    // domain elements that no decomposition of the initial task network uses
+    fn check_reachability(&self) -> Vec<WarningType> {
+        let Some(init_tn) = &self.problem.init_tn else {
+            return vec![];
+        };
+        // undefined tasks are reported as errors
+        let Ok(tdg) = TDG::new(self.domain) else {
+            return vec![];
+        };
+        let mut reachable_tasks = HashSet::new();
+        for subtask in init_tn.tn.subtasks.iter() {
+            if let Ok(reachable) = tdg.reachable(subtask.task.name) {
+                reachable_tasks.extend(reachable.compounds);
+                reachable_tasks.extend(reachable.primitives);
+            }
+        }
+        let reachable_methods: HashSet<&str> = reachable_tasks
             .iter()
-            .cloned()
-            .collect())
+            .flat_map(|task| tdg.methods_of(task))
+            .collect();
+        let mut warnings = vec![];
+        let mut visited = HashSet::new();
+        for task in self.domain.compound_tasks.iter() {
+            if visited.insert(task.name) && !reachable_tasks.contains(task.name) {
+                warnings.push(WarningType::UnreachableTask(WarningInfo {
+                    symbol: task.name.to_string(),
+                    position: task.name_pos,
+                }));
+            }
+        }
+        let mut visited_methods = HashSet::new();
+        for method in self.domain.methods.iter() {
+            if visited_methods.insert(method.name.name) && !reachable_methods.contains(method.name.name) {
+                warnings.push(WarningType::UnreachableMethod(WarningInfo {
+                    symbol: method.name.name.to_string(),
+                    position: method.name.name_pos,
+                }));
+            }
+        }
+        for action in self.domain.actions.iter() {
+            if visited.insert(action.name) && !reachable_tasks.contains(action.name) {
+                warnings.push(WarningType::UnreachableAction(WarningInfo {
+                    symbol: action.name.to_string(),
+                    position: action.name_pos,
+                }));
+            }
+        }
+        warnings
+    }
+
+    // the problem may only rely on requirements of its domain
+    fn check_requirements(&self) -> Vec<WarningType> {
+        let domain_requirements = &self.type_checker.symbol_table.requirements;
+        let mut warnings = vec![];
+        for (requirement, position) in self
+            .problem
+            .requirements
+            .iter()
+            .zip(self.problem.requirement_positions.iter())
+        {
+            if !domain_requirements.contains(requirement) {
+                warnings.push(WarningType::UndeclaredProblemRequirement(WarningInfo {
+                    symbol: requirement.to_string(),
+                    position: *position,
+                }));
+            }
+        }
+        let mut used = vec![];
+        if let Some(object) = self.problem.objects.iter().find(|o| o.symbol_type.is_some()) {
+            used.push((RequirementType::TypedObjects, object.type_pos.unwrap_or(object.name_pos)));
+        }
+        if let Some(goal) = &self.problem.goal {
+            used.extend(precondition_requirements(goal, TokenPosition::default()));
+        }
+        let mut declared = declared_requirements(&self.problem.requirements);
+        declared.extend(domain_requirements.iter());
+        for warning in undeclared_requirements(&used, &declared) {
+            if let WarningType::UndeclaredRequirement(info) = warning {
+                warnings.push(WarningType::UndeclaredProblemFeature(info));
+            }
+        }
+        warnings
     }
 }
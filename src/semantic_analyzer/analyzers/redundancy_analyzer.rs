@@ -0,0 +1,342 @@
+use std::collections::HashSet;
+
+use super::*;
+
+// finds declarations that have no effect on the domain (Sleath & Bercher's modeling smells)
+pub struct RedundancyAnalyzer<'a> {
+    domain: &'a DomainAST<'a>,
+}
+
+impl<'a> RedundancyAnalyzer<'a> {
+    pub fn new(domain: &'a DomainAST<'a>) -> RedundancyAnalyzer<'a> {
+        RedundancyAnalyzer { domain }
+    }
+
+    pub fn analyze(&self) -> Vec<WarningType> {
+        let mut warnings = self.check_predicates();
+        warnings.extend(self.check_parameters());
+        warnings.extend(self.check_effects());
+        warnings
+    }
+
+    // types and tasks are often only used by problems, so they are only checked along with one
+    pub fn analyze_problem(&self, problem: &'a ProblemAST<'a>) -> Vec<WarningType> {
+        let mut warnings = self.check_types(problem);
+        warnings.extend(self.check_subtasks(problem));
+        warnings
+    }
+
+    // types that neither type a symbol nor are the parent of another type
+    fn check_types(&self, problem: &'a ProblemAST<'a>) -> Vec<WarningType> {
+        let Some(types) = &self.domain.types else {
+            return vec![];
+        };
+        let mut used_types: HashSet<&str> = types.iter().filter_map(|t| t.symbol_type).collect();
+        let mut symbols: Vec<&Symbol> = problem.objects.iter().collect();
+        if let Some(constants) = &self.domain.constants {
+            symbols.extend(constants.iter());
+        }
+        for predicate in self.domain.predicates.iter() {
+            symbols.extend(predicate.variables.iter());
+        }
+        for task in self.domain.compound_tasks.iter() {
+            symbols.extend(task.parameters.iter());
+        }
+        for method in self.domain.methods.iter() {
+            symbols.extend(method.params.iter());
+            if let Some(precondition) = &method.precondition {
+                symbols.extend(quantified_variables(precondition));
+            }
+        }
+        for action in self.domain.actions.iter() {
+            symbols.extend(action.parameters.iter());
+            for formula in [&action.preconditions, &action.effects].into_iter().flatten() {
+                symbols.extend(quantified_variables(formula));
+            }
+        }
+        used_types.extend(symbols.iter().filter_map(|symbol| symbol.symbol_type));
+
+        let mut warnings = vec![];
+        let mut visited = HashSet::new();
+        for declared_type in types.iter() {
+            if declared_type.name == "object" || !visited.insert(declared_type.name) {
+                continue;
+            }
+            if !used_types.contains(declared_type.name) {
+                warnings.push(WarningType::UnusedType(WarningInfo {
+                    symbol: declared_type.name.to_string(),
+                    position: declared_type.name_pos,
+                }));
+            }
+        }
+        warnings
+    }
+
+    // predicates that no formula refers to, or that no action changes
+    fn check_predicates(&self) -> Vec<WarningType> {
+        let mut used_predicates = HashSet::new();
+        let mut changed_predicates = HashSet::new();
+        for method in self.domain.methods.iter() {
+            if let Some(precondition) = &method.precondition {
+                used_predicates.extend(atoms(precondition).iter().map(|atom| atom.name));
+            }
+        }
+        for action in self.domain.actions.iter() {
+            if let Some(precondition) = &action.preconditions {
+                used_predicates.extend(atoms(precondition).iter().map(|atom| atom.name));
+            }
+            if let Some(effect) = &action.effects {
+                // conditions of conditional effects are not changed by the action
+                changed_predicates.extend(effect_atoms(effect).iter().map(|atom| atom.name));
+                used_predicates.extend(atoms(effect).iter().map(|atom| atom.name));
+            }
+        }
+
+        let mut warnings = vec![];
+        let mut visited = HashSet::new();
+        for predicate in self.domain.predicates.iter() {
+            if !visited.insert(predicate.name) {
+                continue;
+            }
+            let info = WarningInfo {
+                symbol: predicate.name.to_string(),
+                position: predicate.name_pos,
+            };
+            if !used_predicates.contains(predicate.name) {
+                warnings.push(WarningType::UnusedPredicate(info));
+            } else if !changed_predicates.contains(predicate.name) {
+                warnings.push(WarningType::ImmutablePredicate(info));
+            }
+        }
+        warnings
+    }
+
+    fn check_parameters(&self) -> Vec<WarningType> {
+        let mut warnings = vec![];
+        // a task parameter is used if some method relies on its value
+        for task in self.domain.compound_tasks.iter() {
+            let methods: Vec<&Method> = self
+                .domain
+                .methods
+                .iter()
+                .filter(|method| method.task.name == task.name)
+                .collect();
+            if methods.is_empty() {
+                continue;
+            }
+            for (index, parameter) in task.parameters.iter().enumerate() {
+                let is_used = methods.iter().any(|method| match method.task_terms.get(index) {
+                    Some(term) => {
+                        let is_variable = method.params.iter().any(|param| param.name == term.name);
+                        let occurrences = method.task_terms.iter().filter(|t| t.name == term.name).count();
+                        !is_variable || occurrences > 1 || method_body_terms(method).contains(term.name)
+                    }
+                    // arity errors are reported by the semantic analyzer
+                    None => true,
+                });
+                if !is_used {
+                    warnings.push(unused_parameter(parameter, task.name));
+                }
+            }
+        }
+        for method in self.domain.methods.iter() {
+            let mut used_terms = method_body_terms(method);
+            used_terms.extend(method.task_terms.iter().map(|term| term.name));
+            for parameter in method.params.iter() {
+                if !used_terms.contains(parameter.name) {
+                    warnings.push(unused_parameter(parameter, method.name.name));
+                }
+            }
+        }
+        for action in self.domain.actions.iter() {
+            let mut used_terms = HashSet::new();
+            for formula in [&action.preconditions, &action.effects].into_iter().flatten() {
+                used_terms.extend(terms(formula));
+            }
+            for parameter in action.parameters.iter() {
+                if !used_terms.contains(parameter.name) {
+                    warnings.push(unused_parameter(parameter, action.name));
+                }
+            }
+        }
+        warnings
+    }
+
+    // compound tasks and actions that neither a method nor the initial task network refers to
+    fn check_subtasks(&self, problem: &'a ProblemAST<'a>) -> Vec<WarningType> {
+        // actions of a non-hierarchical domain are never subtasks
+        if self.domain.methods.is_empty() {
+            return vec![];
+        }
+        let mut used_tasks: HashSet<&str> = self
+            .domain
+            .methods
+            .iter()
+            .flat_map(|method| method.tn.subtasks.iter().map(|subtask| subtask.task.name))
+            .collect();
+        if let Some(init_tn) = &problem.init_tn {
+            used_tasks.extend(init_tn.tn.subtasks.iter().map(|subtask| subtask.task.name));
+        }
+        let mut warnings = vec![];
+        let mut visited = HashSet::new();
+        for task in self.domain.compound_tasks.iter() {
+            if visited.insert(task.name) && !used_tasks.contains(task.name) {
+                warnings.push(WarningType::UnusedTask(WarningInfo {
+                    symbol: task.name.to_string(),
+                    position: task.name_pos,
+                }));
+            }
+        }
+        for action in self.domain.actions.iter() {
+            if visited.insert(action.name) && !used_tasks.contains(action.name) {
+                warnings.push(WarningType::UnusedAction(WarningInfo {
+                    symbol: action.name.to_string(),
+                    position: action.name_pos,
+                }));
+            }
+        }
+        warnings
+    }
+
+    // effects that repeat another effect, or a literal of the precondition
+    fn check_effects(&self) -> Vec<WarningType> {
+        let mut warnings = vec![];
+        for action in self.domain.actions.iter() {
+            let Some(effect) = &action.effects else {
+                continue;
+            };
+            let preconditions = match &action.preconditions {
+                Some(precondition) => literals(precondition),
+                None => vec![],
+            };
+            let effects = literals(effect);
+            for (index, effect) in effects.iter().enumerate() {
+                let redundancy = match effects[..index].iter().find(|e| is_same_literal(e, effect)) {
+                    Some(first) => Some(EffectRedundancy::Duplicate(first.1.name_pos)),
+                    None => preconditions
+                        .iter()
+                        .find(|p| is_same_literal(p, effect))
+                        .map(|p| EffectRedundancy::RequiredByPrecondition(p.1.name_pos)),
+                };
+                if let Some(redundancy) = redundancy {
+                    warnings.push(WarningType::RedundantEffect(EffectInfo {
+                        action: action.name.to_string(),
+                        effect: literal_to_string(effect, &action.parameters),
+                        position: effect.1.name_pos,
+                        redundancy,
+                    }));
+                }
+            }
+        }
+        warnings
+    }
+}
+
+fn unused_parameter(parameter: &Symbol, owner: &str) -> WarningType {
+    WarningType::UnusedParameter(ParameterInfo {
+        parameter: parameter.name.to_string(),
+        owner: owner.to_string(),
+        position: parameter.name_pos,
+    })
+}
+
+// terms in the precondition, subtasks, and constraints of a method (but not in its task)
+fn method_body_terms<'a>(method: &'a Method<'a>) -> HashSet<&'a str> {
+    let mut used_terms = HashSet::new();
+    if let Some(precondition) = &method.precondition {
+        used_terms.extend(terms(precondition));
+    }
+    for subtask in method.tn.subtasks.iter() {
+        used_terms.extend(subtask.terms.iter().map(|term| term.name));
+    }
+    for constraint in method.tn.constraints.iter().flatten() {
+        match constraint {
+            Constraint::Equal(a, b) | Constraint::NotEqual(a, b) => {
+                used_terms.insert(a.name);
+                used_terms.insert(b.name);
+            }
+        }
+    }
+    used_terms
+}
+
+fn subformulas<'a>(formula: &'a Formula<'a>) -> Vec<&'a Formula<'a>> {
+    match formula {
+        Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => vec![],
+        Formula::Not(f) | Formula::Exists(_, f) | Formula::ForAll(_, f) => vec![&**f],
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => fs.iter().map(|f| &**f).collect(),
+        Formula::Imply(antecedents, consequences) => {
+            antecedents.iter().chain(consequences.iter()).map(|f| &**f).collect()
+        }
+    }
+}
+
+// every atom of a formula, including those under quantifiers
+fn atoms<'a>(formula: &'a Formula<'a>) -> Vec<&'a Predicate<'a>> {
+    match formula {
+        Formula::Atom(predicate) => vec![predicate],
+        _ => subformulas(formula).into_iter().flat_map(atoms).collect(),
+    }
+}
+
+// atoms that an effect adds or deletes (leaving out the conditions of conditional effects)
+fn effect_atoms<'a>(effect: &'a Formula<'a>) -> Vec<&'a Predicate<'a>> {
+    match effect {
+        Formula::Imply(_, consequences) => consequences.iter().flat_map(|f| effect_atoms(f)).collect(),
+        Formula::Atom(predicate) => vec![predicate],
+        _ => subformulas(effect).into_iter().flat_map(effect_atoms).collect(),
+    }
+}
+
+fn terms<'a>(formula: &'a Formula<'a>) -> HashSet<&'a str> {
+    match formula {
+        Formula::Atom(predicate) => predicate.variables.iter().map(|var| var.name).collect(),
+        Formula::Equals(a, b) => HashSet::from([a.name, b.name]),
+        _ => subformulas(formula).into_iter().flat_map(terms).collect(),
+    }
+}
+
+fn quantified_variables<'a>(formula: &'a Formula<'a>) -> Vec<&'a Symbol<'a>> {
+    let mut variables: Vec<&Symbol> = match formula {
+        Formula::Exists(vars, _) | Formula::ForAll(vars, _) => vars.iter().collect(),
+        _ => vec![],
+    };
+    variables.extend(subformulas(formula).into_iter().flat_map(quantified_variables));
+    variables
+}
+
+// literals of the top-level conjunction, as (is positive, atom)
+fn literals<'a>(formula: &'a Formula<'a>) -> Vec<(bool, &'a Predicate<'a>)> {
+    match formula {
+        Formula::Atom(predicate) => vec![(true, predicate)],
+        Formula::Not(f) => match &**f {
+            Formula::Atom(predicate) => vec![(false, predicate)],
+            _ => vec![],
+        },
+        Formula::And(fs) => fs.iter().flat_map(|f| literals(f)).collect(),
+        _ => vec![],
+    }
+}
+
+fn is_same_literal(a: &(bool, &Predicate), b: &(bool, &Predicate)) -> bool {
+    a.0 == b.0
+        && a.1.name == b.1.name
+        && a.1.variables.len() == b.1.variables.len()
+        && a.1.variables.iter().zip(b.1.variables.iter()).all(|(x, y)| x.name == y.name)
+}
+
+fn literal_to_string(literal: &(bool, &Predicate), parameters: &Vec<Symbol>) -> String {
+    let mut atom = format!("({}", literal.1.name);
+    for var in literal.1.variables.iter() {
+        if parameters.iter().any(|param| param.name == var.name) {
+            atom.push_str(&format!(" ?{}", var.name));
+        } else {
+            atom.push_str(&format!(" {}", var.name));
+        }
+    }
+    atom.push(')');
+    match literal.0 {
+        true => atom,
+        false => format!("(not {})", atom),
+    }
+}
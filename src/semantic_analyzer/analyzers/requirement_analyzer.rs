@@ -0,0 +1,204 @@
+use std::collections::HashSet;
+
+use super::*;
+use crate::lexical_analyzer::{RequirementType, TokenPosition};
+
+// cross-checks the features a domain (or problem) uses against its declared requirements
+pub struct RequirementAnalyzer<'a> {
+    domain: &'a DomainAST<'a>,
+}
+
+impl<'a> RequirementAnalyzer<'a> {
+    pub fn new(domain: &'a DomainAST<'a>) -> RequirementAnalyzer<'a> {
+        RequirementAnalyzer { domain }
+    }
+
+    // planners accept domains with inconsistent requirements, so they are only warned about
+    pub fn analyze(&self) -> Vec<WarningType> {
+        let used = self.used_requirements();
+        let mut warnings = undeclared_requirements(&used, &declared_requirements(&self.domain.requirements));
+        warnings.extend(unused_requirements(
+            &self.domain.requirements,
+            &self.domain.requirement_positions,
+            &used,
+        ));
+        warnings
+    }
+
+    // every feature of the domain, along with the position of its use
+    pub fn used_requirements(&self) -> Vec<(RequirementType, TokenPosition)> {
+        let mut used = vec![];
+        // typing
+        let typed_symbol = self
+            .domain
+            .types
+            .iter()
+            .flatten()
+            .chain(self.domain.constants.iter().flatten())
+            .chain(self.domain.predicates.iter().flat_map(|p| p.variables.iter()))
+            .chain(self.domain.compound_tasks.iter().flat_map(|t| t.parameters.iter()))
+            .chain(self.domain.methods.iter().flat_map(|m| m.params.iter()))
+            .chain(self.domain.actions.iter().flat_map(|a| a.parameters.iter()))
+            .find(|symbol| symbol.symbol_type.is_some());
+        if let Some(symbol) = typed_symbol {
+            used.push((RequirementType::TypedObjects, symbol.type_pos.unwrap_or(symbol.name_pos)));
+        }
+        // hierarchy
+        if let Some(task) = self.domain.compound_tasks.first() {
+            used.push((RequirementType::Hierarchy, task.name_pos));
+        }
+        for method in self.domain.methods.iter() {
+            if let Some(precondition) = &method.precondition {
+                used.push((RequirementType::MethodPreconditions, method.name.name_pos));
+                used.extend(precondition_requirements(precondition, method.name.name_pos));
+            }
+            for constraint in method.tn.constraints.iter().flatten() {
+                used.push((RequirementType::Equality, method.name.name_pos));
+                if let Constraint::NotEqual(_, _) = constraint {
+                    used.push((RequirementType::NegativePreconditions, method.name.name_pos));
+                }
+            }
+        }
+        for action in self.domain.actions.iter() {
+            if let Some(precondition) = &action.preconditions {
+                used.extend(precondition_requirements(precondition, action.name_pos));
+            }
+            if let Some(effect) = &action.effects {
+                used.extend(effect_requirements(effect, action.name_pos));
+            }
+        }
+        used
+    }
+}
+
+// declared requirements, including the ones they are a shorthand for
+pub fn declared_requirements(requirements: &[RequirementType]) -> HashSet<RequirementType> {
+    requirements.iter().flat_map(|requirement| requirement.expand()).collect()
+}
+
+// features that are used without declaring their requirement (reported once for each requirement)
+pub fn undeclared_requirements(
+    used: &[(RequirementType, TokenPosition)],
+    declared: &HashSet<RequirementType>,
+) -> Vec<WarningType> {
+    let mut reported = HashSet::new();
+    let mut warnings = vec![];
+    for (requirement, position) in used.iter() {
+        if !declared.contains(requirement) && reported.insert(*requirement) {
+            warnings.push(WarningType::UndeclaredRequirement(WarningInfo {
+                symbol: requirement.to_string(),
+                position: *position,
+            }));
+        }
+    }
+    warnings
+}
+
+pub fn unused_requirements(
+    requirements: &[RequirementType],
+    positions: &[TokenPosition],
+    used: &[(RequirementType, TokenPosition)],
+) -> Vec<WarningType> {
+    let used: HashSet<RequirementType> = used.iter().map(|(requirement, _)| *requirement).collect();
+    let mut warnings = vec![];
+    for (requirement, position) in requirements.iter().zip(positions.iter()) {
+        // strips is the baseline of every domain, and is used even if it is not declared
+        if *requirement == RequirementType::STRIPS {
+            continue;
+        }
+        if !requirement.expand().iter().any(|r| used.contains(r)) {
+            warnings.push(WarningType::UnusedRequirement(WarningInfo {
+                symbol: requirement.to_string(),
+                position: *position,
+            }));
+        }
+    }
+    warnings
+}
+
+pub fn precondition_requirements(formula: &Formula, owner: TokenPosition) -> Vec<(RequirementType, TokenPosition)> {
+    let position = first_position(formula).unwrap_or(owner);
+    let mut used = vec![];
+    match formula {
+        Formula::Empty | Formula::Atom(_) => {}
+        Formula::Not(f) => {
+            used.push((RequirementType::NegativePreconditions, position));
+            used.extend(precondition_requirements(f, owner));
+        }
+        Formula::And(fs) | Formula::Xor(fs) => {
+            for f in fs.iter() {
+                used.extend(precondition_requirements(f, owner));
+            }
+        }
+        Formula::Or(fs) => {
+            used.push((RequirementType::DisjunctivePreconditions, position));
+            for f in fs.iter() {
+                used.extend(precondition_requirements(f, owner));
+            }
+        }
+        Formula::Imply(antecedents, consequences) => {
+            used.push((RequirementType::DisjunctivePreconditions, position));
+            for f in antecedents.iter().chain(consequences.iter()) {
+                used.extend(precondition_requirements(f, owner));
+            }
+        }
+        Formula::Exists(_, f) => {
+            used.push((RequirementType::ExistentialPreconditions, position));
+            used.extend(precondition_requirements(f, owner));
+        }
+        Formula::ForAll(_, f) => {
+            used.push((RequirementType::UniversalPreconditions, position));
+            used.extend(precondition_requirements(f, owner));
+        }
+        Formula::Equals(_, _) => {
+            used.push((RequirementType::Equality, position));
+        }
+    }
+    used
+}
+
+fn effect_requirements(formula: &Formula, owner: TokenPosition) -> Vec<(RequirementType, TokenPosition)> {
+    let position = first_position(formula).unwrap_or(owner);
+    let mut used = vec![];
+    match formula {
+        Formula::And(fs) => {
+            for f in fs.iter() {
+                used.extend(effect_requirements(f, owner));
+            }
+        }
+        Formula::Imply(antecedents, consequences) => {
+            used.push((RequirementType::ConditionalEffects, position));
+            for f in antecedents.iter() {
+                used.extend(precondition_requirements(f, owner));
+            }
+            for f in consequences.iter() {
+                used.extend(effect_requirements(f, owner));
+            }
+        }
+        Formula::ForAll(_, f) => {
+            used.push((RequirementType::ConditionalEffects, position));
+            used.extend(effect_requirements(f, owner));
+        }
+        _ => {}
+    }
+    used
+}
+
+// the position of the first symbol in a formula (connectives do not keep their positions)
+fn first_position(formula: &Formula) -> Option<TokenPosition> {
+    match formula {
+        Formula::Empty => None,
+        Formula::Atom(predicate) => Some(predicate.name_pos),
+        Formula::Equals(a, _) => Some(a.name_pos),
+        Formula::Not(f) => first_position(f),
+        Formula::Exists(vars, f) | Formula::ForAll(vars, f) => match vars.first() {
+            Some(var) => Some(var.name_pos),
+            None => first_position(f),
+        },
+        Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => fs.iter().find_map(|f| first_position(f)),
+        Formula::Imply(antecedents, consequences) => antecedents
+            .iter()
+            .chain(consequences.iter())
+            .find_map(|f| first_position(f)),
+    }
+}
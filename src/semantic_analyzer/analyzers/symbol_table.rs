@@ -2,10 +2,13 @@ use super::*;
 use std::collections::HashSet;
 use petgraph::prelude::GraphMap;
 use petgraph::Directed;
+use crate::lexical_analyzer::RequirementType;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SymbolTable<'a> {
     pub warnings: Vec<WarningType>,
+    // declared requirements, including the ones they are a shorthand for
+    pub requirements: HashSet<RequirementType>,
     pub constants: HashSet<&'a Symbol<'a>>,
     pub predicates: HashSet<&'a Predicate<'a>>,
     pub tasks: HashSet<&'a Task<'a>>,
@@ -3,6 +3,7 @@ mod type_checker;
 mod tests;
 mod analyzers;
 mod tdg;
+mod symbol_index;
 
 use crate::syntactic_analyzer::*;
 use crate::output::*;
@@ -12,4 +13,6 @@ use type_checker::*;
 extern crate petgraph;
 
 pub use analyzers::*;
-pub use tdg::TDG;
\ No newline at end of file
+pub use tdg::{TDG, ReachableSet, Landmarks};
+pub use type_checker::TypeChecker;
+pub use symbol_index::*;
\ No newline at end of file
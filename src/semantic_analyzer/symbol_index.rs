@@ -0,0 +1,311 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use super::*;
+use crate::lexical_analyzer::TokenPosition;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    Type,
+    Constant,
+    Predicate,
+    Task,
+    Action,
+    Method,
+}
+
+impl fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SymbolKind::Type => write!(f, "type"),
+            SymbolKind::Constant => write!(f, "constant"),
+            SymbolKind::Predicate => write!(f, "predicate"),
+            SymbolKind::Task => write!(f, "compound task"),
+            SymbolKind::Action => write!(f, "action"),
+            SymbolKind::Method => write!(f, "method"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceFile {
+    Domain,
+    Problem,
+}
+
+// an occurrence of a named element in the source
+#[derive(Debug, Clone)]
+pub struct SymbolReference {
+    pub kind: SymbolKind,
+    pub name: String,
+    pub file: SourceFile,
+    pub position: TokenPosition,
+    pub is_declaration: bool,
+}
+
+// declarations and uses of every named element of a domain (and its problem)
+#[derive(Debug)]
+pub struct SymbolIndex {
+    pub references: Vec<SymbolReference>,
+    signatures: HashMap<(SymbolKind, String), String>,
+}
+
+impl SymbolIndex {
+    pub fn new(domain: &DomainAST, problem: Option<&ProblemAST>) -> SymbolIndex {
+        let mut builder = IndexBuilder::new(domain, problem);
+        builder.index_domain(domain);
+        if let Some(problem) = problem {
+            builder.index_problem(problem);
+        }
+        SymbolIndex {
+            references: builder.references,
+            signatures: builder.signatures,
+        }
+    }
+
+    // the symbol whose name covers the given byte offset
+    pub fn symbol_at(&self, file: SourceFile, offset: usize) -> Option<&SymbolReference> {
+        self.references.iter().find(|reference| {
+            reference.file == file
+                && reference.position.start <= offset
+                && offset <= reference.position.end
+        })
+    }
+
+    pub fn declaration(&self, kind: SymbolKind, name: &str) -> Option<&SymbolReference> {
+        self.references
+            .iter()
+            .find(|reference| reference.is_declaration && reference.kind == kind && reference.name == name)
+    }
+
+    // all occurrences of a symbol, including its declaration
+    pub fn references(&self, kind: SymbolKind, name: &str) -> Vec<&SymbolReference> {
+        self.references
+            .iter()
+            .filter(|reference| reference.kind == kind && reference.name == name)
+            .collect()
+    }
+
+    // the declared signature of a symbol, e.g. "(at ?a - airplane ?s - segment)"
+    pub fn signature(&self, kind: SymbolKind, name: &str) -> Option<&str> {
+        self.signatures
+            .get(&(kind, name.to_string()))
+            .map(|signature| signature.as_str())
+    }
+}
+
+struct IndexBuilder<'b> {
+    file: SourceFile,
+    constants: HashSet<&'b str>,
+    actions: HashSet<&'b str>,
+    references: Vec<SymbolReference>,
+    signatures: HashMap<(SymbolKind, String), String>,
+}
+
+impl<'b> IndexBuilder<'b> {
+    fn new(domain: &'b DomainAST, problem: Option<&'b ProblemAST>) -> IndexBuilder<'b> {
+        let mut constants: HashSet<&str> = HashSet::new();
+        if let Some(domain_constants) = &domain.constants {
+            constants.extend(domain_constants.iter().map(|c| c.name));
+        }
+        if let Some(problem) = problem {
+            constants.extend(problem.objects.iter().map(|o| o.name));
+        }
+        IndexBuilder {
+            file: SourceFile::Domain,
+            constants,
+            actions: domain.actions.iter().map(|a| a.name).collect(),
+            references: vec![],
+            signatures: HashMap::new(),
+        }
+    }
+
+    fn index_domain(&mut self, domain: &DomainAST) {
+        if let Some(types) = &domain.types {
+            for t in types.iter() {
+                self.declare(SymbolKind::Type, t.name, t.name_pos, format!("type {}", typed_list(&vec![t.clone()], false)));
+                self.add_type_reference(t);
+            }
+        }
+        if let Some(constants) = &domain.constants {
+            self.index_constants(constants);
+        }
+        for predicate in domain.predicates.iter() {
+            let signature = format!("({} {})", predicate.name, typed_list(&predicate.variables, true));
+            self.declare(SymbolKind::Predicate, predicate.name, predicate.name_pos, signature);
+            self.add_parameter_types(&predicate.variables);
+        }
+        for task in domain.compound_tasks.iter() {
+            let signature = format!("(:task {} :parameters ({}))", task.name, typed_list(&task.parameters, true));
+            self.declare(SymbolKind::Task, task.name, task.name_pos, signature);
+            self.add_parameter_types(&task.parameters);
+        }
+        for action in domain.actions.iter() {
+            let signature = format!("(:action {} :parameters ({}))", action.name, typed_list(&action.parameters, true));
+            self.declare(SymbolKind::Action, action.name, action.name_pos, signature);
+            self.add_parameter_types(&action.parameters);
+            let scope: Vec<&str> = action.parameters.iter().map(|p| p.name).collect();
+            if let Some(precondition) = &action.preconditions {
+                self.index_formula(precondition, &scope);
+            }
+            if let Some(effect) = &action.effects {
+                self.index_formula(effect, &scope);
+            }
+        }
+        for method in domain.methods.iter() {
+            let task_terms: Vec<String> = method.task_terms.iter().map(|t| term(t, &method.params)).collect();
+            let signature = format!(
+                "(:method {} :parameters ({}) :task ({} {}))",
+                method.name.name,
+                typed_list(&method.params, true),
+                method.task.name,
+                task_terms.join(" ")
+            );
+            self.declare(SymbolKind::Method, method.name.name, method.name.name_pos, signature);
+            self.add_parameter_types(&method.params);
+            let scope: Vec<&str> = method.params.iter().map(|p| p.name).collect();
+            self.add_task_reference(&method.task);
+            self.add_constant_references(&method.task_terms, &scope);
+            if let Some(precondition) = &method.precondition {
+                self.index_formula(precondition, &scope);
+            }
+            self.index_htn(&method.tn, &scope);
+        }
+    }
+
+    fn index_problem(&mut self, problem: &ProblemAST) {
+        self.file = SourceFile::Problem;
+        self.index_constants(&problem.objects);
+        if let Some(init_tn) = &problem.init_tn {
+            let mut scope = vec![];
+            if let Some(parameters) = &init_tn.parameters {
+                self.add_parameter_types(parameters);
+                scope.extend(parameters.iter().map(|p| p.name));
+            }
+            self.index_htn(&init_tn.tn, &scope);
+        }
+        for fact in problem.init_state.iter() {
+            self.add_predicate_reference(fact, &vec![]);
+        }
+        if let Some(goal) = &problem.goal {
+            self.index_formula(goal, &vec![]);
+        }
+    }
+
+    fn index_constants(&mut self, constants: &Vec<Symbol>) {
+        for constant in constants.iter() {
+            let signature = format!("constant {}", typed_list(&vec![constant.clone()], false));
+            self.declare(SymbolKind::Constant, constant.name, constant.name_pos, signature);
+            self.add_type_reference(constant);
+        }
+    }
+
+    fn index_htn(&mut self, tn: &HTN, scope: &Vec<&str>) {
+        for subtask in tn.subtasks.iter() {
+            self.add_task_reference(&subtask.task);
+            self.add_constant_references(&subtask.terms, scope);
+        }
+    }
+
+    fn index_formula(&mut self, formula: &Formula, scope: &Vec<&str>) {
+        match formula {
+            Formula::Empty => {}
+            Formula::Atom(predicate) => {
+                self.add_predicate_reference(predicate, scope);
+            }
+            Formula::Not(f) => {
+                self.index_formula(f, scope);
+            }
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                for f in fs.iter() {
+                    self.index_formula(f, scope);
+                }
+            }
+            Formula::Imply(antecedents, consequences) => {
+                for f in antecedents.iter().chain(consequences.iter()) {
+                    self.index_formula(f, scope);
+                }
+            }
+            Formula::Exists(vars, f) | Formula::ForAll(vars, f) => {
+                self.add_parameter_types(vars);
+                let mut new_scope = scope.clone();
+                new_scope.extend(vars.iter().map(|v| v.name));
+                self.index_formula(f, &new_scope);
+            }
+            Formula::Equals(a, b) => {
+                self.add_constant_references(&vec![a.clone(), b.clone()], scope);
+            }
+        }
+    }
+
+    fn declare(&mut self, kind: SymbolKind, name: &str, position: TokenPosition, signature: String) {
+        self.add(kind, name, position, true);
+        // keep the first declaration of duplicated symbols
+        self.signatures.entry((kind, name.to_string())).or_insert(signature);
+    }
+
+    fn add(&mut self, kind: SymbolKind, name: &str, position: TokenPosition, is_declaration: bool) {
+        self.references.push(SymbolReference {
+            kind,
+            name: name.to_string(),
+            file: self.file,
+            position,
+            is_declaration,
+        });
+    }
+
+    fn add_type_reference(&mut self, symbol: &Symbol) {
+        if let (Some(symbol_type), Some(type_pos)) = (symbol.symbol_type, symbol.type_pos) {
+            self.add(SymbolKind::Type, symbol_type, type_pos, false);
+        }
+    }
+
+    fn add_parameter_types(&mut self, parameters: &Vec<Symbol>) {
+        for parameter in parameters.iter() {
+            self.add_type_reference(parameter);
+        }
+    }
+
+    fn add_task_reference(&mut self, task: &Symbol) {
+        // subtasks may refer to both compound and primitive tasks
+        let kind = match self.actions.contains(task.name) {
+            true => SymbolKind::Action,
+            false => SymbolKind::Task,
+        };
+        self.add(kind, task.name, task.name_pos, false);
+    }
+
+    fn add_predicate_reference(&mut self, predicate: &Predicate, scope: &Vec<&str>) {
+        self.add(SymbolKind::Predicate, predicate.name, predicate.name_pos, false);
+        self.add_constant_references(&predicate.variables, scope);
+    }
+
+    fn add_constant_references(&mut self, terms: &Vec<Symbol>, scope: &Vec<&str>) {
+        for term in terms.iter() {
+            // parameters shadow constants
+            if !scope.contains(&term.name) && self.constants.contains(term.name) {
+                self.add(SymbolKind::Constant, term.name, term.name_pos, false);
+            }
+        }
+    }
+}
+
+// renders a declaration list, e.g. "?a - airplane ?s - segment"
+fn typed_list(symbols: &Vec<Symbol>, are_variables: bool) -> String {
+    let prefix = if are_variables { "?" } else { "" };
+    symbols
+        .iter()
+        .map(|symbol| match symbol.symbol_type {
+            Some(symbol_type) => format!("{}{} - {}", prefix, symbol.name, symbol_type),
+            None => format!("{}{}", prefix, symbol.name),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn term(symbol: &Symbol, parameters: &Vec<Symbol>) -> String {
+    match parameters.iter().any(|p| p.name == symbol.name) {
+        true => format!("?{}", symbol.name),
+        false => symbol.name.to_string(),
+    }
+}
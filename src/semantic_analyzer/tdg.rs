@@ -1,6 +1,11 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use petgraph::algo::tarjan_scc;
+use petgraph::prelude::GraphMap;
+use petgraph::Directed;
+
 use super::*;
+use crate::lexical_analyzer::TokenPosition;
 
 pub struct TDG<'a> {
     tasks: Vec<(&'a str, TaskType)>,
@@ -10,7 +15,8 @@ pub struct TDG<'a> {
 }
 
 impl<'a> TDG<'a> {
-    pub fn new(domain: &'a DomainAST<'a>) -> TDG<'a> {
+    // fails on the first method whose task or subtask is not declared
+    pub fn new(domain: &'a DomainAST<'a>) -> Result<TDG<'a>, SemanticErrorType> {
         // collect task names
         let mut tasks: Vec<(&str, TaskType)> = vec![];
         tasks.extend(
@@ -37,13 +43,15 @@ impl<'a> TDG<'a> {
         for (method_index, method) in domain.methods.iter().enumerate() {
             methods.push((&method.name, method.tn.clone()));
             match task_indices.get(method.task.name) {
-                Some(task_index) => match to_methods.get_mut(task_index) {
-                    Some(set) => {
-                        set.insert(method_index);
-                    }
-                    None => panic!("{} not found", task_index),
-                },
-                None => panic!("{} is not defined", method.task.name),
+                Some(task_index) => {
+                    to_methods.get_mut(task_index).unwrap().insert(method_index);
+                }
+                None => {
+                    return Err(SemanticErrorType::UndefinedTask(UndefinedSymbolError {
+                        symbol: method.task.name.to_string(),
+                        position: method.task.name_pos,
+                    }));
+                }
             }
         }
 
@@ -54,36 +62,80 @@ impl<'a> TDG<'a> {
                 .subtasks
                 .iter()
                 .map(|x| match task_indices.get(x.task.name) {
-                    Some(id) => *id,
-                    None => panic!("{} not found", x.task.name),
+                    Some(id) => Ok(*id),
+                    None => Err(SemanticErrorType::UndefinedSubtask(UndefinedSymbolError {
+                        symbol: x.task.name.to_string(),
+                        position: x.task.name_pos,
+                    })),
                 })
-                .collect();
+                .collect::<Result<_, _>>()?;
             to_tasks.insert(method_index, tasks);
         }
-        TDG {
+        Ok(TDG {
             tasks: tasks,
             methods: methods,
             edges_from_tasks: to_methods,
             edges_to_tasks: to_tasks,
-        }
+        })
     }
 
-    pub fn reachable(&self, task_name: &str) -> ReachableSet {
-        let mut reach_t = HashSet::new();
-        let task_index = match self
-            .tasks
+    pub fn compound_tasks(&self) -> Vec<&'a str> {
+        self.tasks
             .iter()
-            .enumerate()
-            .filter(|(_, (name, _))| *name == task_name)
-            .next()
-            .unwrap() {
+            .filter(|(_, t_type)| *t_type == TaskType::Compound)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    pub fn primitive_tasks(&self) -> Vec<&'a str> {
+        self.tasks
+            .iter()
+            .filter(|(_, t_type)| *t_type == TaskType::Primitive)
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    // methods in the order they are declared
+    pub fn methods(&self) -> Vec<&'a str> {
+        self.methods.iter().map(|(name, _)| name.name).collect()
+    }
+
+    // methods that decompose the task (in the order they are declared)
+    pub fn methods_of(&self, task_name: &str) -> Vec<&'a str> {
+        let Some(task_index) = self.tasks.iter().position(|(name, _)| *name == task_name) else {
+            return vec![];
+        };
+        let mut methods: Vec<usize> = match self.edges_from_tasks.get(&task_index) {
+            Some(methods) => methods.iter().cloned().collect(),
+            None => vec![],
+        };
+        methods.sort();
+        methods.iter().map(|m| self.methods[*m].0.name).collect()
+    }
+
+    // distinct tasks in the task network of the method (in the order they are declared)
+    pub fn subtasks_of(&self, method_name: &str) -> Vec<&'a str> {
+        let Some(method_index) = self.methods.iter().position(|(name, _)| name.name == method_name) else {
+            return vec![];
+        };
+        let mut tasks: Vec<usize> = match self.edges_to_tasks.get(&method_index) {
+            Some(tasks) => tasks.iter().cloned().collect(),
+            None => vec![],
+        };
+        tasks.sort();
+        tasks.iter().map(|t| self.tasks[*t].0).collect()
+    }
+
+    pub fn reachable(&self, task_name: &str) -> Result<ReachableSet<'a>, SemanticErrorType> {
+        let mut reach_t = HashSet::new();
+        let task_index = match self.find_task(task_name)? {
                 // if primitive, the only reachable task is itself
                 (_, (name, TaskType::Primitive)) => {
-                    return ReachableSet {
+                    return Ok(ReachableSet {
                         primitives: HashSet::from([*name]),
                         compounds: HashSet::new(),
                         nullable: false
-                    };
+                    });
                 }
                 // if compound, add the index for further processing
                 (i, (_, TaskType::Compound)) => {
@@ -128,10 +180,112 @@ impl<'a> TDG<'a> {
                 }
             }
         }
-        ReachableSet {
+        Ok(ReachableSet {
             primitives,
             compounds,
             nullable: nullables.contains(task_name),
+        })
+    }
+
+    // a task that is queried by name has no position in the files
+    fn find_task(&self, task_name: &str) -> Result<(usize, &(&'a str, TaskType)), SemanticErrorType> {
+        self.tasks
+            .iter()
+            .enumerate()
+            .find(|(_, (name, _))| *name == task_name)
+            .ok_or_else(|| {
+                SemanticErrorType::UndefinedTask(UndefinedSymbolError {
+                    symbol: task_name.to_string(),
+                    position: TokenPosition::default(),
+                })
+            })
+    }
+
+    // tasks that can be decomposed into (possibly no) primitive tasks
+    pub fn refinable_tasks(&self) -> HashSet<&'a str> {
+        let mut refinable: HashSet<usize> = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, t_type))| *t_type == TaskType::Primitive)
+            .map(|(index, _)| index)
+            .collect();
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for (task, methods) in self.edges_from_tasks.iter() {
+                if refinable.contains(task) {
+                    continue;
+                }
+                let has_refinable_method = methods
+                    .iter()
+                    .any(|method| self.edges_to_tasks[method].iter().all(|subtask| refinable.contains(subtask)));
+                if has_refinable_method {
+                    refinable.insert(*task);
+                    is_changed = true;
+                }
+            }
+        }
+        refinable.iter().map(|index| self.tasks[*index].0).collect()
+    }
+
+    // why a task can not be refined, following the blocked subtasks of its methods down to the root causes
+    pub fn explain_unrefinable(&self, task_name: &str) -> BlockingCause {
+        let refinable = self.refinable_tasks();
+        self.blocking_cause(task_name, &refinable, &mut vec![], &mut HashSet::new())
+    }
+
+    fn blocking_cause(
+        &self,
+        task_name: &str,
+        refinable: &HashSet<&'a str>,
+        chain: &mut Vec<&'a str>,
+        explained: &mut HashSet<&'a str>,
+    ) -> BlockingCause {
+        let methods = self.methods_of(task_name);
+        if methods.is_empty() {
+            return BlockingCause::NoMethods;
+        }
+        let task_name = self.tasks[self.get_task_index(task_name)].0;
+        chain.push(task_name);
+        explained.insert(task_name);
+        let mut blocked_methods = vec![];
+        for method in methods {
+            let mut blocked_subtasks = vec![];
+            for subtask in self.subtasks_of(method) {
+                if refinable.contains(subtask) {
+                    continue;
+                }
+                let cause = if chain.contains(&subtask) {
+                    BlockingCause::LoopsBack
+                } else if explained.contains(subtask) {
+                    BlockingCause::ExplainedAbove
+                } else {
+                    self.blocking_cause(subtask, refinable, chain, explained)
+                };
+                blocked_subtasks.push(BlockedTask {
+                    task: subtask.to_string(),
+                    cause,
+                });
+            }
+            // only the methods of a refinable task may have no blocked subtasks
+            if !blocked_subtasks.is_empty() {
+                blocked_methods.push(BlockedMethod {
+                    method: method.to_string(),
+                    blocked_subtasks,
+                });
+            }
+        }
+        chain.pop();
+        let is_cycle = blocked_methods.iter().all(|method| {
+            method
+                .blocked_subtasks
+                .iter()
+                .all(|subtask| subtask.cause == BlockingCause::LoopsBack)
+        });
+        match is_cycle {
+            true => BlockingCause::OnlyCycles(blocked_methods),
+            false => BlockingCause::Blocked(blocked_methods),
         }
     }
 
@@ -251,6 +405,190 @@ impl<'a> TDG<'a> {
         return recursion_type;
     }
 
+    // strongly connected components of the TDG that have a cycle, each classified by the
+    // worst kind of cycle in it, along with a shortest cycle of that kind
+    pub fn recursive_components(&self, nullable_symbols: &HashSet<&'a str>) -> Vec<RecursiveComponent> {
+        let components = self.cyclic_components();
+        components
+            .iter()
+            .map(|component| {
+                let edges = self.cycle_edges(component, nullable_symbols);
+                RecursiveComponent {
+                    tasks: component.iter().map(|task| self.tasks[*task].0.to_string()).collect(),
+                    recursion: self.classify_component(&edges),
+                }
+            })
+            .collect()
+    }
+
+    // strongly connected components of the TDG (over tasks) that have a cycle
+    fn cyclic_components(&self) -> Vec<Vec<usize>> {
+        let mut task_graph: GraphMap<usize, (), Directed> = GraphMap::new();
+        for index in 0..self.tasks.len() {
+            task_graph.add_node(index);
+        }
+        for (task, methods) in self.edges_from_tasks.iter() {
+            for method in methods {
+                for subtask in self.edges_to_tasks[method].iter() {
+                    task_graph.add_edge(*task, *subtask, ());
+                }
+            }
+        }
+        let mut components: Vec<Vec<usize>> = tarjan_scc(&task_graph)
+            .into_iter()
+            .filter(|component| component.len() > 1 || task_graph.contains_edge(component[0], component[0]))
+            .map(|mut component| {
+                component.sort();
+                component
+            })
+            .collect();
+        components.sort();
+        components
+    }
+
+    // the acyclic, regular and tail-recursive classes that the hierarchy belongs to, along with a
+    // method that violates each of the other classes, and whether all methods are totally ordered
+    pub fn hierarchy_class(&self, nullable_symbols: &HashSet<&'a str>) -> HierarchyClassification {
+        let mut component_of = HashMap::new();
+        for (i, component) in self.cyclic_components().iter().enumerate() {
+            for task in component {
+                component_of.insert(*task, i);
+            }
+        }
+        let mut task_of = HashMap::new();
+        for (task, methods) in self.edges_from_tasks.iter() {
+            for method in methods {
+                task_of.insert(*method, *task);
+            }
+        }
+        let mut partially_ordered_method = None;
+        let mut cyclic_method = None;
+        let mut irregular_method = None;
+        let mut non_tail_recursive_method = None;
+        for (method, (name, tn)) in self.methods.iter().enumerate() {
+            let subtasks: Vec<usize> = tn.subtasks.iter().map(|x| self.get_task_index(x.task.name)).collect();
+            let predecessors: Vec<HashSet<usize>> = (0..subtasks.len()).map(|i| predecessors(tn, i)).collect();
+            // the subtasks that may come after the one at the given position
+            let successors = |position: usize| -> Vec<usize> {
+                (0..subtasks.len())
+                    .filter(|i| *i != position && !predecessors[position].contains(i))
+                    .collect()
+            };
+            let is_totally_ordered = (0..subtasks.len())
+                .all(|i| (0..i).all(|j| predecessors[i].contains(&j) || predecessors[j].contains(&i)));
+            if !is_totally_ordered && partially_ordered_method.is_none() {
+                partially_ordered_method = Some(name.name.to_string());
+            }
+            // regular methods have at most one compound subtask, which comes last
+            let compounds: Vec<usize> = (0..subtasks.len())
+                .filter(|i| self.tasks[subtasks[*i]].1 == TaskType::Compound)
+                .collect();
+            let is_regular = match compounds[..] {
+                [] => true,
+                [compound] => successors(compound).is_empty(),
+                _ => false,
+            };
+            if !is_regular && irregular_method.is_none() {
+                irregular_method = Some(name.name.to_string());
+            }
+            let component = match component_of.get(&task_of[&method]) {
+                Some(component) => component,
+                None => continue,
+            };
+            let recursive_positions: Vec<usize> = (0..subtasks.len())
+                .filter(|i| component_of.get(&subtasks[*i]) == Some(component))
+                .collect();
+            if !recursive_positions.is_empty() && cyclic_method.is_none() {
+                cyclic_method = Some(name.name.to_string());
+            }
+            // a recursive subtask is in tail position if everything that may follow it is nullable
+            let is_tail_recursive = recursive_positions.iter().all(|position| {
+                successors(*position).iter().all(|i| nullable_symbols.contains(self.tasks[subtasks[*i]].0))
+            });
+            if !is_tail_recursive && non_tail_recursive_method.is_none() {
+                non_tail_recursive_method = Some(name.name.to_string());
+            }
+        }
+        let mut classes = vec![];
+        let mut violations = vec![];
+        for (class, method) in [
+            (HierarchyClass::Acyclic, cyclic_method),
+            (HierarchyClass::Regular, irregular_method),
+            (HierarchyClass::TailRecursive, non_tail_recursive_method),
+        ] {
+            match method {
+                Some(method) => violations.push((class, method)),
+                None => classes.push(class),
+            }
+        }
+        HierarchyClassification {
+            classes,
+            violations,
+            partially_ordered_method,
+        }
+    }
+
+    // decompositions from a task of the component into one of its subtasks in the component
+    fn cycle_edges(&self, component: &[usize], nullable_symbols: &HashSet<&'a str>) -> Vec<CycleEdge> {
+        let mut edges = vec![];
+        for task in component.iter() {
+            let mut methods: Vec<usize> = self.edges_from_tasks[task].iter().cloned().collect();
+            methods.sort();
+            for method in methods {
+                let tn = &self.methods[method].1;
+                let subtasks: Vec<usize> = tn.subtasks.iter().map(|x| self.get_task_index(x.task.name)).collect();
+                for (position, subtask) in subtasks.iter().enumerate() {
+                    if !component.contains(subtask) {
+                        continue;
+                    }
+                    let predecessors = predecessors(tn, position);
+                    let is_nullable = |i: &usize| nullable_symbols.contains(self.tasks[subtasks[*i]].0);
+                    // the tasks that may come after the subtask
+                    let suffix: Vec<usize> = (0..subtasks.len())
+                        .filter(|i| *i != position && !predecessors.contains(i))
+                        .collect();
+                    edges.push(CycleEdge {
+                        source: *task,
+                        method,
+                        target: *subtask,
+                        has_nullable_prefix: predecessors.iter().all(is_nullable),
+                        has_empty_suffix: suffix.is_empty(),
+                        has_nullable_suffix: suffix.iter().all(is_nullable),
+                    });
+                }
+            }
+        }
+        edges
+    }
+
+    fn classify_component(&self, edges: &[CycleEdge]) -> RecursionType {
+        let to_names = |cycle: Vec<(usize, usize)>| -> Vec<(String, String)> {
+            cycle
+                .iter()
+                .map(|(task, method)| (self.tasks[*task].0.to_string(), self.methods[*method].0.name.to_string()))
+                .collect()
+        };
+        // every cycle with an empty prefix is either empty, grows and shrinks, or grows
+        if let Some(cycle) = shortest_cycle(
+            edges,
+            |e| e.has_nullable_prefix && e.has_nullable_suffix,
+            |e| !e.has_empty_suffix,
+        ) {
+            return RecursionType::GrowAndShrinkRecursion(to_names(cycle));
+        }
+        if let Some(cycle) = shortest_cycle(edges, |e| e.has_nullable_prefix && e.has_empty_suffix, |_| true) {
+            return RecursionType::EmptyRecursion(to_names(cycle));
+        }
+        if let Some(cycle) = shortest_cycle(edges, |e| e.has_nullable_prefix, |e| !e.has_nullable_suffix) {
+            return RecursionType::GrowingEmptyPrefixRecursion(to_names(cycle));
+        }
+        // every edge inside a component is on some cycle
+        match shortest_cycle(edges, |_| true, |e| !e.has_empty_suffix) {
+            Some(cycle) => RecursionType::Recursive(to_names(cycle)),
+            None => RecursionType::TailRecursion(to_names(shortest_cycle(edges, |_| true, |_| true).unwrap())),
+        }
+    }
+
     fn get_prefix(&self, task_index: usize, method_index: usize) -> Vec<usize> {
         let (_, method) = &self.methods[method_index];
         let (task, _) = &self.tasks[task_index];
@@ -516,6 +854,235 @@ impl<'a> TDG<'a> {
         }
         result
     }
+
+    // compound tasks, methods and actions that occur in every decomposition of the given
+    // tasks into primitive ones, or none if the tasks can not be refined
+    pub fn landmarks(&self, tasks: &[&str]) -> Result<Option<Landmarks<'a>>, SemanticErrorType> {
+        let n_tasks = self.tasks.len();
+        let refinable: HashSet<usize> = self
+            .refinable_tasks()
+            .iter()
+            .map(|task| self.get_task_index(task))
+            .collect();
+        // methods are numbered after the tasks, and a missing set contains every node
+        let mut landmarks: Vec<Option<HashSet<usize>>> = vec![None; n_tasks + self.methods.len()];
+        for (index, (_, t_type)) in self.tasks.iter().enumerate() {
+            if *t_type == TaskType::Primitive {
+                landmarks[index] = Some(HashSet::from([index]));
+            }
+        }
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            // a method needs the landmarks of all of its subtasks
+            for (method, subtasks) in self.edges_to_tasks.iter() {
+                let mut value = Some(HashSet::from([n_tasks + method]));
+                for subtask in subtasks.iter() {
+                    value = value.zip(landmarks[*subtask].as_ref()).map(|(mut value, subtask_landmarks)| {
+                        value.extend(subtask_landmarks);
+                        value
+                    });
+                }
+                if value != landmarks[n_tasks + method] {
+                    landmarks[n_tasks + method] = value;
+                    is_changed = true;
+                }
+            }
+            // a task needs what all of its usable methods have in common
+            for (task, methods) in self.edges_from_tasks.iter() {
+                let usable = methods
+                    .iter()
+                    .filter(|method| self.edges_to_tasks[method].iter().all(|subtask| refinable.contains(subtask)));
+                let mut common: Option<HashSet<usize>> = None;
+                for method in usable {
+                    if let Some(method_landmarks) = &landmarks[n_tasks + method] {
+                        common = Some(match common {
+                            None => method_landmarks.clone(),
+                            Some(common) => common.intersection(method_landmarks).cloned().collect(),
+                        });
+                    }
+                }
+                let value = common.map(|mut common| {
+                    common.insert(*task);
+                    common
+                });
+                if value.is_some() && value != landmarks[*task] {
+                    landmarks[*task] = value;
+                    is_changed = true;
+                }
+            }
+        }
+        let mut nodes = HashSet::new();
+        for task in tasks.iter() {
+            let (index, _) = self.find_task(task)?;
+            match &landmarks[index] {
+                Some(task_landmarks) if refinable.contains(&index) => nodes.extend(task_landmarks),
+                _ => return Ok(None),
+            }
+        }
+        let mut nodes: Vec<usize> = nodes.into_iter().collect();
+        nodes.sort();
+        let mut result = Landmarks {
+            compound_tasks: vec![],
+            methods: vec![],
+            actions: vec![],
+        };
+        for node in nodes {
+            match self.tasks.get(node) {
+                Some((name, TaskType::Compound)) => result.compound_tasks.push(name),
+                Some((name, TaskType::Primitive)) => result.actions.push(name),
+                None => result.methods.push(self.methods[node - n_tasks].0.name),
+            }
+        }
+        Ok(Some(result))
+    }
+
+    // for every refinable compound task, the minimum number of primitive tasks and the
+    // minimum decomposition depth over all of its refinements, along with the method
+    // to choose for each (primitive tasks have length one and depth zero)
+    pub fn compute_task_bounds(&self) -> Vec<TaskBounds> {
+        let mut lengths: HashMap<usize, (u64, Option<usize>)> = HashMap::new();
+        let mut depths: HashMap<usize, (u64, Option<usize>)> = HashMap::new();
+        for (index, (_, t_type)) in self.tasks.iter().enumerate() {
+            if *t_type == TaskType::Primitive {
+                lengths.insert(index, (1, None));
+                depths.insert(index, (0, None));
+            }
+        }
+        let mut methods: Vec<(&usize, &HashSet<usize>)> = self.edges_from_tasks.iter().collect();
+        methods.sort_by_key(|(task, _)| **task);
+        let mut is_changed = true;
+        while is_changed {
+            is_changed = false;
+            for (task, task_methods) in methods.iter() {
+                let mut task_methods: Vec<&usize> = task_methods.iter().collect();
+                task_methods.sort();
+                for method in task_methods {
+                    let subtasks: Vec<usize> = self.methods[*method]
+                        .1
+                        .subtasks
+                        .iter()
+                        .map(|x| self.get_task_index(x.task.name))
+                        .collect();
+                    if !subtasks.iter().all(|subtask| lengths.contains_key(subtask)) {
+                        continue;
+                    }
+                    let length = subtasks.iter().fold(0u64, |sum, subtask| sum.saturating_add(lengths[subtask].0));
+                    let depth = 1 + subtasks.iter().map(|subtask| depths[subtask].0).max().unwrap_or(0);
+                    if lengths.get(task).is_none_or(|(previous, _)| length < *previous) {
+                        lengths.insert(**task, (length, Some(*method)));
+                        is_changed = true;
+                    }
+                    if depths.get(task).is_none_or(|(previous, _)| depth < *previous) {
+                        depths.insert(**task, (depth, Some(*method)));
+                        is_changed = true;
+                    }
+                }
+            }
+        }
+        let method_name = |method: Option<usize>| method.map(|m| self.methods[m].0.name.to_string());
+        self.tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, t_type))| *t_type == TaskType::Compound)
+            .filter_map(|(index, (name, _))| {
+                let (min_plan_length, length_method) = lengths.get(&index)?;
+                let (min_depth, depth_method) = depths[&index];
+                Some(TaskBounds {
+                    task: name.to_string(),
+                    min_plan_length: *min_plan_length,
+                    length_method: method_name(*length_method).unwrap(),
+                    min_depth,
+                    depth_method: method_name(depth_method).unwrap(),
+                })
+            })
+            .collect()
+    }
+}
+
+// a decomposition of a task into one of its subtasks, inside a strongly connected component
+struct CycleEdge {
+    source: usize,
+    method: usize,
+    target: usize,
+    // all subtasks ordered before the target are nullable
+    has_nullable_prefix: bool,
+    // no subtask may come after the target
+    has_empty_suffix: bool,
+    // all subtasks that may come after the target are nullable
+    has_nullable_suffix: bool,
+}
+
+// a shortest cycle that only uses allowed edges, and uses at least one required edge,
+// as (task, method) pairs where the last task closes the cycle
+fn shortest_cycle(
+    edges: &[CycleEdge],
+    is_allowed: impl Fn(&CycleEdge) -> bool,
+    is_required: impl Fn(&CycleEdge) -> bool,
+) -> Option<Vec<(usize, usize)>> {
+    let allowed: Vec<&CycleEdge> = edges.iter().filter(|e| is_allowed(e)).collect();
+    let mut shortest: Option<Vec<&CycleEdge>> = None;
+    for first in allowed.iter().filter(|e| is_required(e)) {
+        // breadth-first search back from the target of the edge to its source
+        let mut parents: HashMap<usize, &CycleEdge> = HashMap::new();
+        let mut queue = VecDeque::from([first.target]);
+        let mut visited = HashSet::from([first.target]);
+        while let Some(task) = queue.pop_front() {
+            if task == first.source {
+                break;
+            }
+            for edge in allowed.iter().filter(|e| e.source == task) {
+                if visited.insert(edge.target) {
+                    parents.insert(edge.target, edge);
+                    queue.push_back(edge.target);
+                }
+            }
+        }
+        if !visited.contains(&first.source) {
+            continue;
+        }
+        let mut path = vec![];
+        let mut task = first.source;
+        while task != first.target {
+            let edge = parents[&task];
+            path.push(edge);
+            task = edge.source;
+        }
+        path.push(first);
+        path.reverse();
+        if shortest.as_ref().is_none_or(|cycle| path.len() < cycle.len()) {
+            shortest = Some(path);
+        }
+    }
+    shortest.map(|cycle| {
+        let mut pairs: Vec<(usize, usize)> = cycle.iter().map(|e| (e.source, e.method)).collect();
+        pairs.push((cycle[0].source, cycle[cycle.len() - 1].method));
+        pairs
+    })
+}
+
+// positions of the subtasks that are (transitively) ordered before the given one
+fn predecessors(tn: &HTN, position: usize) -> HashSet<usize> {
+    match &tn.orderings {
+        TaskOrdering::Total => (0..position).collect(),
+        TaskOrdering::Partial(orderings) => {
+            let index_of = |id: &str| tn.subtasks.iter().position(|x| x.id.as_ref().is_some_and(|s| s.name == id));
+            let edges: Vec<(usize, usize)> = orderings
+                .iter()
+                .filter_map(|(first, second)| Some((index_of(first)?, index_of(second)?)))
+                .collect();
+            let mut predecessors = HashSet::new();
+            let mut stack = vec![position];
+            while let Some(current) = stack.pop() {
+                for (first, _) in edges.iter().filter(|(_, second)| *second == current) {
+                    if predecessors.insert(*first) {
+                        stack.push(*first);
+                    }
+                }
+            }
+            predecessors
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -524,6 +1091,14 @@ enum TaskType {
     Compound,
 }
 
+// in declaration order
+#[derive(Debug, PartialEq, Eq)]
+pub struct Landmarks<'a> {
+    pub compound_tasks: Vec<&'a str>,
+    pub methods: Vec<&'a str>,
+    pub actions: Vec<&'a str>,
+}
+
 pub struct ReachableSet<'a> {
     pub primitives: HashSet<&'a str>,
     pub compounds: HashSet<&'a str>,
@@ -156,4 +156,49 @@ pub fn unsat_method_prec_test () {
         }
         _ => panic!()
     }
-}
\ No newline at end of file
+}
+#[test]
+pub fn undecided_action_prec_test () {
+    // a contradiction whose encoding is too large to be checked
+    let disjuncts = "(and (at ?l1) (at ?l1)) ".repeat(35000);
+    let program = format!(
+        "(define (domain bal)
+            (:predicates 
+                (at ?l)
+            )
+            (:action p_1
+            :parameters(?l1)
+            :precondition (and
+                    (not (at ?l1))
+                    (or {})
+                )
+            )
+        ) ",
+        disjuncts
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+            match semantic_analyzer.verify_domain() {
+                Ok(sym_table) => {
+                    let warnings: Vec<String> = sym_table
+                        .warnings
+                        .iter()
+                        .filter(|w| matches!(w, WarningType::UndecidedSatisfiability(_)))
+                        .map(|w| w.to_string())
+                        .collect();
+                    assert_eq!(
+                        warnings,
+                        vec!["line 5: could not decide whether the precondition of action p_1 is satisfiable, so it was not checked for contradictions"]
+                    );
+                }
+                _ => panic!()
+            }
+        }
+        _ => panic!()
+    }
+}
@@ -43,7 +43,7 @@ pub fn objects_duplicate_test() {
             let problem_ast = problem_parser.parse().unwrap();
             match problem_ast {
                 AbstractSyntaxTree::Problem(p) => {
-                    let semantic_parser = ProblemSemanticAnalyzer::new(&p, symbols);
+                    let semantic_parser = ProblemSemanticAnalyzer::new(&p, &d, symbols);
                     match semantic_parser.verify_problem() {
                         Err(SemanticErrorType::DuplicateObjectDeclaration(x)) => {
                             assert_eq!(x.symbol, "a");
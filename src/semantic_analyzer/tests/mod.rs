@@ -6,6 +6,12 @@ mod tdg_tests;
 mod problem_test;
 mod warning_tests;
 mod complementary_tests;
+#[cfg(test)]
+mod multi_error_tests;
+#[cfg(test)]
+mod symbol_index_tests;
+#[cfg(test)]
+mod requirement_tests;
 
 use super::*;
 use crate::syntactic_analyzer::*;
@@ -0,0 +1,78 @@
+use super::*;
+
+#[test]
+pub fn multiple_domain_errors_test() {
+    let program = String::from(
+        "(define (domain bal)
+            (:predicates 
+                (at ?a)
+                (hold ?a ?b)
+            )
+            (:task abs_1 :parameters (?a))
+            (:action a_1
+             :parameters (?p_1 ?p_2)
+             :precondition (and (at ?p_1) (pred_5))
+             :effect (and (not (hold ?p_1 ?p_2 ?p_2)))
+            )
+            (:action a_1
+             :parameters (?p_1)
+             :precondition (at ?p_1)
+            )
+            (:method m_1
+             :parameters (?p_1)
+             :task (abs_1 ?p_1)
+             :ordered-subtasks (and
+                (t1 (a_2 ?p_1))
+             )
+            )
+         ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.analyze_domain();
+            assert_eq!(errors.len(), 4);
+            match &errors[0] {
+                SemanticErrorType::UndefinedPredicate(x) => {
+                    assert_eq!(x.symbol, "pred_5");
+                    assert_eq!(x.position.line, 9);
+                }
+                token => panic!("{:?}", token)
+            }
+            match &errors[1] {
+                SemanticErrorType::InconsistentPredicateArity(x) => {
+                    assert_eq!(x.symbol, "hold");
+                    assert_eq!(x.position.line, 10);
+                }
+                token => panic!("{:?}", token)
+            }
+            match &errors[2] {
+                SemanticErrorType::DuplicateActionDeclaration(x) => {
+                    assert_eq!(x.symbol, "a_1");
+                    assert_eq!(x.first_pos.line, 7);
+                    assert_eq!(x.second_pos.line, 12);
+                }
+                token => panic!("{:?}", token)
+            }
+            match &errors[3] {
+                SemanticErrorType::UndefinedSubtask(x) => {
+                    assert_eq!(x.symbol, "a_2");
+                    assert_eq!(x.position.line, 20);
+                }
+                token => panic!("{:?}", token)
+            }
+            // the first error is the one reported by verify_domain
+            match semantic_parser.verify_domain() {
+                Err(SemanticErrorType::UndefinedPredicate(x)) => {
+                    assert_eq!(x.symbol, "pred_5");
+                }
+                _ => panic!()
+            }
+        }
+        _ => panic!()
+    }
+}
@@ -164,7 +164,7 @@ pub fn p_undeclared_type_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
@@ -215,7 +215,7 @@ pub fn p_undefined_object_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
@@ -268,7 +268,7 @@ pub fn p_duplicate_object_definition_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
@@ -328,7 +328,7 @@ pub fn p_cyclic_init_tn_ordering_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
@@ -383,7 +383,7 @@ pub fn p_inconsistent_goal_predicate_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
@@ -438,7 +438,7 @@ pub fn p_inconsistent_init_predicate_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
@@ -492,7 +492,7 @@ pub fn p_inconsistent_subtask_test() {
                 AbstractSyntaxTree::Problem(p) => {
                     let d_analyzer = DomainSemanticAnalyzer::new(&d);
                     let domain_symbols = d_analyzer.verify_domain().unwrap();
-                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, domain_symbols);
+                    let p_analyzer = ProblemSemanticAnalyzer::new(&p, &d, domain_symbols);
                     match p_analyzer.verify_problem() {
                         Ok(_) => {
                             panic!("error not found")
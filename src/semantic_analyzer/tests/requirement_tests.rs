@@ -0,0 +1,135 @@
+use super::*;
+
+fn requirement_warnings(program: &str) -> Vec<String> {
+    let program = program.to_string().into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let AbstractSyntaxTree::Domain(d) = parser.parse().unwrap() else {
+        panic!()
+    };
+    RequirementAnalyzer::new(&d)
+        .analyze()
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+}
+
+#[test]
+pub fn undeclared_requirement_test() {
+    let warnings = requirement_warnings(
+        "(define (domain features)
+            (:requirements :typing)
+            (:types place)
+            (:predicates (at ?p - place) (visited ?p - place))
+            (:action move
+                :parameters (?from ?to - place)
+                :precondition (and (at ?from) (not (at ?to)) (not (= ?from ?to)))
+                :effect (and (not (at ?from)) (at ?to)
+                    (forall (?p - place) (not (visited ?p))))
+            )
+        )",
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            "line 7: this feature requires :negative-preconditions, but it is not declared",
+            "line 7: this feature requires :equality, but it is not declared",
+            "line 9: this feature requires :conditional-effects, but it is not declared",
+        ]
+    );
+}
+
+#[test]
+pub fn quantified_precondition_test() {
+    let program = "(define (domain features)
+            (:requirements :existential-preconditions)
+            (:predicates (at ?p) (road ?a ?b))
+            (:action move
+                :parameters (?from)
+                :precondition (and (at ?from)
+                    (exists (?to) (road ?from ?to))
+                    (forall (?to) (road ?from ?to)))
+                :effect (not (at ?from))
+            )
+        )";
+    assert_eq!(
+        requirement_warnings(program),
+        vec!["line 8: this feature requires :universal-preconditions, but it is not declared"]
+    );
+    // :adl is a shorthand for quantified preconditions
+    let program = program.replace(":existential-preconditions", ":adl");
+    assert!(requirement_warnings(&program).is_empty());
+}
+
+#[test]
+pub fn unused_requirement_test() {
+    let warnings = requirement_warnings(
+        "(define (domain features)
+            (:requirements :strips :negative-preconditions :hierarchy :constraints
+                :method-preconditions)
+            (:predicates (at ?p))
+            (:task visit :parameters (?p))
+            (:method m_visit
+                :parameters (?p)
+                :task (visit ?p)
+                :precondition (at ?p)
+                :subtasks ()
+            )
+        )",
+    );
+    assert_eq!(
+        warnings,
+        vec![
+            "line 2: requirement :negative-preconditions is declared, but never used",
+            "line 2: requirement :constraints is declared, but never used",
+        ]
+    );
+}
+
+#[test]
+pub fn problem_requirement_test() {
+    let domain = "(define (domain features)
+            (:requirements :hierarchy)
+            (:predicates (at ?p))
+            (:task visit :parameters (?p))
+            (:method m_visit
+                :parameters (?p)
+                :task (visit ?p)
+                :subtasks ()
+            )
+        )"
+    .to_string()
+    .into_bytes();
+    let problem = "(define (problem p) (:domain features)
+            (:requirements :hierarchy :typing)
+            (:objects a)
+            (:htn :subtasks (and (t1 (visit a))))
+            (:init)
+            (:goal (not (at a)))
+        )"
+    .to_string()
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&domain);
+    let AbstractSyntaxTree::Domain(d) = Parser::new(lexer).parse().unwrap() else {
+        panic!()
+    };
+    let lexer = LexicalAnalyzer::new(&problem);
+    let AbstractSyntaxTree::Problem(p) = Parser::new(lexer).parse().unwrap() else {
+        panic!()
+    };
+    let sym_table = DomainSemanticAnalyzer::new(&d).verify_domain().unwrap();
+    let warnings: Vec<String> = ProblemSemanticAnalyzer::new(&p, &d, sym_table)
+        .verify_problem()
+        .unwrap()
+        .iter()
+        .filter(|w| w.is_in_problem())
+        .map(|w| w.to_string())
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "line 2: requirement :typing is not declared in the domain",
+            "line 6: this feature requires :negative-preconditions, but it is not declared",
+        ]
+    );
+}
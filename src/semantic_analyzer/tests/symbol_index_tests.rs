@@ -0,0 +1,69 @@
+use super::*;
+
+#[test]
+pub fn symbol_index_test() {
+    let domain = String::from(
+        "(define (domain bal)
+            (:types location - object)
+            (:constants home - location)
+            (:predicates
+                (at ?l - location)
+            )
+            (:task go :parameters (?l - location))
+            (:action move
+             :parameters (?from ?to - location)
+             :precondition (and (at ?from) (not (at home)))
+             :effect (and (not (at ?from)) (at ?to))
+            )
+            (:method m_go
+             :parameters (?l - location)
+             :task (go ?l)
+             :ordered-subtasks (and
+                (t1 (move home ?l))
+             )
+            )
+         ) ",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain bal)
+            (:objects park - location)
+            (:htn :parameters () :subtasks (and (task0 (go park))))
+            (:init (at home))
+         ) ",
+    )
+    .into_bytes();
+    let domain_parser = Parser::new(LexicalAnalyzer::new(&domain));
+    let problem_parser = Parser::new(LexicalAnalyzer::new(&problem));
+    match (domain_parser.parse().unwrap(), problem_parser.parse().unwrap()) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => {
+            let index = SymbolIndex::new(&d, Some(&p));
+            // predicates
+            let at = index.references(SymbolKind::Predicate, "at");
+            assert_eq!(at.len(), 6);
+            assert_eq!(at.iter().filter(|r| r.file == SourceFile::Problem).count(), 1);
+            let declaration = index.declaration(SymbolKind::Predicate, "at").unwrap();
+            assert_eq!(declaration.position.line, 5);
+            assert_eq!(declaration.position.column, 18);
+            assert_eq!(index.signature(SymbolKind::Predicate, "at"), Some("(at ?l - location)"));
+            // parameters are not mistaken for constants
+            let home = index.references(SymbolKind::Constant, "home");
+            assert_eq!(home.len(), 4);
+            assert_eq!(index.references(SymbolKind::Constant, "park").len(), 2);
+            // subtasks refer to both compound tasks and actions
+            assert_eq!(index.references(SymbolKind::Task, "go").len(), 3);
+            assert_eq!(index.references(SymbolKind::Action, "move").len(), 2);
+            assert_eq!(
+                index.signature(SymbolKind::Task, "go"),
+                Some("(:task go :parameters (?l - location))")
+            );
+            assert_eq!(index.references(SymbolKind::Type, "location").len(), 8);
+            // lookup by offset
+            let offset = domain.windows(4).position(|w| w == b"move").unwrap() + 2;
+            let symbol = index.symbol_at(SourceFile::Domain, offset).unwrap();
+            assert_eq!(symbol.kind, SymbolKind::Action);
+            assert!(symbol.is_declaration);
+        }
+        _ => panic!("wrong parsing")
+    }
+}
@@ -60,9 +60,9 @@ pub fn tdg_correctness_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
-                    let reachable_abs_1 = tdg.reachable("abs_1");
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
+                    let reachable_abs_1 = tdg.reachable("abs_1").unwrap();
                     assert_eq!(reachable_abs_1.compounds.len(), 3);
                     assert_eq!(reachable_abs_1.primitives.len(), 1);
                     assert_eq!(reachable_abs_1.compounds.contains("abs_1"), true);
@@ -71,7 +71,7 @@ pub fn tdg_correctness_test () {
                     assert_eq!(reachable_abs_1.primitives.contains("p_1"), true);
                     assert_eq!(reachable_abs_1.nullable, true);
 
-                    let reachable_p_2 = tdg.reachable("p_2");
+                    let reachable_p_2 = tdg.reachable("p_2").unwrap();
                     assert_eq!(reachable_p_2.primitives.len(), 1);
                     assert_eq!(reachable_p_2.primitives.contains("p_2"), true);
                     assert_eq!(reachable_p_2.nullable, false);
@@ -134,8 +134,8 @@ pub fn tdg_non_recursive_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     let nullables = tdg.compute_nullables();
                     assert_eq!(tdg.get_recursion_type(&nullables), RecursionType::NonRecursive)
                 }
@@ -198,8 +198,8 @@ pub fn tdg_recursive_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     let nullables = tdg.compute_nullables();
                     match tdg.get_recursion_type(&nullables) {
                         RecursionType::Recursive(_) => {}
@@ -270,8 +270,8 @@ pub fn tdg_grow_and_shrink_cycle_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     let nullables = tdg.compute_nullables();
                     match tdg.get_recursion_type(&nullables) {
                         RecursionType::GrowAndShrinkRecursion(_) => {}
@@ -348,8 +348,8 @@ pub fn tdg_grow_and_shrink_cycle_partial_order_1_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     let nullables = tdg.compute_nullables();
                     match tdg.get_recursion_type(&nullables) {
                         RecursionType::GrowAndShrinkRecursion(_) => {}
@@ -424,8 +424,8 @@ pub fn tdg_growing_cycle_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     let nullables = tdg.compute_nullables();
                     match tdg.get_recursion_type(&nullables) {
                         RecursionType::GrowingEmptyPrefixRecursion(_) => {}
@@ -511,8 +511,8 @@ pub fn satelite_domain_cycle_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     let nullables = tdg.compute_nullables();
                     match tdg.get_recursion_type(&nullables) {
                         RecursionType::Recursive(_) => {}
@@ -600,8 +600,8 @@ pub fn tdg_nullables_test () {
             let p_parser = Parser::new(p_lexer);
             let p_ast = p_parser.parse().unwrap();
             match p_ast {
-                AbstractSyntaxTree::Problem(p_ast) => {
-                    let tdg = TDG::new(&d);
+                AbstractSyntaxTree::Problem(_) => {
+                    let tdg = TDG::new(&d).unwrap();
                     assert_eq!(tdg.compute_nullables(), HashSet::from(["abs_1", "abs_2", "abs_3"]))
                 }
                 _ => panic!()
@@ -609,4 +609,348 @@ pub fn tdg_nullables_test () {
         }
         AbstractSyntaxTree::Problem(_) => panic!()
     }
-}
\ No newline at end of file
+}
+#[test]
+pub fn tdg_recursive_components_test () {
+    let program = String::from(
+        "(define (domain components)
+            (:task tail :parameters ())
+            (:task other_tail :parameters ())
+            (:task growing :parameters ())
+            (:task empty :parameters ())
+            (:task flat :parameters ())
+            (:action p :parameters ())
+            (:method m_tail
+                :parameters ()
+                :task (tail)
+                :ordered-subtasks (and (p) (other_tail))
+            )
+            (:method m_other_tail
+                :parameters ()
+                :task (other_tail)
+                :ordered-subtasks (and (p) (tail))
+            )
+            (:method m_other_tail_base
+                :parameters ()
+                :task (other_tail)
+                :ordered-subtasks (p)
+            )
+            (:method m_growing
+                :parameters ()
+                :task (growing)
+                :ordered-subtasks (and (growing) (p))
+            )
+            (:method m_growing_base
+                :parameters ()
+                :task (growing)
+                :ordered-subtasks (p)
+            )
+            (:method m_empty
+                :parameters ()
+                :task (empty)
+                :ordered-subtasks (empty)
+            )
+            (:method m_empty_base
+                :parameters ()
+                :task (empty)
+                :ordered-subtasks ()
+            )
+            (:method m_flat
+                :parameters ()
+                :task (flat)
+                :ordered-subtasks (and (tail) (growing))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let tdg = TDG::new(&d).unwrap();
+            let nullables = tdg.compute_nullables();
+            let components = tdg.recursive_components(&nullables);
+            assert_eq!(components.len(), 3);
+            let find = |task: &str| components.iter().find(|c| c.tasks.contains(&task.to_string())).unwrap();
+            let tail = find("tail");
+            assert_eq!(tail.tasks, vec!["tail".to_string(), "other_tail".to_string()]);
+            assert_eq!(
+                tail.recursion,
+                RecursionType::TailRecursion(vec![
+                    ("tail".to_string(), "m_tail".to_string()),
+                    ("other_tail".to_string(), "m_other_tail".to_string()),
+                    ("tail".to_string(), "m_other_tail".to_string()),
+                ])
+            );
+            match &find("growing").recursion {
+                RecursionType::GrowingEmptyPrefixRecursion(cycle) => assert_eq!(cycle.len(), 2),
+                _ => panic!()
+            }
+            match &find("empty").recursion {
+                RecursionType::EmptyRecursion(_) => {}
+                _ => panic!()
+            }
+            assert!(components.iter().all(|c| !c.tasks.contains(&"flat".to_string())));
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
+}
+
+#[test]
+pub fn tdg_hierarchy_class_test () {
+    let classify = |methods: &str| {
+        let program = format!(
+            "(define (domain classes)
+                (:task a :parameters ())
+                (:task b :parameters ())
+                (:action p :parameters ())
+                (:action q :parameters ())
+                (:method m_b
+                    :parameters ()
+                    :task (b)
+                    :ordered-subtasks (p)
+                )
+                {}
+            ) ",
+            methods
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        match parser.parse().unwrap() {
+            AbstractSyntaxTree::Domain(d) => {
+                let tdg = TDG::new(&d).unwrap();
+                let nullables = tdg.compute_nullables();
+                tdg.hierarchy_class(&nullables)
+            }
+            AbstractSyntaxTree::Problem(_) => panic!()
+        }
+    };
+    let acyclic = classify("(:method m_a :parameters () :task (a) :ordered-subtasks (and (b) (b)))");
+    assert_eq!(acyclic.classes, vec![HierarchyClass::Acyclic, HierarchyClass::TailRecursive]);
+    assert_eq!(acyclic.violating_method(HierarchyClass::Acyclic), None);
+    assert_eq!(acyclic.violating_method(HierarchyClass::Regular), Some("m_a"));
+    assert!(acyclic.is_totally_ordered());
+    assert_eq!(acyclic.to_string(), "acyclic, tail-recursive (not regular because of method m_a), totally ordered");
+
+    // regular, but not acyclic
+    let regular = classify("(:method m_a :parameters () :task (a) :ordered-subtasks (and (p) (a)))");
+    assert_eq!(regular.classes, vec![HierarchyClass::Regular, HierarchyClass::TailRecursive]);
+    assert_eq!(regular.violating_method(HierarchyClass::Acyclic), Some("m_a"));
+    assert_eq!(regular.violating_method(HierarchyClass::Regular), None);
+    assert_eq!(regular.violating_method(HierarchyClass::TailRecursive), None);
+    assert_eq!(regular.to_string(), "regular, tail-recursive (not acyclic because of method m_a), totally ordered");
+
+    // tail-recursive, but not regular
+    let tail_recursive = classify(
+        "(:method m_a :parameters () :task (a) :ordered-subtasks (and (p) (a)))
+        (:method m_a_twice :parameters () :task (a) :ordered-subtasks (and (b) (a)))",
+    );
+    assert_eq!(tail_recursive.classes, vec![HierarchyClass::TailRecursive]);
+    assert!(!tail_recursive.belongs_to(HierarchyClass::Regular));
+    assert_eq!(tail_recursive.violating_method(HierarchyClass::Acyclic), Some("m_a"));
+    assert_eq!(tail_recursive.violating_method(HierarchyClass::Regular), Some("m_a_twice"));
+    assert_eq!(tail_recursive.violating_method(HierarchyClass::TailRecursive), None);
+    assert_eq!(
+        tail_recursive.to_string(),
+        "tail-recursive (not acyclic because of method m_a, not regular because of method m_a_twice), totally ordered"
+    );
+
+    let general = classify(
+        "(:method m_a :parameters () :task (a)
+            :subtasks (and (t1 (a)) (t2 (q)))
+            :ordering (< t1 t2)
+        )
+        (:method m_a_unordered :parameters () :task (a) :subtasks (and (p) (q)))",
+    );
+    assert!(general.is_general());
+    assert_eq!(general.violating_method(HierarchyClass::TailRecursive), Some("m_a"));
+    assert_eq!(general.partially_ordered_method, Some("m_a_unordered".to_string()));
+    assert_eq!(
+        general.to_string(),
+        "general (not acyclic because of method m_a, not regular because of method m_a, not tail-recursive because of method m_a), partially ordered (method m_a_unordered)"
+    );
+}
+
+#[test]
+pub fn tdg_task_bounds_test () {
+    let program = String::from(
+        "(define (domain bounds)
+            (:task top :parameters ())
+            (:task pair :parameters ())
+            (:task deep :parameters ())
+            (:task stuck :parameters ())
+            (:task single :parameters ())
+            (:action p :parameters ())
+            (:method m_top_wide
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (and (p) (p) (p))
+            )
+            (:method m_top_deep
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (and (deep) (pair))
+            )
+            (:method m_pair
+                :parameters ()
+                :task (pair)
+                :ordered-subtasks (and (p) (p))
+            )
+            (:method m_deep
+                :parameters ()
+                :task (deep)
+                :ordered-subtasks (deep)
+            )
+            (:method m_deep_base
+                :parameters ()
+                :task (deep)
+                :ordered-subtasks ()
+            )
+            (:method m_stuck
+                :parameters ()
+                :task (stuck)
+                :ordered-subtasks (stuck)
+            )
+            (:method m_single
+                :parameters ()
+                :task (single)
+                :ordered-subtasks (p)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let tdg = TDG::new(&d).unwrap();
+            let bounds = tdg.compute_task_bounds();
+            let find = |task: &str| bounds.iter().find(|b| b.task == task);
+            let top = find("top").unwrap();
+            assert_eq!((top.min_plan_length, top.length_method.as_str()), (2, "m_top_deep"));
+            assert_eq!((top.min_depth, top.depth_method.as_str()), (1, "m_top_wide"));
+            let deep = find("deep").unwrap();
+            assert_eq!((deep.min_plan_length, deep.min_depth), (0, 1));
+            assert_eq!(deep.length_method, "m_deep_base");
+            assert_eq!(find("pair").unwrap().min_plan_length, 2);
+            assert!(find("stuck").is_none());
+            assert_eq!(find("pair").unwrap().to_string(), "pair: 2 primitive tasks (m_pair), depth 1 (m_pair)");
+            assert_eq!(find("single").unwrap().to_string(), "single: 1 primitive task (m_single), depth 1 (m_single)");
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
+}
+
+#[test]
+pub fn tdg_landmarks_test () {
+    let program = String::from(
+        "(define (domain landmarks)
+            (:task top :parameters ())
+            (:task loop :parameters ())
+            (:task stuck :parameters ())
+            (:task single :parameters ())
+            (:action p :parameters ())
+            (:action q :parameters ())
+            (:action r :parameters ())
+            (:method m_top_1
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (and (p) (loop))
+            )
+            (:method m_top_2
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (and (q) (loop) (p))
+            )
+            (:method m_top_blocked
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (stuck)
+            )
+            (:method m_loop
+                :parameters ()
+                :task (loop)
+                :ordered-subtasks (and (q) (loop))
+            )
+            (:method m_loop_base
+                :parameters ()
+                :task (loop)
+                :ordered-subtasks (r)
+            )
+            (:method m_stuck
+                :parameters ()
+                :task (stuck)
+                :ordered-subtasks (stuck)
+            )
+            (:method m_single
+                :parameters ()
+                :task (single)
+                :ordered-subtasks (p)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(d) => {
+            let tdg = TDG::new(&d).unwrap();
+            let landmarks = tdg.landmarks(&["top"]).unwrap().unwrap();
+            assert_eq!(landmarks.compound_tasks, vec!["top", "loop"]);
+            assert_eq!(landmarks.methods, vec!["m_loop_base"]);
+            assert_eq!(landmarks.actions, vec!["p", "r"]);
+            assert_eq!(tdg.landmarks(&["top", "stuck"]).unwrap(), None);
+            assert_eq!(tdg.landmarks(&[]).unwrap().unwrap().actions.len(), 0);
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
+}
+
+#[test]
+pub fn tdg_undefined_task_test () {
+    let program = String::from(
+        "(define (domain undefined)
+            (:task top :parameters ())
+            (:action p :parameters ())
+            (:method m_top
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (and (p) (missing))
+            )
+            (:method m_other
+                :parameters ()
+                :task (other)
+                :ordered-subtasks (p)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    match parser.parse().unwrap() {
+        AbstractSyntaxTree::Domain(mut d) => {
+            match TDG::new(&d) {
+                Err(SemanticErrorType::UndefinedTask(error)) => {
+                    assert_eq!(error.symbol, "other");
+                    assert_eq!(error.position.line, 11);
+                }
+                _ => panic!()
+            }
+            d.methods.pop();
+            match TDG::new(&d) {
+                Err(SemanticErrorType::UndefinedSubtask(error)) => {
+                    assert_eq!(error.symbol, "missing");
+                    assert_eq!(error.position.line, 7);
+                }
+                _ => panic!()
+            }
+            d.methods[0].tn.subtasks.pop();
+            let tdg = TDG::new(&d).unwrap();
+            assert!(matches!(tdg.reachable("missing"), Err(SemanticErrorType::UndefinedTask(_))));
+            assert!(matches!(tdg.landmarks(&["missing"]), Err(SemanticErrorType::UndefinedTask(_))));
+        }
+        AbstractSyntaxTree::Problem(_) => panic!()
+    }
+}
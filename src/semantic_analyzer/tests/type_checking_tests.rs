@@ -284,4 +284,101 @@ pub fn method_subtask_checking_test () {
         }
         _ => panic!()
     }
-}
\ No newline at end of file
+}
+#[test]
+pub fn quantified_type_checking_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:types truck location - object)
+            (:predicates
+                (at ?t - truck ?l - location)
+                (road ?a ?b - location)
+                (visited ?l - location)
+            )
+            (:action move
+             :parameters (?t - truck ?from - location)
+             :precondition (and
+                (at ?t ?from)
+                (exists (?to - location) (road ?from ?to))
+                (forall (?x - truck) (at ?x ?from)))
+             :effect (and
+                (forall (?l - location)
+                    (when (road ?from ?l) (and (visited ?l) (not (at ?t ?from)))))
+                (forall (?l - truck) (when (at ?l ?from) (visited ?l))))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.analyze_domain();
+            // the second quantifier binds ?l to trucks, which can not be visited
+            assert_eq!(errors.len(), 1);
+            match &errors[0] {
+                SemanticErrorType::InconsistentPredicateArgType(t_err) => {
+                    assert_eq!(t_err.var_name, "l");
+                    assert_eq!(t_err.found.as_deref(), Some("truck"));
+                    assert_eq!(t_err.expected.as_deref(), Some("location"));
+                    assert_eq!(t_err.position.line, 17);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn quantified_variable_scope_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:types location)
+            (:predicates (road ?a ?b - location))
+            (:action a_1
+             :parameters (?from - location)
+             :precondition (and
+                (exists (?to - location) (road ?from ?to))
+                (road ?to ?from))
+             :effect (forall (?l - place) (road ?l ?from))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.analyze_domain();
+            assert_eq!(errors.len(), 3);
+            // quantified variables are out of scope after their quantifier
+            match &errors[0] {
+                SemanticErrorType::UndefinedParameter(x) => {
+                    assert_eq!(x.symbol, "to");
+                    assert_eq!(x.position.line, 8);
+                }
+                error => panic!("{:?}", error)
+            }
+            // types of quantified variables must be declared
+            match &errors[1] {
+                SemanticErrorType::UndefinedType(x) => {
+                    assert_eq!(x.symbol, "place");
+                    assert_eq!(x.position.line, 9);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &errors[2] {
+                SemanticErrorType::InconsistentPredicateArgType(t_err) => {
+                    assert_eq!(t_err.found.as_deref(), Some("place"));
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
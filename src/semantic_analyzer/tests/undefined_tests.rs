@@ -501,4 +501,93 @@ pub fn undefined_method_task_test() {
         _ => panic!()
     }
     
-}
\ No newline at end of file
+}
+#[test]
+pub fn undefined_quantified_predicate_test() {
+    let program = String::from(
+        "(define (domain bal)
+                (:predicates
+                    (at ?a_1)
+                    (hold ?a_1 ?a_2)
+                )
+                (:action a_1
+                    :parameters (?p_1)
+                    :precondition (exists (?p_2) (imply (hold ?p_1 ?p_2) (pred_2 ?p_2)))
+                    :effect (forall (?p_2) (when (at ?p_2) (hold ?p_2)))
+                )
+             ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.analyze_domain();
+            assert_eq!(errors.len(), 2);
+            match &errors[0] {
+                SemanticErrorType::UndefinedPredicate(x) => {
+                    assert_eq!(x.symbol, "pred_2");
+                    assert_eq!(x.position.line, 8);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &errors[1] {
+                SemanticErrorType::InconsistentPredicateArity(x) => {
+                    assert_eq!(x.symbol, "hold");
+                    assert_eq!(x.position.line, 9);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn undefined_equality_term_test() {
+    let program = String::from(
+        "(define (domain bal)
+                (:requirements :equality :hierarchy)
+                (:predicates (at ?a_1))
+                (:task t :parameters (?x))
+                (:action a_1
+                    :parameters (?x)
+                    :precondition (and (at ?x) (= ?x ?undeclared))
+                )
+                (:method m_1
+                    :parameters (?x ?y)
+                    :task (t ?x)
+                    :subtasks (and (task0 (a_1 ?x)))
+                    :constraints (and (not (= ?y ?z)))
+                )
+             ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    match ast {
+        AbstractSyntaxTree::Domain(d) => {
+            let semantic_parser = DomainSemanticAnalyzer::new(&d);
+            let (_, errors) = semantic_parser.analyze_domain();
+            assert_eq!(errors.len(), 2);
+            match &errors[0] {
+                SemanticErrorType::UndefinedParameter(x) => {
+                    assert_eq!(x.symbol, "undeclared");
+                    assert_eq!(x.position.line, 7);
+                }
+                error => panic!("{:?}", error)
+            }
+            match &errors[1] {
+                SemanticErrorType::UndefinedParameter(x) => {
+                    assert_eq!(x.symbol, "z");
+                    assert_eq!(x.position.line, 13);
+                }
+                error => panic!("{:?}", error)
+            }
+        }
+        _ => panic!()
+    }
+}
@@ -42,8 +42,13 @@ pub fn primitive_refinement_test () {
             let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
             match semantic_analyzer.verify_domain() {
                 Ok(sym_table) => {
-                    assert_eq!(sym_table.warnings.len(), 1);
-                    match &sym_table.warnings[0] {
+                    let warnings: Vec<&WarningType> = sym_table
+                        .warnings
+                        .iter()
+                        .filter(|w| matches!(w, WarningType::NoPrimitiveRefinement(_)))
+                        .collect();
+                    assert_eq!(warnings.len(), 1);
+                    match warnings[0] {
                         WarningType::NoPrimitiveRefinement(info) => {
                             assert_eq!(info.symbol, "abs_3");
                             assert_eq!(info.position.line, 15);
@@ -56,4 +61,243 @@ pub fn primitive_refinement_test () {
         }
         _ => panic!()
     }
-}
\ No newline at end of file
+}
+#[test]
+pub fn redundancy_warnings_test () {
+    let program = String::from(
+        "(define (domain smells) (:requirements :typing :hierarchy)
+            (:types truck - vehicle location trailer)
+            (:predicates
+                (at ?v - vehicle ?l - location)
+                (road ?a - location ?b - location)
+                (broken ?v - vehicle)
+            )
+            (:task deliver :parameters (?v - vehicle ?l - location))
+            (:method m_deliver
+                :parameters (?v - vehicle ?l ?to ?from ?unused - location)
+                :task (deliver ?v ?l)
+                :ordered-subtasks (and
+                    (t1 (drive ?v ?to ?from))
+                )
+            )
+            (:action drive
+                :parameters (?v - vehicle ?to - location ?from - location)
+                :precondition (and (at ?v ?from) (road ?from ?to))
+                :effect (and (not (at ?v ?from)) (at ?v ?to) (road ?from ?to) (at ?v ?to))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    let AbstractSyntaxTree::Domain(d) = ast else {
+        panic!()
+    };
+    let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+    let sym_table = semantic_analyzer.verify_domain().unwrap();
+    let warnings: Vec<String> = sym_table.warnings.iter().map(|w| w.to_string()).collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "line 6: predicate broken is declared, but never used",
+            "line 8: parameter ?l of deliver is declared, but never used",
+            "line 10: parameter ?unused of m_deliver is declared, but never used",
+            "line 19: effect (road ?from ?to) of action drive is already required by its precondition",
+            "line 19: effect (at ?v ?to) of action drive is repeated",
+        ]
+    );
+    match &sym_table.warnings[3] {
+        WarningType::RedundantEffect(info) => match info.redundancy {
+            EffectRedundancy::RequiredByPrecondition(position) => {
+                assert_eq!(position.line, 18);
+            }
+            _ => panic!()
+        },
+        _ => panic!()
+    }
+
+    // types and tasks are only checked along with a problem, whose objects and tasks use them
+    let problem = String::from(
+        "(define (problem p) (:domain smells)
+            (:objects t - truck)
+            (:htn :parameters () :subtasks (task0 (deliver t t)))
+        ) ",
+    )
+    .into_bytes();
+    let AbstractSyntaxTree::Problem(p) = Parser::new(LexicalAnalyzer::new(&problem)).parse().unwrap() else {
+        panic!()
+    };
+    let warnings: Vec<String> = RedundancyAnalyzer::new(&d).analyze_problem(&p).iter().map(|w| w.to_string()).collect();
+    assert_eq!(warnings, vec!["line 2: type trailer is declared, but never used"]);
+}
+
+#[test]
+pub fn immutable_predicate_test () {
+    let program = String::from(
+        "(define (domain static)
+            (:predicates
+                (at ?l)
+                (road ?a ?b)
+            )
+            (:action move
+                :parameters (?from ?to)
+                :precondition (and (at ?from) (road ?from ?to))
+                :effect (and (not (at ?from)) (at ?to))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let AbstractSyntaxTree::Domain(d) = parser.parse().unwrap() else {
+        panic!()
+    };
+    let semantic_analyzer = DomainSemanticAnalyzer::new(&d);
+    let sym_table = semantic_analyzer.verify_domain().unwrap();
+    assert_eq!(sym_table.warnings.len(), 1);
+    match &sym_table.warnings[0] {
+        WarningType::ImmutablePredicate(info) => {
+            assert_eq!(info.symbol, "road");
+            assert_eq!(info.position.line, 4);
+        }
+        _ => panic!()
+    }
+}
+
+#[test]
+pub fn primitive_refinement_explanation_test () {
+    let program = String::from(
+        "(define (domain bal)
+            (:predicates (at ?l))
+            (:action p_1 :parameters(?l1) :precondition (at ?l1))
+            (:task deliver :parameters(?a))
+            (:task route :parameters(?a))
+            (:task missing :parameters(?a))
+            (:task loop :parameters(?a))
+
+            (:method m_deliver
+                :parameters (?p1) 
+                :task (deliver ?p1)
+                :ordered-subtasks (and (p_1 ?p1) (route ?p1) (missing ?p1))
+            )
+            (:method m_route_missing
+                :parameters (?p1) 
+                :task (route ?p1)
+                :ordered-subtasks (and (missing ?p1))
+            )
+            (:method m_route_loop
+                :parameters (?p1) 
+                :task (route ?p1)
+                :ordered-subtasks (and (loop ?p1) (deliver ?p1))
+            )
+            (:method m_loop
+                :parameters (?p1) 
+                :task (loop ?p1)
+                :ordered-subtasks (and (loop ?p1))
+            )
+        ) ",
+    )
+    .into_bytes();
+    let lexer = LexicalAnalyzer::new(&program);
+    let parser = Parser::new(lexer);
+    let ast = parser.parse().unwrap();
+    let AbstractSyntaxTree::Domain(d) = ast else { panic!() };
+    let sym_table = DomainSemanticAnalyzer::new(&d).verify_domain().unwrap();
+    let warnings: Vec<&RefinementInfo> = sym_table
+        .warnings
+        .iter()
+        .filter_map(|w| match w {
+            WarningType::NoPrimitiveRefinement(info) => Some(info),
+            _ => None,
+        })
+        .collect();
+    // only the tasks that reach no primitive task are reported
+    let tasks: Vec<&str> = warnings.iter().map(|info| info.symbol.as_str()).collect();
+    assert_eq!(tasks, vec!["missing", "loop"]);
+    assert_eq!(warnings[0].cause, BlockingCause::NoMethods);
+    assert_eq!(
+        warnings[1].cause,
+        BlockingCause::OnlyCycles(vec![BlockedMethod {
+            method: "m_loop".to_string(),
+            blocked_subtasks: vec![BlockedTask { task: "loop".to_string(), cause: BlockingCause::LoopsBack }],
+        }])
+    );
+
+    // tasks that reach a primitive task can still be blocked
+    let tdg = TDG::new(&d).unwrap();
+    assert!(!tdg.refinable_tasks().contains("deliver"));
+    assert!(tdg.refinable_tasks().contains("p_1"));
+    let warning = WarningType::NoPrimitiveRefinement(RefinementInfo {
+        symbol: "deliver".to_string(),
+        position: warnings[0].position,
+        cause: tdg.explain_unrefinable("deliver"),
+    });
+    assert_eq!(
+        warning.to_string(),
+        "line 6: compound task deliver does not have a primitive refinement
+    deliver has no refinable method
+        method m_deliver is blocked by route, missing
+            route has no refinable method
+                method m_route_missing is blocked by missing
+                    missing has no methods
+                method m_route_loop is blocked by deliver, loop
+                    deliver loops back to a task above
+                    loop only has methods that lead back to it
+                        method m_loop is blocked by loop
+                            loop loops back to a task above
+            missing has no methods"
+    );
+}
+
+#[test]
+pub fn unreachable_elements_test () {
+    let domain = String::from(
+        "(define (domain dead) (:requirements :hierarchy)
+            (:task top :parameters ())
+            (:task dead :parameters ())
+            (:action a :parameters ())
+            (:action b :parameters ())
+            (:action c :parameters ())
+            (:method m_top
+                :parameters ()
+                :task (top)
+                :ordered-subtasks (a)
+            )
+            (:method m_dead
+                :parameters ()
+                :task (dead)
+                :ordered-subtasks (b)
+            )
+        ) ",
+    )
+    .into_bytes();
+    let problem = String::from(
+        "(define (problem p) (:domain dead)
+            (:htn :parameters () :ordered-subtasks (top))
+        ) ",
+    )
+    .into_bytes();
+    let AbstractSyntaxTree::Domain(d) = Parser::new(LexicalAnalyzer::new(&domain)).parse().unwrap() else {
+        panic!()
+    };
+    let AbstractSyntaxTree::Problem(p) = Parser::new(LexicalAnalyzer::new(&problem)).parse().unwrap() else {
+        panic!()
+    };
+    let symbols = DomainSemanticAnalyzer::new(&d).verify_domain().unwrap();
+    // without a problem, every task may be an initial one
+    assert!(symbols.warnings.is_empty());
+    let warnings = ProblemSemanticAnalyzer::new(&p, &d, symbols).verify_problem().unwrap();
+    let warnings: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+    assert_eq!(
+        warnings,
+        vec![
+            "line 3: compound task dead is declared, but never used as a subtask",
+            "line 6: action c is declared, but never used as a subtask",
+            "line 3: compound task dead can not be reached from the initial task network",
+            "line 12: method m_dead can not be reached from the initial task network",
+            "line 5: action b can not be reached from the initial task network",
+            "line 6: action c can not be reached from the initial task network",
+        ]
+    );
+}
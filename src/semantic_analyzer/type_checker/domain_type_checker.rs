@@ -16,14 +16,14 @@ impl<'a> DomainTypeChecker<'a> {
         }
     }
 
-    pub fn get_type_hierarchy(&'a self) -> GraphMap<&'a str, (), Directed> {
+    pub fn get_type_hierarchy(&self) -> GraphMap<&'a str, (), Directed> {
         self.generic_type_checker.type_hierarchy.clone()
     }
 
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
         self.generic_type_checker
             .check_type_declarations(parameters)
     }
@@ -32,89 +32,178 @@ impl<'a> DomainTypeChecker<'a> {
         self.generic_type_checker.verify_type_hierarchy()
     }
 
-    // TODO: Add support for "universal qunatification" parameters
     pub fn check_formula(
         &self,
-        formula: &Vec<&Predicate<'a>>,
+        formula: &Formula<'a>,
         parameters: &Vec<Symbol<'a>>,
         declared_constants: &HashSet<&Symbol<'a>>,
         declared_predicates: &HashSet<&'a Predicate<'a>>,
-    ) -> Result<(), SemanticErrorType> {
-        // Assert all types are declared
-        if let Some(undeclared_type) = self
-            .generic_type_checker
-            .check_type_declarations(parameters)
-        {
-            return Err(undeclared_type);
-        }
+    ) -> Vec<SemanticErrorType> {
         // Store parameter types
         let par_types: HashMap<&str, Option<&str>> =
             HashMap::from_iter(parameters.iter().map(|par| (par.name, par.symbol_type)));
+        self.check_scoped_formula(formula, &par_types, declared_constants, declared_predicates)
+    }
+
+    // quantified variables are in scope of their subformula (and shadow the outer ones)
+    fn check_scoped_formula(
+        &self,
+        formula: &Formula<'a>,
+        par_types: &HashMap<&'a str, Option<&'a str>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
+        match formula {
+            Formula::Empty => {}
+            Formula::Equals(a, b) => {
+                for term in [a, b] {
+                    errors.extend(self.check_term(term, par_types, declared_constants));
+                }
+            }
+            Formula::Atom(predicate) => {
+                errors.extend(self.check_predicate(
+                    predicate,
+                    par_types,
+                    declared_constants,
+                    declared_predicates,
+                ));
+            }
+            Formula::Not(f) => {
+                errors.extend(self.check_scoped_formula(f, par_types, declared_constants, declared_predicates));
+            }
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                for f in fs.iter() {
+                    errors.extend(self.check_scoped_formula(f, par_types, declared_constants, declared_predicates));
+                }
+            }
+            Formula::Imply(antecedents, consequences) => {
+                for f in antecedents.iter().chain(consequences.iter()) {
+                    errors.extend(self.check_scoped_formula(f, par_types, declared_constants, declared_predicates));
+                }
+            }
+            Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+                errors.extend(self.check_type_declarations(vars));
+                let mut scope = par_types.clone();
+                scope.extend(vars.iter().map(|var| (var.name, var.symbol_type)));
+                errors.extend(self.check_scoped_formula(f, &scope, declared_constants, declared_predicates));
+            }
+        }
+        errors
+    }
+
+    // the terms of (in)equality constraints must be parameters or constants
+    pub fn check_constraints(
+        &self,
+        constraints: &Vec<Constraint<'a>>,
+        parameters: &Vec<Symbol<'a>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+    ) -> Vec<SemanticErrorType> {
+        let par_types: HashMap<&str, Option<&str>> =
+            HashMap::from_iter(parameters.iter().map(|par| (par.name, par.symbol_type)));
+        let mut errors = vec![];
+        for constraint in constraints.iter() {
+            let (Constraint::Equal(a, b) | Constraint::NotEqual(a, b)) = constraint;
+            for term in [a, b] {
+                errors.extend(self.check_term(term, &par_types, declared_constants));
+            }
+        }
+        errors
+    }
+
+    fn check_term(
+        &self,
+        term: &Symbol<'a>,
+        par_types: &HashMap<&'a str, Option<&'a str>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+    ) -> Option<SemanticErrorType> {
+        if par_types.contains_key(term.name) || declared_constants.contains(term) {
+            return None;
+        }
+        Some(SemanticErrorType::UndefinedParameter(UndefinedSymbolError {
+            symbol: term.name.to_string(),
+            position: term.name_pos,
+        }))
+    }
+
+    fn check_predicate(
+        &self,
+        instantiated_predicate: &Predicate<'a>,
+        par_types: &HashMap<&'a str, Option<&'a str>>,
+        declared_constants: &HashSet<&Symbol<'a>>,
+        declared_predicates: &HashSet<&'a Predicate<'a>>,
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         // Assert predicate typing correctness
-        for instantiated_predicate in formula {
-            match declared_predicates.get(instantiated_predicate) {
-                Some(predicate_definition) => {
-                    let mut instantiated_vars = vec![];
-                    for var in instantiated_predicate.variables.iter() {
-                        match par_types.get(var.name) {
-                            Some(par_type) => {
-                                instantiated_vars.push((var, par_type));
-                            }
-                            None => match declared_constants.get(var) {
-                                Some(constant) => {
-                                    instantiated_vars.push((var, &constant.symbol_type))
-                                }
-                                None => {
-                                    return Err(SemanticErrorType::UndefinedParameter(
-                                        UndefinedSymbolError {
-                                            symbol: var.name.to_string(),
-                                            position: var.name_pos,
-                                        },
-                                    ));
-                                }
-                            },
-                        }
-                    }
-                    let mut expected_list: Vec<&Option<&str>> = predicate_definition
-                        .variables
-                        .iter()
-                        .map(|x| &x.symbol_type)
-                        .collect();
-                    // Assert args have the same arity
-                    if &instantiated_vars.len() != &expected_list.len() {
-                        return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
-                            symbol: instantiated_predicate.name.to_string(),
-                            expected_arity: expected_list.len() as u32,
-                            found_arity: instantiated_vars.len() as u32,
-                            position: instantiated_predicate.name_pos,
-                        }));
-                    }
-                    for ((var, f), e) in
-                        instantiated_vars.into_iter().zip(expected_list.into_iter())
-                    {
-                        if !self.generic_type_checker.is_var_type_consistent(*f, *e) {
-                            return Err(SemanticErrorType::InconsistentPredicateArgType(
-                                TypeError {
-                                    expected: e.map(|inner| inner.to_string()),
-                                    found: f.map(|inner| inner.to_string()),
-                                    var_name: var.name.to_string(),
-                                    position: var.name_pos,
-                                },
-                            ));
+        match declared_predicates.get(instantiated_predicate) {
+            Some(predicate_definition) => {
+                let mut instantiated_vars = vec![];
+                let mut is_instantiated = true;
+                for var in instantiated_predicate.variables.iter() {
+                    match par_types.get(var.name) {
+                        Some(par_type) => {
+                            instantiated_vars.push((var, par_type));
                         }
+                        None => match declared_constants.get(var) {
+                            Some(constant) => {
+                                instantiated_vars.push((var, &constant.symbol_type))
+                            }
+                            None => {
+                                errors.push(SemanticErrorType::UndefinedParameter(
+                                    UndefinedSymbolError {
+                                        symbol: var.name.to_string(),
+                                        position: var.name_pos,
+                                    },
+                                ));
+                                is_instantiated = false;
+                            }
+                        },
                     }
                 }
-                None => {
-                    return Err(SemanticErrorType::UndefinedPredicate(
-                        UndefinedSymbolError {
-                            symbol: instantiated_predicate.name.to_string(),
-                            position: instantiated_predicate.name_pos,
-                        },
-                    ));
+                // types can not be compared when some arguments are undefined
+                if !is_instantiated {
+                    return errors;
+                }
+                let expected_list: Vec<&Option<&str>> = predicate_definition
+                    .variables
+                    .iter()
+                    .map(|x| &x.symbol_type)
+                    .collect();
+                // Assert args have the same arity
+                if &instantiated_vars.len() != &expected_list.len() {
+                    errors.push(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                        symbol: instantiated_predicate.name.to_string(),
+                        expected_arity: expected_list.len() as u32,
+                        found_arity: instantiated_vars.len() as u32,
+                        position: instantiated_predicate.name_pos,
+                    }));
+                    return errors;
                 }
+                for ((var, f), e) in
+                    instantiated_vars.into_iter().zip(expected_list.into_iter())
+                {
+                    if !self.generic_type_checker.is_var_type_consistent(*f, *e) {
+                        errors.push(SemanticErrorType::InconsistentPredicateArgType(
+                            TypeError {
+                                expected: e.map(|inner| inner.to_string()),
+                                found: f.map(|inner| inner.to_string()),
+                                var_name: var.name.to_string(),
+                                position: var.name_pos,
+                            },
+                        ));
+                    }
+                }
+            }
+            None => {
+                errors.push(SemanticErrorType::UndefinedPredicate(
+                    UndefinedSymbolError {
+                        symbol: instantiated_predicate.name.to_string(),
+                        position: instantiated_predicate.name_pos,
+                    },
+                ));
             }
         }
-        Ok(())
+        errors
     }
 
     pub fn is_task_consistent(
@@ -125,7 +214,8 @@ impl<'a> DomainTypeChecker<'a> {
         declared_constants: &HashSet<&Symbol<'a>>,
         declared_tasks: &HashSet<&Task<'a>>,
         declared_actions: &HashSet<&Action<'a>>,
-    ) -> Result<(), SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         // Store parameter types as a mapping from name to (type and position)
         let par_types: HashMap<&str, &Symbol> =
             HashMap::from_iter(parameters.iter().map(|par| (par.name, par)));
@@ -133,15 +223,15 @@ impl<'a> DomainTypeChecker<'a> {
         for term in task_terms.iter() {
             match par_types.get(term.name) {
                 Some(par_definition) => {
-                    found_types.push((term, par_definition));
+                    found_types.push((term, *par_definition));
                 }
                 None => {
                     match declared_constants.get(term) {
                         Some(constant) => {
-                            found_types.push((term, constant))
+                            found_types.push((term, *constant))
                         }
                         None => {
-                            return Err(SemanticErrorType::UndefinedParameter(
+                            errors.push(SemanticErrorType::UndefinedParameter(
                                 UndefinedSymbolError {
                                     symbol: term.name.to_string(),
                                     position: term.name_pos,
@@ -152,77 +242,55 @@ impl<'a> DomainTypeChecker<'a> {
                 }
             }
         }
-        match declared_actions.iter().find(|x| x.name == task.name) {
-            Some(definition) => {
-                let expected_types: Vec<Option<&str>> = definition
+        let expected_types: Vec<Option<&str>> = match declared_actions.iter().find(|x| x.name == task.name) {
+            Some(definition) => definition
+                .parameters
+                .iter()
+                .map(|x| x.symbol_type)
+                .collect(),
+            None => match declared_tasks.iter().find(|x| x.name == task.name) {
+                Some(definition) => definition
                     .parameters
                     .iter()
                     .map(|x| x.symbol_type)
-                    .collect();
-                if task_terms.len() != expected_types.len() {
-                    return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
-                        symbol: task.name.to_string(),
-                        expected_arity: expected_types.len() as u32,
-                        found_arity: task_terms.len() as u32,
-                        position: task.name_pos,
-                    }));
-                }
-                for ((term, parameter), expected_type) in
-                    found_types.iter().zip(expected_types.iter())
-                {
-                    if !self
-                        .generic_type_checker
-                        .is_var_type_consistent(parameter.symbol_type, *expected_type)
-                    {
-                        return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
-                            expected: expected_type.map(|inner| inner.to_string()),
-                            found: parameter.symbol_type.map(|inner| inner.to_string()),
-                            var_name: term.name.to_string(),
-                            position: term.name_pos,
-                        }));
-                    }
-                }
-                return Ok(());
-            }
-            None => match declared_tasks.iter().find(|x| x.name == task.name) {
-                Some(definition) => {
-                    let expected: Vec<Option<&str>> = definition
-                        .parameters
-                        .iter()
-                        .map(|x| x.symbol_type)
-                        .collect();
-                    if found_types.len() != expected.len() {
-                        return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
-                            symbol: task.name.to_string(),
-                            expected_arity: expected.len() as u32,
-                            found_arity: found_types.len() as u32,
-                            position: task.name_pos,
-                        }));
-                    }
-                    for ((term, parameter), expected_type) in
-                        found_types.iter().zip(expected.iter())
-                    {
-                        if !self
-                            .generic_type_checker
-                            .is_var_type_consistent(parameter.symbol_type, *expected_type)
-                        {
-                            return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
-                                expected: expected_type.map(|inner| inner.to_string()),
-                                found: parameter.symbol_type.map(|inner| inner.to_string()),
-                                var_name: term.name.to_string(),
-                                position: term.name_pos,
-                            }));
-                        }
-                    }
-                    return Ok(());
-                }
+                    .collect(),
                 None => {
-                    return Err(SemanticErrorType::UndefinedSubtask(UndefinedSymbolError {
+                    errors.push(SemanticErrorType::UndefinedSubtask(UndefinedSymbolError {
                         symbol: task.name.to_string(),
                         position: task.name_pos,
                     }));
+                    return errors;
                 }
             },
+        };
+        if task_terms.len() != expected_types.len() {
+            errors.push(SemanticErrorType::InconsistentTaskArity(ArityError {
+                symbol: task.name.to_string(),
+                expected_arity: expected_types.len() as u32,
+                found_arity: task_terms.len() as u32,
+                position: task.name_pos,
+            }));
+            return errors;
+        }
+        // types can not be compared when some arguments are undefined
+        if found_types.len() != task_terms.len() {
+            return errors;
+        }
+        for ((term, parameter), expected_type) in
+            found_types.iter().zip(expected_types.iter())
+        {
+            if !self
+                .generic_type_checker
+                .is_var_type_consistent(parameter.symbol_type, *expected_type)
+            {
+                errors.push(SemanticErrorType::InconsistentTaskArgType(TypeError {
+                    expected: expected_type.map(|inner| inner.to_string()),
+                    found: parameter.symbol_type.map(|inner| inner.to_string()),
+                    var_name: term.name.to_string(),
+                    position: term.name_pos,
+                }));
+            }
         }
+        errors
     }
 }
@@ -47,18 +47,19 @@ impl<'a> TypeChecker<'a> {
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         for parameter in parameters.iter() {
             if let Some(t) = parameter.symbol_type {
                 if !self.type_hierarchy.contains_node(t) {
-                    return Some(SemanticErrorType::UndefinedType(UndefinedSymbolError {
+                    errors.push(SemanticErrorType::UndefinedType(UndefinedSymbolError {
                         symbol: parameter.symbol_type.unwrap().to_string(),
                         position: parameter.type_pos.unwrap(),
                     }));
                 }
             }
         }
-        None
+        errors
     }
 
     pub fn is_var_type_consistent(
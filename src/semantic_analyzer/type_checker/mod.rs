@@ -10,4 +10,4 @@ use super::*;
 
 pub use domain_type_checker::DomainTypeChecker;
 pub use problem_type_checker::ProblemTypeChecker;
-use generic_type_checker::*;
\ No newline at end of file
+pub use generic_type_checker::*;
\ No newline at end of file
@@ -28,7 +28,7 @@ impl<'a> ProblemTypeChecker<'a> {
     pub fn check_type_declarations(
         &self,
         parameters: &Vec<Symbol<'a>>,
-    ) -> Option<SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
         self.generic_type_checker
             .check_type_declarations(parameters)
     }
@@ -36,29 +36,74 @@ impl<'a> ProblemTypeChecker<'a> {
     pub fn check_predicate_instantiation(
         &self,
         predicate: &'a Predicate<'a>,
-    ) -> Result<(), SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
+        self.check_scoped_predicate(predicate, &HashMap::new())
+    }
+
+    // goals may quantify over the objects, so their variables are in scope of the subformula
+    pub fn check_goal(
+        &self,
+        goal: &'a Formula<'a>,
+        scope: &HashMap<&'a str, Option<&'a str>>,
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
+        match goal {
+            Formula::Empty | Formula::Equals(_, _) => {}
+            Formula::Atom(predicate) => {
+                errors.extend(self.check_scoped_predicate(predicate, scope));
+            }
+            Formula::Not(f) => {
+                errors.extend(self.check_goal(f, scope));
+            }
+            Formula::And(fs) | Formula::Or(fs) | Formula::Xor(fs) => {
+                for f in fs.iter() {
+                    errors.extend(self.check_goal(f, scope));
+                }
+            }
+            Formula::Imply(antecedents, consequences) => {
+                for f in antecedents.iter().chain(consequences.iter()) {
+                    errors.extend(self.check_goal(f, scope));
+                }
+            }
+            Formula::ForAll(vars, f) | Formula::Exists(vars, f) => {
+                errors.extend(self.check_type_declarations(vars));
+                let mut inner_scope = scope.clone();
+                inner_scope.extend(vars.iter().map(|var| (var.name, var.symbol_type)));
+                errors.extend(self.check_goal(f, &inner_scope));
+            }
+        }
+        errors
+    }
+
+    fn check_scoped_predicate(
+        &self,
+        predicate: &'a Predicate<'a>,
+        scope: &HashMap<&'a str, Option<&'a str>>,
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         match &self.symbol_table.predicates.get(predicate) {
             Some(definition) => {
                 if definition.variables.len() != predicate.variables.len() {
-                    return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                    errors.push(SemanticErrorType::InconsistentPredicateArity(ArityError {
                         symbol: predicate.name.to_string(),
                         expected_arity: definition.variables.len() as u32,
                         found_arity: predicate.variables.len() as u32,
                         position: predicate.name_pos,
                     }));
+                    return errors;
                 }
                 for (expected, found) in definition.variables.iter().zip(predicate.variables.iter())
                 {
-                    match self.objects.get(found.name) {
+                    match scope.get(found.name).or(self.objects.get(found.name)) {
                         Some(object_type) => {
                             let is_consistent = self
                                 .generic_type_checker
                                 .is_var_type_consistent(*object_type, expected.symbol_type);
                             if !is_consistent {
-                                return Err(SemanticErrorType::InconsistentPredicateArgType(
+                                errors.push(SemanticErrorType::InconsistentPredicateArgType(
                                     TypeError {
                                         expected: expected.symbol_type.map(String::from),
-                                        found: found.symbol_type.map(String::from),
+                                        found: object_type.map(String::from),
                                         var_name: predicate.name.to_string(),
                                         position: found.name_pos,
                                     },
@@ -73,7 +118,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                         expected.symbol_type,
                                     );
                                 if !is_consistent {
-                                    return Err(SemanticErrorType::InconsistentPredicateArgType(
+                                    errors.push(SemanticErrorType::InconsistentPredicateArgType(
                                         TypeError {
                                             expected: expected.symbol_type.map(String::from),
                                             found: constant.symbol_type.map(String::from),
@@ -84,7 +129,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                 }
                             }
                             None => {
-                                return Err(SemanticErrorType::UndefinedObject(
+                                errors.push(SemanticErrorType::UndefinedObject(
                                     UndefinedSymbolError {
                                         symbol: found.name.to_string(),
                                         position: found.name_pos,
@@ -94,15 +139,16 @@ impl<'a> ProblemTypeChecker<'a> {
                         },
                     }
                 }
-                return Ok(());
+                return errors;
             }
             None => {
-                return Err(SemanticErrorType::UndefinedPredicate(
+                errors.push(SemanticErrorType::UndefinedPredicate(
                     UndefinedSymbolError {
                         symbol: predicate.name.to_string(),
                         position: predicate.name_pos,
                     },
                 ));
+                return errors;
             }
         }
     }
@@ -112,16 +158,18 @@ impl<'a> ProblemTypeChecker<'a> {
         &self,
         subtask: &'a Subtask<'a>,
         parameters: &Option<Vec<Symbol<'a>>>,
-    ) -> Result<(), SemanticErrorType> {
+    ) -> Vec<SemanticErrorType> {
+        let mut errors = vec![];
         if self.symbol_table.actions.contains(&subtask.task.name) {
             let action = self.symbol_table.actions.get(&subtask.task.name).unwrap();
             if action.parameters.len() != subtask.terms.len() {
-                return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
+                errors.push(SemanticErrorType::InconsistentTaskArity(ArityError {
                     symbol: subtask.task.name.to_string(),
                     expected_arity: action.parameters.len() as u32,
                     found_arity: subtask.terms.len() as u32,
                     position: subtask.task.name_pos,
                 }));
+                return errors;
             }
             for (expected, found) in action.parameters.iter().zip(subtask.terms.iter()) {
                 match self.objects.get(found.name) {
@@ -130,7 +178,7 @@ impl<'a> ProblemTypeChecker<'a> {
                             .generic_type_checker
                             .is_var_type_consistent(*object_type, expected.symbol_type);
                         if !is_consistent {
-                            return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
+                            errors.push(SemanticErrorType::InconsistentTaskArgType(TypeError {
                                 expected: expected.symbol_type.map(String::from),
                                 found: object_type.map(String::from),
                                 var_name: subtask.task.name.to_string(),
@@ -149,7 +197,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                             expected.symbol_type,
                                         );
                                     if !is_consistent {
-                                        return Err(SemanticErrorType::InconsistentTaskArgType(
+                                        errors.push(SemanticErrorType::InconsistentTaskArgType(
                                             TypeError {
                                                 expected: expected.symbol_type.map(String::from),
                                                 found: param.symbol_type.map(String::from),
@@ -176,7 +224,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                             expected.symbol_type,
                                         );
                                     if !is_consistent {
-                                        return Err(
+                                        errors.push(
                                             SemanticErrorType::InconsistentPredicateArgType(
                                                 TypeError {
                                                     expected: expected
@@ -191,7 +239,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                     }
                                 }
                                 None => {
-                                    return Err(SemanticErrorType::UndefinedObject(
+                                    errors.push(SemanticErrorType::UndefinedObject(
                                         UndefinedSymbolError {
                                             symbol: found.name.to_string(),
                                             position: found.name_pos,
@@ -203,16 +251,17 @@ impl<'a> ProblemTypeChecker<'a> {
                     }
                 }
             }
-            return Ok(());
+            return errors;
         } else if self.symbol_table.tasks.contains(subtask.task.name) {
             let task = self.symbol_table.tasks.get(&subtask.task.name).unwrap();
             if task.parameters.len() != subtask.terms.len() {
-                return Err(SemanticErrorType::InconsistentTaskArity(ArityError {
+                errors.push(SemanticErrorType::InconsistentTaskArity(ArityError {
                     symbol: subtask.task.name.to_string(),
                     expected_arity: task.parameters.len() as u32,
                     found_arity: subtask.terms.len() as u32,
                     position: subtask.task.name_pos,
                 }));
+                return errors;
             }
             for (expected, found) in task.parameters.iter().zip(subtask.terms.iter()) {
                 match self.objects.get(found.name) {
@@ -221,7 +270,7 @@ impl<'a> ProblemTypeChecker<'a> {
                             .generic_type_checker
                             .is_var_type_consistent(*object_type, expected.symbol_type);
                         if !is_consistent {
-                            return Err(SemanticErrorType::InconsistentTaskArgType(TypeError {
+                            errors.push(SemanticErrorType::InconsistentTaskArgType(TypeError {
                                 expected: expected.symbol_type.map(String::from),
                                 found: object_type.map(String::from),
                                 var_name: subtask.task.name.to_string(),
@@ -240,7 +289,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                             expected.symbol_type,
                                         );
                                     if !is_consistent {
-                                        return Err(SemanticErrorType::InconsistentTaskArgType(
+                                        errors.push(SemanticErrorType::InconsistentTaskArgType(
                                             TypeError {
                                                 expected: expected.symbol_type.map(String::from),
                                                 found: definition.symbol_type.map(String::from),
@@ -267,7 +316,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                             expected.symbol_type,
                                         );
                                     if !is_consistent {
-                                        return Err(
+                                        errors.push(
                                             SemanticErrorType::InconsistentPredicateArgType(
                                                 TypeError {
                                                     expected: expected
@@ -282,7 +331,7 @@ impl<'a> ProblemTypeChecker<'a> {
                                     }
                                 }
                                 None => {
-                                    return Err(SemanticErrorType::UndefinedObject(
+                                    errors.push(SemanticErrorType::UndefinedObject(
                                         UndefinedSymbolError {
                                             symbol: found.name.to_string(),
                                             position: found.name_pos,
@@ -294,12 +343,13 @@ impl<'a> ProblemTypeChecker<'a> {
                     }
                 }
             }
-            return Ok(());
+            return errors;
         } else {
-            return Err(SemanticErrorType::UndefinedSubtask(UndefinedSymbolError {
+            errors.push(SemanticErrorType::UndefinedSubtask(UndefinedSymbolError {
                 symbol: subtask.task.name.to_string(),
                 position: subtask.task.name_pos,
             }));
+            return errors;
         }
     }
 }
@@ -3,7 +3,8 @@ use super::*;
 pub fn check_predicate_declarations<'a>(
     formula: &Formula<'a>,
     declared_predicates: &Vec<Predicate<'a>>,
-) -> Result<(), SemanticErrorType> {
+) -> Vec<SemanticErrorType> {
+    let mut errors = vec![];
     match &*formula {
         Formula::Empty => {}
         Formula::Atom(predicate) => {
@@ -11,19 +12,18 @@ pub fn check_predicate_declarations<'a>(
                 // Assert same name
                 if predicate.name == declared_predicate.name {
                     // Assert same arity
-                    if predicate.variables.len() == declared_predicate.variables.len() {
-                        return Ok(());
-                    } else {
-                        return Err(SemanticErrorType::InconsistentPredicateArity(ArityError {
+                    if predicate.variables.len() != declared_predicate.variables.len() {
+                        errors.push(SemanticErrorType::InconsistentPredicateArity(ArityError {
                             symbol: predicate.name.to_string(),
                             expected_arity: declared_predicate.variables.len() as u32,
                             found_arity: predicate.variables.len() as u32,
                             position: predicate.name_pos
                         }));
                     }
+                    return errors;
                 }
             }
-            return Err(SemanticErrorType::UndefinedPredicate(
+            errors.push(SemanticErrorType::UndefinedPredicate(
                 UndefinedSymbolError {
                     symbol: predicate.name.to_string(),
                     position: predicate.name_pos,
@@ -35,17 +35,18 @@ pub fn check_predicate_declarations<'a>(
         }
         Formula::And(new_formula) | Formula::Or(new_formula) | Formula::Xor(new_formula) => {
             for f in new_formula {
-                check_predicate_declarations(&*f, declared_predicates)?;
+                errors.extend(check_predicate_declarations(&*f, declared_predicates));
             }
         }
-        Formula::ForAll(_, new_formula) => {
+        Formula::Imply(antecedents, consequences) => {
+            for f in antecedents.iter().chain(consequences.iter()) {
+                errors.extend(check_predicate_declarations(&*f, declared_predicates));
+            }
+        }
+        Formula::ForAll(_, new_formula) | Formula::Exists(_, new_formula) => {
             return check_predicate_declarations(&*new_formula, declared_predicates);
         }
         Formula::Equals(_, _) => {}
-        // TODO: add support for imply, and exists
-        _ => {
-            panic!()
-        }
     }
-    return Ok(());
+    return errors;
 }
@@ -1,7 +1,7 @@
 use super::*;
 
 impl<'a> Parser<'a> {
-    pub fn parse_formula(&'a self) -> Result<Formula, ParsingError> {
+    pub fn parse_formula(&self) -> Result<Formula<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::RParentheses) => {
                 return Ok(Formula::Empty);
@@ -63,29 +63,38 @@ impl<'a> Parser<'a> {
                     }
                     // Equality
                     Token::Operator(OperationType::Equal) => match self.tokenizer.get_token()? {
-                        Token::Identifier(p1) => match self.tokenizer.get_token()? {
-                            Token::Identifier(p2) => match self.tokenizer.get_token()? {
-                                Token::Punctuator(PunctuationType::RParentheses) => {
-                                    return Ok(Formula::Equals(p1, p2));
+                        Token::Identifier(p1) => {
+                            let p1_pos = self.tokenizer.get_last_token_position();
+                            match self.tokenizer.get_token()? {
+                                Token::Identifier(p2) => {
+                                    let p2_pos = self.tokenizer.get_last_token_position();
+                                    match self.tokenizer.get_token()? {
+                                        Token::Punctuator(PunctuationType::RParentheses) => {
+                                            return Ok(Formula::Equals(
+                                                Symbol::new(p1, p1_pos, None, None),
+                                                Symbol::new(p2, p2_pos, None, None),
+                                            ));
+                                        }
+                                        token => {
+                                            let error = SyntacticError {
+                                                expected: "equality's closing parenthesis".to_string(),
+                                                found: token.to_string(),
+                                                position: self.tokenizer.get_last_token_position(),
+                                            };
+                                            return Err(ParsingError::Syntactic(error));
+                                        }
+                                    }
                                 }
                                 token => {
                                     let error = SyntacticError {
-                                        expected: "equality's closing parenthesis".to_string(),
+                                        expected: "right hand side of the equality".to_string(),
                                         found: token.to_string(),
                                         position: self.tokenizer.get_last_token_position(),
                                     };
                                     return Err(ParsingError::Syntactic(error));
                                 }
-                            },
-                            token => {
-                                let error = SyntacticError {
-                                    expected: "right hand side of the equality".to_string(),
-                                    found: token.to_string(),
-                                    position: self.tokenizer.get_last_token_position(),
-                                };
-                                return Err(ParsingError::Syntactic(error));
                             }
-                        },
+                        }
                         token => {
                             let error = SyntacticError {
                                 expected: "left hand side of the equality".to_string(),
@@ -95,6 +104,27 @@ impl<'a> Parser<'a> {
                             return Err(ParsingError::Syntactic(error));
                         }
                     },
+                    // Implication (and conditional effects)
+                    Token::Operator(OperationType::Implication) => {
+                        let antecedent = self.parse_formula()?;
+                        let consequent = self.parse_formula()?;
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => {
+                                return Ok(Formula::Imply(
+                                    vec![Box::new(antecedent)],
+                                    vec![Box::new(consequent)],
+                                ));
+                            }
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "')' to close the implication".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                return Err(ParsingError::Syntactic(error));
+                            }
+                        }
+                    }
                     // Universal Quantifier
                     Token::Operator(OperationType::ForAll) => match self.tokenizer.get_token()? {
                         Token::Punctuator(PunctuationType::LParentheses) => {
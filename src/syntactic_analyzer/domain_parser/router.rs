@@ -1,7 +1,7 @@
 use super::*;
 
 impl <'a> Parser<'a> {
-    pub fn parse_domain(&'a self, domain_name: &'a str) -> Result<DomainAST<'a>, ParsingError> {
+    pub fn parse_domain(&self, domain_name: &'a str) -> Result<DomainAST<'a>, ParsingError> {
         let mut syntax_tree = DomainAST::new(domain_name.to_string());
         loop {
             match self.tokenizer.get_token()? {
@@ -16,44 +16,27 @@ impl <'a> Parser<'a> {
                         }
                         // compund task definition
                         Token::Keyword(KeywordName::Task) => {
-                            let task = self.parse_task()?;
-                            match self.tokenizer.get_token()? {
-                                Token::Punctuator(
-                                    PunctuationType::RParentheses,
-                                ) => {
-                                    syntax_tree.add_compound_task(task);
-                                }
-                                token => {
-                                    let error = SyntacticError {
-                                        expected: format!(
-                                            "')' after definition of {}",
-                                            task.name
-                                        )
-                                        .to_string(),
-                                        found: token.to_string(),
-                                        position: self
-                                            .tokenizer
-                                            .get_last_token_position(),
-                                    };
-                                    return Err(ParsingError::Syntactic(error));
-                                }
+                            if let Err(error) = self.parse_compound_task(&mut syntax_tree) {
+                                self.parse_recovered_blocks(error, &mut syntax_tree)?;
                             }
                         }
                         // method definition
                         Token::Keyword(KeywordName::Method) => {
-                            let method = self.parse_method()?;
-                            syntax_tree.add_method(method);
+                            if let Err(error) = self.parse_method_block(&mut syntax_tree) {
+                                self.parse_recovered_blocks(error, &mut syntax_tree)?;
+                            }
                         }
                         // action definition
                         Token::Keyword(KeywordName::Action) => {
-                            let action = self.parse_action()?;
-                            syntax_tree.add_action(action);
+                            if let Err(error) = self.parse_action_block(&mut syntax_tree) {
+                                self.parse_recovered_blocks(error, &mut syntax_tree)?;
+                            }
                         }
                         // requirement declaration
                         Token::Keyword(KeywordName::Requirements) => {
                             let requirements = self.parse_requirements()?;
-                            for requirement in requirements {
-                                syntax_tree.add_requirement(requirement);
+                            for (requirement, position) in requirements {
+                                syntax_tree.add_requirement(requirement, position);
                             }
                         }
                         // type hierarchy declaration
@@ -76,22 +59,87 @@ impl <'a> Parser<'a> {
                                 found: token.to_string(),
                                 position: self.tokenizer.get_last_token_position(),
                             };
-                            return Err(ParsingError::Syntactic(error));
+                            self.parse_recovered_blocks(ParsingError::Syntactic(error), &mut syntax_tree)?;
                         }
                     }
                 }
                 Token::Punctuator(PunctuationType::RParentheses) => {
                     return Ok(syntax_tree);
                 }
+                // the end of file is reached while recovering from a broken block
+                Token::EOF if self.has_recovered() => {
+                    return Ok(syntax_tree);
+                }
                 token => {
                     let error = SyntacticError {
                         expected: format!("either ')' to close the definition of {}, or '(' to start defining new components", domain_name),
                         found: token.to_string(),
                         position: self.tokenizer.get_last_token_position(),
                     };
-                    return Err(ParsingError::Syntactic(error));
+                    self.parse_recovered_blocks(ParsingError::Syntactic(error), &mut syntax_tree)?;
+                }
+            }
+        }
+    }
+
+    fn parse_compound_task(&self, syntax_tree: &mut DomainAST<'a>) -> Result<(), ParsingError> {
+        let task = self.parse_task()?;
+        match self.tokenizer.get_token()? {
+            Token::Punctuator(
+                PunctuationType::RParentheses,
+            ) => {
+                syntax_tree.add_compound_task(task);
+                Ok(())
+            }
+            token => {
+                let error = SyntacticError {
+                    expected: format!(
+                        "')' after definition of {}",
+                        task.name
+                    )
+                    .to_string(),
+                    found: token.to_string(),
+                    position: self
+                        .tokenizer
+                        .get_last_token_position(),
+                };
+                return Err(ParsingError::Syntactic(error));
+            }
+        }
+    }
+
+    fn parse_method_block(&self, syntax_tree: &mut DomainAST<'a>) -> Result<(), ParsingError> {
+        let method = self.parse_method()?;
+        syntax_tree.add_method(method);
+        Ok(())
+    }
+
+    fn parse_action_block(&self, syntax_tree: &mut DomainAST<'a>) -> Result<(), ParsingError> {
+        let action = self.parse_action()?;
+        syntax_tree.add_action(action);
+        Ok(())
+    }
+
+    // skips a broken block and keeps parsing the :action, :method, and :task
+    // blocks that come after it (only in recovery mode)
+    fn parse_recovered_blocks(&self, error: ParsingError, syntax_tree: &mut DomainAST<'a>) -> Result<(), ParsingError> {
+        let mut next_block = self.recover(error)?;
+        while let Some(keyword) = next_block {
+            let result = match keyword {
+                Token::Keyword(KeywordName::Task) => self.parse_compound_task(syntax_tree),
+                Token::Keyword(KeywordName::Method) => self.parse_method_block(syntax_tree),
+                Token::Keyword(KeywordName::Action) => self.parse_action_block(syntax_tree),
+                _ => unreachable!(),
+            };
+            match result {
+                Ok(_) => {
+                    next_block = None;
+                }
+                Err(error) => {
+                    next_block = self.recover(error)?;
                 }
             }
         }
+        Ok(())
     }
-}
+}
\ No newline at end of file
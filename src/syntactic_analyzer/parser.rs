@@ -1,16 +1,81 @@
+use std::cell::{Cell, RefCell};
+
 use self::definition_types::ProblemDefinition;
 
 use super::*;
 
 pub struct Parser<'a> {
     pub tokenizer: LexicalAnalyzer<'a>,
+    // whether to recover from errors in domain blocks (panic mode)
+    recovery: Cell<bool>,
+    recovered_errors: RefCell<Vec<ParsingError>>,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokenizer: LexicalAnalyzer<'a>) -> Parser<'a> {
-        Parser { tokenizer }
+        Parser {
+            tokenizer,
+            recovery: Cell::new(false),
+            recovered_errors: RefCell::new(vec![]),
+        }
     }
-    pub fn parse(&'a self) -> Result<AbstractSyntaxTree<'a>, ParsingError> {
+
+    // parses the document while recovering from errors at the boundaries of
+    // :action, :method, and :task blocks, and returns all errors found
+    pub fn parse_all(&self) -> Result<AbstractSyntaxTree<'a>, Vec<ParsingError>> {
+        self.recovery.set(true);
+        let result = self.parse();
+        let mut errors = self.recovered_errors.take();
+        match result {
+            Ok(ast) => {
+                if errors.is_empty() {
+                    Ok(ast)
+                } else {
+                    Err(errors)
+                }
+            }
+            Err(error) => {
+                errors.push(error);
+                Err(errors)
+            }
+        }
+    }
+
+    pub(super) fn has_recovered(&self) -> bool {
+        !self.recovered_errors.borrow().is_empty()
+    }
+
+    // records the error of a broken block (in recovery mode), and skips the
+    // tokens until the start of the next :action, :method, or :task block
+    pub(super) fn recover(&self, error: ParsingError) -> Result<Option<Token<'a>>, ParsingError> {
+        if !self.recovery.get() {
+            return Err(error);
+        }
+        self.recovered_errors.borrow_mut().push(error);
+        let mut is_block_start = false;
+        loop {
+            match self.tokenizer.get_token() {
+                Ok(Token::EOF) => {
+                    return Ok(None);
+                }
+                Ok(Token::Punctuator(PunctuationType::LParentheses)) => {
+                    is_block_start = true;
+                }
+                Ok(keyword @ Token::Keyword(KeywordName::Action))
+                | Ok(keyword @ Token::Keyword(KeywordName::Method))
+                | Ok(keyword @ Token::Keyword(KeywordName::Task)) => {
+                    if is_block_start {
+                        return Ok(Some(keyword));
+                    }
+                }
+                // lexical errors inside a broken block are ignored
+                _ => {
+                    is_block_start = false;
+                }
+            }
+        }
+    }
+    pub fn parse(&self) -> Result<AbstractSyntaxTree<'a>, ParsingError> {
         // match opening '('
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
@@ -37,7 +102,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_document_type(&self) -> Result<DefinitionType, ParsingError> {
+    fn parse_document_type(&self) -> Result<DefinitionType<'a>, ParsingError> {
         // match keyword 'define'
         match self.tokenizer.get_token()? {
             Token::Keyword(KeywordName::Define) => {
@@ -83,7 +148,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_domain_header(&self) -> Result<DefinitionType, ParsingError> {
+    fn parse_domain_header(&self) -> Result<DefinitionType<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Identifier(domain_name) => {
                 // match closing paranthesis
@@ -112,7 +177,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_problem_header(&self) -> Result<DefinitionType, ParsingError> {
+    fn parse_problem_header(&self) -> Result<DefinitionType<'a>, ParsingError> {
         // match problem name
         match self.tokenizer.get_token()? {
             Token::Identifier(problem_name) => {
@@ -197,13 +262,17 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_requirements(&self) -> Result<Vec<RequirementType>, ParsingError> {
+    pub fn parse_requirements(&self) -> Result<Vec<(RequirementType, TokenPosition)>, ParsingError> {
         let mut requirements = vec![];
         let mut finished = false;
         while !finished {
             match self.tokenizer.get_token()? {
                 Token::Requirement(req) => {
-                    requirements.push(req);
+                    requirements.push((req, self.tokenizer.get_last_token_position()));
+                }
+                // ":constraints" is both a requirement and a keyword of task networks
+                Token::Keyword(KeywordName::Constraints) => {
+                    requirements.push((RequirementType::Constraints, self.tokenizer.get_last_token_position()));
                 }
                 Token::Punctuator(PunctuationType::RParentheses) => {
                     finished = true;
@@ -1,7 +1,7 @@
 use super::*;
 
 impl<'a> Parser<'a> {
-    pub fn parse_initial_tn(&'a self) -> Result<InitialTaskNetwork<'a>, ParsingError> {
+    pub fn parse_initial_tn(&self) -> Result<InitialTaskNetwork<'a>, ParsingError> {
         loop {
             match self.tokenizer.lookahead()? {
                 Token::Keyword(KeywordName::Parameters) => {
@@ -53,7 +53,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_htn(&'a self) -> Result<HTN<'a>, ParsingError> {
+    pub fn parse_htn(&self) -> Result<HTN<'a>, ParsingError> {
         let mut subtasks = vec![];
         let mut orderings = vec![];
         let mut constraints = None;
@@ -197,12 +197,25 @@ impl<'a> Parser<'a> {
                 match self.tokenizer.get_token()? {
                     Token::Keyword(KeywordName::Constraints) => {
                         constraints = Some(self.parse_constraints()?);
-                        return Ok(HTN {
-                            subtasks,
-                            ordering_pos,
-                            orderings: TaskOrdering::Total,
-                            constraints,
-                        });
+                        // match the closing ')' of the enclosing block
+                        match self.tokenizer.get_token()? {
+                            Token::Punctuator(PunctuationType::RParentheses) => {
+                                return Ok(HTN {
+                                    subtasks,
+                                    ordering_pos,
+                                    orderings: TaskOrdering::Total,
+                                    constraints,
+                                });
+                            }
+                            token => {
+                                let error = SyntacticError {
+                                    expected: "closing ')' after task network definition".to_string(),
+                                    found: token.to_string(),
+                                    position: self.tokenizer.get_last_token_position(),
+                                };
+                                return Err(ParsingError::Syntactic(error));
+                            }
+                        }
                     }
                     Token::Punctuator(PunctuationType::RParentheses) => {
                         return Ok(HTN {
@@ -234,7 +247,7 @@ impl<'a> Parser<'a> {
     }
 
     // parse a single ordering constraint
-    fn parse_ordering(&'a self) -> Result<Vec<(&'a str, &'a str)>, ParsingError> {
+    fn parse_ordering(&self) -> Result<Vec<(&'a str, &'a str)>, ParsingError> {
         let mut orderings: Vec<(&str, &str)> = vec![];
         match self.tokenizer.get_token()? {
             Token::Operator(OperationType::LessThan) => match self.tokenizer.get_token()? {
@@ -277,7 +290,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    fn parse_subtasks(&self) -> Result<Vec<Subtask>, ParsingError> {
+    fn parse_subtasks(&self) -> Result<Vec<Subtask<'a>>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
                 match self.tokenizer.lookahead()? {
@@ -336,7 +349,7 @@ impl<'a> Parser<'a> {
     }
 
     // parses a single subtask
-    fn parse_subtask(&'a self) -> Result<Subtask, ParsingError> {
+    fn parse_subtask(&self) -> Result<Subtask<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Identifier(id) => {
                 let id_symbol = Symbol::new(
@@ -479,7 +492,7 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_constraints(&'a self) -> Result<Vec<Constraint<'a>>, ParsingError> {
+    pub fn parse_constraints(&self) -> Result<Vec<Constraint<'a>>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Punctuator(PunctuationType::LParentheses) => {
                 let mut constraints = vec![];
@@ -539,58 +552,62 @@ impl<'a> Parser<'a> {
         }
     }
 
-    pub fn parse_constraint(&'a self) -> Result<Constraint<'a>, ParsingError> {
+    pub fn parse_constraint(&self) -> Result<Constraint<'a>, ParsingError> {
         match self.tokenizer.get_token()? {
             Token::Operator(OperationType::Not) => match self.tokenizer.get_token()? {
                 Token::Punctuator(PunctuationType::LParentheses) => {
                     match self.tokenizer.get_token()? {
                         Token::Operator(OperationType::Equal) => {
                             match self.tokenizer.get_token()? {
-                                Token::Identifier(t1) => match self.tokenizer.get_token()? {
-                                    Token::Identifier(t2) => {
-                                        match self.tokenizer.get_token()? {
-                                            Token::Punctuator(PunctuationType::RParentheses) => {
-                                                match self.tokenizer.get_token()? {
-                                                    Token::Punctuator(
-                                                        PunctuationType::RParentheses,
-                                                    ) => {
-                                                        return Ok(Constraint::NotEqual(t1, t2));
-                                                    }
-                                                    token => {
-                                                        let error = SyntacticError{
-                                                                    expected: format!(") to close the inequality constraint").to_string(),
-                                                                    found: token.to_string(),
-                                                                    position: self.tokenizer.get_last_token_position(),
-                                                                };
-                                                        return Err(ParsingError::Syntactic(error));
+                                Token::Identifier(t1) => {
+                                    let t1 = Symbol::new(t1, self.tokenizer.get_last_token_position(), None, None);
+                                    match self.tokenizer.get_token()? {
+                                        Token::Identifier(t2) => {
+                                            let t2 = Symbol::new(t2, self.tokenizer.get_last_token_position(), None, None);
+                                            match self.tokenizer.get_token()? {
+                                                Token::Punctuator(PunctuationType::RParentheses) => {
+                                                    match self.tokenizer.get_token()? {
+                                                        Token::Punctuator(
+                                                            PunctuationType::RParentheses,
+                                                        ) => {
+                                                            return Ok(Constraint::NotEqual(t1, t2));
+                                                        }
+                                                        token => {
+                                                            let error = SyntacticError{
+                                                                        expected: format!(") to close the inequality constraint").to_string(),
+                                                                        found: token.to_string(),
+                                                                        position: self.tokenizer.get_last_token_position(),
+                                                                    };
+                                                            return Err(ParsingError::Syntactic(error));
+                                                        }
                                                     }
                                                 }
+                                                token => {
+                                                    let error = SyntacticError {
+                                                        expected: format!(
+                                                            ") to close the inequality constraint"
+                                                        )
+                                                        .to_string(),
+                                                        found: token.to_string(),
+                                                        position: self
+                                                            .tokenizer
+                                                            .get_last_token_position(),
+                                                    };
+                                                    return Err(ParsingError::Syntactic(error));
+                                                }
                                             }
-                                            token => {
-                                                let error = SyntacticError {
-                                                    expected: format!(
-                                                        ") to close the inequality constraint"
-                                                    )
+                                        }
+                                        token => {
+                                            let error = SyntacticError {
+                                                expected: format!("right hand side of {}!=...", t1.name)
                                                     .to_string(),
-                                                    found: token.to_string(),
-                                                    position: self
-                                                        .tokenizer
-                                                        .get_last_token_position(),
-                                                };
-                                                return Err(ParsingError::Syntactic(error));
-                                            }
+                                                found: token.to_string(),
+                                                position: self.tokenizer.get_last_token_position(),
+                                            };
+                                            return Err(ParsingError::Syntactic(error));
                                         }
                                     }
-                                    token => {
-                                        let error = SyntacticError {
-                                            expected: format!("right hand side of {}!=...", t1)
-                                                .to_string(),
-                                            found: token.to_string(),
-                                            position: self.tokenizer.get_last_token_position(),
-                                        };
-                                        return Err(ParsingError::Syntactic(error));
-                                    }
-                                },
+                                }
                                 token => {
                                     let error = SyntacticError {
                                         expected: "task identifier".to_string(),
@@ -621,29 +638,35 @@ impl<'a> Parser<'a> {
                 }
             },
             Token::Operator(OperationType::Equal) => match self.tokenizer.get_token()? {
-                Token::Identifier(t1) => match self.tokenizer.get_token()? {
-                    Token::Identifier(t2) => match self.tokenizer.get_token()? {
-                        Token::Punctuator(PunctuationType::RParentheses) => {
-                            return Ok(Constraint::Equal(t1, t2));
+                Token::Identifier(t1) => {
+                    let t1 = Symbol::new(t1, self.tokenizer.get_last_token_position(), None, None);
+                    match self.tokenizer.get_token()? {
+                        Token::Identifier(t2) => {
+                            let t2 = Symbol::new(t2, self.tokenizer.get_last_token_position(), None, None);
+                            match self.tokenizer.get_token()? {
+                                Token::Punctuator(PunctuationType::RParentheses) => {
+                                    return Ok(Constraint::Equal(t1, t2));
+                                }
+                                token => {
+                                    let error = SyntacticError {
+                                        expected: format!(") to close the equality constraint").to_string(),
+                                        found: token.to_string(),
+                                        position: self.tokenizer.get_last_token_position(),
+                                    };
+                                    return Err(ParsingError::Syntactic(error));
+                                }
+                            }
                         }
                         token => {
                             let error = SyntacticError {
-                                expected: format!(") to close the equality constraint").to_string(),
+                                expected: format!("right hand side of {}=...", t1.name).to_string(),
                                 found: token.to_string(),
                                 position: self.tokenizer.get_last_token_position(),
                             };
                             return Err(ParsingError::Syntactic(error));
                         }
-                    },
-                    token => {
-                        let error = SyntacticError {
-                            expected: format!("right hand side of {}=...", t1).to_string(),
-                            found: token.to_string(),
-                            position: self.tokenizer.get_last_token_position(),
-                        };
-                        return Err(ParsingError::Syntactic(error));
                     }
-                },
+                }
                 token => {
                     let error = SyntacticError {
                         expected: "a task identifier".to_string(),
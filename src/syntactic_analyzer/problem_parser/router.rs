@@ -1,8 +1,11 @@
 use super::*;
 
 impl <'a> Parser<'a> {
-    pub fn parse_problem(&'a self, meta_data: ProblemDefinition<'a>) -> Result<ProblemAST<'a>, ParsingError> {
-        let mut syntax_tree = ProblemAST::new();
+    pub fn parse_problem(&self, meta_data: ProblemDefinition<'a>) -> Result<ProblemAST<'a>, ParsingError> {
+        let mut syntax_tree = ProblemAST::new(
+            meta_data.problem_name.to_string(),
+            meta_data.domain_name.to_string(),
+        );
         loop {
             match self.tokenizer.get_token()? {
                 Token::Punctuator(PunctuationType::LParentheses) => {
@@ -11,8 +14,8 @@ impl <'a> Parser<'a> {
                         // requirement declaration
                         Token::Keyword(KeywordName::Requirements) => {
                             let requirements = self.parse_requirements()?;
-                            for requirement in requirements {
-                                syntax_tree.add_requirement(requirement);
+                            for (requirement, position) in requirements {
+                                syntax_tree.add_requirement(requirement, position);
                             }
                         }
                         // objects declaration
@@ -66,8 +69,13 @@ impl <'a> Parser<'a> {
                 Token::EOF | Token::Punctuator(PunctuationType::RParentheses) => {
                     return Ok(syntax_tree);
                 }
-                err => {
-                    panic!("unexpected token {:?}", err)
+                token => {
+                    let error = SyntacticError {
+                        expected: "a block definition".to_string(),
+                        found: token.to_string(),
+                        position: self.tokenizer.get_last_token_position(),
+                    };
+                    return Err(ParsingError::Syntactic(error));
                 }
             }
         }
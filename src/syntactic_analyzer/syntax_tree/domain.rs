@@ -1,4 +1,5 @@
 use super::*;
+use crate::TokenPosition;
 
 #[derive(Debug)]
 pub struct DomainAST<'a> {
@@ -6,6 +7,7 @@ pub struct DomainAST<'a> {
     pub types: Option<Vec<Symbol<'a>>>,
     pub constants: Option<Vec<Symbol<'a>>>,
     pub requirements: Vec<RequirementType>,
+    pub requirement_positions: Vec<TokenPosition>,
     pub predicates: Vec<Predicate<'a>>,
     pub compound_tasks: Vec<Task<'a>>,
     pub methods: Vec<Method<'a>>,
@@ -19,6 +21,7 @@ impl<'a> DomainAST<'a> {
             types: None,
             constants: None,
             requirements: vec![],
+            requirement_positions: vec![],
             predicates: vec![],
             compound_tasks: vec![],
             methods: vec![],
@@ -26,8 +29,9 @@ impl<'a> DomainAST<'a> {
         }
     }
 
-    pub fn add_requirement(&mut self, req: RequirementType) {
+    pub fn add_requirement(&mut self, req: RequirementType, position: TokenPosition) {
         self.requirements.push(req);
+        self.requirement_positions.push(position);
     }
 
     pub fn add_predicate(&mut self, predicate: Predicate<'a>) {
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, fmt};
 
-extern crate Robinson;
 use super::*;
+use crate::sat::{Dpll, SatSolver};
 
 #[derive(Clone, Debug)]
 pub enum Formula<'a> {
@@ -18,7 +18,7 @@ pub enum Formula<'a> {
     // ∀vars: formula
     ForAll(Vec<Symbol<'a>>, Box<Formula<'a>>),
     // formula = formula'
-    Equals(&'a str, &'a str),
+    Equals(Symbol<'a>, Symbol<'a>),
 }
 
 impl<'a> Formula<'a> {
@@ -69,8 +69,8 @@ impl<'a> Formula<'a> {
                     },
                     Formula::Equals(a, b) => {
                         Formula::Xor(vec![
-                            Box::new(Formula::Atom(Predicate::new_dummy(a))),
-                            Box::new(Formula::Atom(Predicate::new_dummy(b))),
+                            Box::new(Formula::Atom(Predicate::new_dummy(a.name))),
+                            Box::new(Formula::Atom(Predicate::new_dummy(b.name))),
                         ]).simplify()
                     }
                     _ => {
@@ -130,9 +130,9 @@ impl<'a> Formula<'a> {
                 Formula::And(result)
             }
             Formula::Imply(antecedents, consequents) => {
-                let not_antecedents: Box<Formula<'a>> = Box::new(Formula::And(
+                let not_antecedents: Box<Formula<'a>> = Box::new(Formula::Not(Box::new(Formula::And(
                     antecedents.iter().map(|f| Box::new(f.simplify())).collect(),
-                ));
+                ))));
                 let consequents: Box<Formula<'a>> = Box::new(Formula::And(
                     consequents.iter().map(|f| Box::new(f.simplify())).collect(),
                 ));
@@ -147,10 +147,10 @@ impl<'a> Formula<'a> {
             Formula::Equals(a, b) => {
                 // a = b -> (a ^ b) v (~a ^ ~b)
                 let pred_a = Box::new(
-                    Formula::Atom(Predicate::new_dummy(&a))
+                    Formula::Atom(Predicate::new_dummy(a.name))
                 );
                 let pred_b = Box::new(
-                    Formula::Atom(Predicate::new_dummy(&b))
+                    Formula::Atom(Predicate::new_dummy(b.name))
                 );
                 let pos_conjunct = Formula::And(vec![pred_a.clone(), pred_b.clone()]);
                 let not_a = Box::new(Formula::Not(pred_a));
@@ -171,21 +171,21 @@ impl<'a> Formula<'a> {
                 Formula::Not(g) => g.to_nnf(),
                 Formula::And(fs) => Formula::Or(
                     fs.iter()
-                        .map(|f| Box::new(Formula::Not(Box::new(f.to_nnf()))))
+                        .map(|f| Box::new(Formula::Not(f.clone()).to_nnf()))
                         .collect(),
                 ),
                 Formula::Or(fs) => Formula::And(
                     fs.iter()
-                        .map(|f| Box::new(Formula::Not(Box::new(f.to_nnf()))))
+                        .map(|f| Box::new(Formula::Not(f.clone()).to_nnf()))
                         .collect(),
                 ),
                 Formula::Exists(quantifier, f) => Formula::ForAll(
                     quantifier.clone(),
-                    Box::new(Formula::Not(Box::new(f.to_nnf()))),
+                    Box::new(Formula::Not(f.clone()).to_nnf()),
                 ),
                 Formula::ForAll(quantifier, f) => Formula::Exists(
                     quantifier.clone(),
-                    Box::new(Formula::Not(Box::new(f.to_nnf()))),
+                    Box::new(Formula::Not(f.clone()).to_nnf()),
                 ),
                 //
                 Formula::Xor(_) | Formula::Imply(_, _) | Formula::Equals(_, _) => unreachable!("not simplified")
@@ -205,11 +205,17 @@ impl<'a> Formula<'a> {
     fn distribute_disjunction(&self) -> Formula<'a> {
         match self {
             Formula::Empty | Formula::Atom(_) | Formula::Not(_) => self.clone(),
-            Formula::And(fs) => Formula::And(
-                fs.iter()
-                    .map(|f| Box::new(f.distribute_disjunction()))
-                    .collect(),
-            ),
+            Formula::And(fs) => {
+                // conjunctions of distributed disjunctions are flattened into a single list of clauses
+                let mut conjuncts = vec![];
+                for f in fs {
+                    match f.distribute_disjunction() {
+                        Formula::And(clauses) => conjuncts.extend(clauses),
+                        clause => conjuncts.push(Box::new(clause)),
+                    }
+                }
+                Formula::And(conjuncts)
+            }
             Formula::Or(fs) => {
                 let distributed: Vec<Box<Formula<'a>>> = fs
                     .iter()
@@ -230,7 +236,14 @@ impl<'a> Formula<'a> {
                             queue.push(new_formula);
                         }
                     } else {
-                        result.push(Formula::Or(current));
+                        let mut disjuncts = vec![];
+                        for f in current {
+                            match *f {
+                                Formula::Or(sub_fs) => disjuncts.extend(sub_fs),
+                                other => disjuncts.push(Box::new(other)),
+                            }
+                        }
+                        result.push(Formula::Or(disjuncts));
                     }
                 }
                 if result.len() == 1 {
@@ -249,161 +262,102 @@ impl<'a> Formula<'a> {
         }
     }
 
-    fn drop_quantifiers(&self) -> Formula<'a> {
+    // quantified subformulas are left unconstrained (i.e. Empty), along with the connectives they make unconstrained
+    pub(super) fn drop_quantifiers(&self) -> Formula<'a> {
         match self {
-            Formula::Empty => {},
-            Formula::Atom(_) => {},
-            Formula::Not(f) => {
-                return Formula::Not(Box::new(f.drop_quantifiers()))
-            }
+            Formula::Empty | Formula::Atom(_) | Formula::Equals(_, _) => self.clone(),
+            Formula::Not(f) => match f.drop_quantifiers() {
+                Formula::Empty => Formula::Empty,
+                new_f => Formula::Not(Box::new(new_f)),
+            },
             Formula::And(fs) => {
-                return Formula::And(fs.iter().map(|f| {
-                    Box::new(f.drop_quantifiers())
-                }).collect());
-            }
-            Formula::Or(fs) => {
-                return Formula::Or(fs.iter().map(|f| {
-                    Box::new(f.drop_quantifiers())
-                }).collect());
+                let conjuncts: Vec<Box<Formula<'a>>> = fs
+                    .iter()
+                    .map(|f| f.drop_quantifiers())
+                    .filter(|f| !matches!(f, Formula::Empty))
+                    .map(Box::new)
+                    .collect();
+                if conjuncts.is_empty() {
+                    Formula::Empty
+                } else {
+                    Formula::And(conjuncts)
+                }
             }
-            Formula::Xor(fs) => {
-                return Formula::Xor(fs.iter().map(|f| {
-                    Box::new(f.drop_quantifiers())
-                }).collect());
+            Formula::Or(fs) | Formula::Xor(fs) => {
+                let new_fs: Vec<Formula<'a>> = fs.iter().map(|f| f.drop_quantifiers()).collect();
+                if new_fs.iter().any(|f| matches!(f, Formula::Empty)) {
+                    return Formula::Empty;
+                }
+                let new_fs = new_fs.into_iter().map(Box::new).collect();
+                match self {
+                    Formula::Or(_) => Formula::Or(new_fs),
+                    _ => Formula::Xor(new_fs),
+                }
             }
             Formula::Imply(ps, qs) => {
-                let new_ps = ps.iter().map(|p| {
-                    Box::new(p.drop_quantifiers())
-                }).collect();
-                let new_qs = qs.iter().map(|q| {
-                    Box::new(q.drop_quantifiers())
-                }).collect();
-                return Formula::Imply(new_ps, new_qs);
+                let new_ps: Vec<Formula<'a>> = ps.iter().map(|p| p.drop_quantifiers()).collect();
+                let new_qs: Vec<Formula<'a>> = qs.iter().map(|q| q.drop_quantifiers()).collect();
+                if new_ps.iter().chain(new_qs.iter()).any(|f| matches!(f, Formula::Empty)) {
+                    return Formula::Empty;
+                }
+                Formula::Imply(
+                    new_ps.into_iter().map(Box::new).collect(),
+                    new_qs.into_iter().map(Box::new).collect(),
+                )
             }
-            Formula::Equals(_, _) => {}
-            Formula::ForAll(_, _) | Formula::Exists(_, _) => {return Formula::Empty}
+            Formula::ForAll(_, _) | Formula::Exists(_, _) => Formula::Empty,
         }
-        self.clone()
     }
 
-    fn to_clauses(&self) -> (u32, Vec<Vec<i32>>) {
+    // clauses of the textbook CNF, which may be exponentially larger than the formula
+    pub fn to_clauses(&self) -> (u32, Vec<Vec<i32>>) {
+        let (symbols, clauses) = self.to_clauses_with_symbols();
+        (symbols.len() as u32, clauses)
+    }
+
+    // clauses of the textbook CNF, along with the symbol of each variable (i.e. variable i is symbols[i - 1])
+    pub fn to_clauses_with_symbols(&self) -> (Vec<String>, Vec<Vec<i32>>) {
+        let mut symbols = vec![];
         let mut literal_ids = HashMap::new();
-        let mut clauses: Vec<Vec<i32>> = vec![];
-        let mut count = 1;
-        let propositional = self.drop_quantifiers();
-        match propositional.to_cnf() {
-            Formula::Empty => {
-                return (0, vec![]);
-            }
-            Formula::And(subformula) => {
-                for f in subformula {
-                    let mut clause: Vec<i32> = vec![];
-                    match *f {
-                        Formula::Empty => {}
-                        Formula::Atom(predicate) => {
-                            if !literal_ids.contains_key(&predicate.to_string()) {
-                                literal_ids.insert(predicate.to_string(), count);
-                                count+=1;
-                            }
-                            clause.push(*literal_ids.get(&predicate.to_string()).unwrap());
-                        }
-                        Formula::Not(pred_box) => {
-                            if let Formula::Atom(predicate) = *pred_box {
-                                if !literal_ids.contains_key(&predicate.to_string()) {
-                                    literal_ids.insert(predicate.to_string(), count);
-                                    count+=1;
-                                }
-                                clause.push(-1 * literal_ids.get(&predicate.to_string()).unwrap().clone());
-                            } else {
-                                panic!("not simplified")
-                            }
-                        }
-                        Formula::Or(disjuncts) => {
-                            for disjunct in disjuncts {
-                                match *disjunct {
-                                    Formula::Atom(predicate) => {
-                                        if !literal_ids.contains_key(&predicate.to_string()) {
-                                            literal_ids.insert(predicate.to_string(), count);
-                                            count+=1;
-                                        }
-                                        clause.push(*literal_ids.get(&predicate.to_string()).unwrap());
-                                    },
-                                    Formula::Not(pred_box) => {
-                                        if let Formula::Atom(predicate) = *pred_box {
-                                            if !literal_ids.contains_key(&predicate.to_string()) {
-                                                literal_ids.insert(predicate.to_string(), count);
-                                                count+=1;
-                                            }
-                                            clause.push(-1 * literal_ids.get(&predicate.to_string()).unwrap().clone());
-                                        } else {
-                                            panic!("not simplified: {:?}", *pred_box)
-                                        }
-                                    }
-                                    _ => panic!("not in CNF")
-                                }
-                            }
-                        }
-                        token => panic!("not in CNF, found {:?}", token)
+        let mut literal = |f: &Formula<'a>| -> i32 {
+            let (predicate, sign) = match f {
+                Formula::Atom(predicate) => (predicate, 1),
+                Formula::Not(inner) => match &**inner {
+                    Formula::Atom(predicate) => (predicate, -1),
+                    token => panic!("not simplified: {:?}", token),
+                },
+                token => panic!("not in CNF, found {:?}", token),
+            };
+            let id = *literal_ids.entry(predicate.to_string()).or_insert_with(|| {
+                symbols.push(predicate.to_string());
+                symbols.len() as i32
+            });
+            sign * id
+        };
+        let clauses = match self.drop_quantifiers().to_cnf() {
+            Formula::Empty => vec![],
+            Formula::And(conjuncts) => conjuncts
+                .iter()
+                .map(|conjunct| match &**conjunct {
+                    Formula::Or(disjuncts) => disjuncts.iter().map(|d| literal(d)).collect(),
+                    f => vec![literal(f)],
+                })
+                .collect(),
+            Formula::Or(disjuncts) => vec![disjuncts.iter().map(|d| literal(d)).collect()],
+            f => vec![vec![literal(&f)]],
+        };
+        (symbols, clauses)
+    }
 
-                    }
-                    clauses.push(clause);
-                }
-            }
-            Formula::Or(disjuncts) => {
-                for disjunct in disjuncts.iter() {
-                    let mut clause = vec![];
-                    match &**disjunct {
-                        Formula::Atom(predicate) => {
-                            if !literal_ids.contains_key(&predicate.to_string()) {
-                                literal_ids.insert(predicate.to_string(), count);
-                                count+=1;
-                            }
-                        },
-                        Formula::Not(pred_box) => {
-                            if let Formula::Atom(predicate) = &**pred_box {
-                                if !literal_ids.contains_key(&predicate.to_string()) {
-                                    literal_ids.insert(predicate.to_string(), count);
-                                    count+=1;
-                                }
-                                clause.push(-1 * literal_ids.get(&predicate.to_string()).unwrap());
-                            } else {
-                                panic!("not simplified")
-                            }
-                        }
-                        _ => panic!("not in CNF")
-                    }
-                    return (disjuncts.len() as u32, vec![clause]);
-                }
-            }
-            Formula::Not(p) => {
-                if let Formula::Atom(predicate) = *p {
-                    if !literal_ids.contains_key(&predicate.to_string()) {
-                        literal_ids.insert(predicate.to_string(), count);
-                        count+=1;
-                    }
-                    let clause = vec![-1 * literal_ids.get(&predicate.to_string()).unwrap()];
-                    clauses.push(clause);
-                } else {
-                    panic!("not simplified")
-                }
-            }
-            Formula::Atom(predicate) => {
-                if !literal_ids.contains_key(&predicate.to_string()) {
-                    literal_ids.insert(predicate.to_string(), count);
-                    count+=1;
-                }
-                let clause = vec![literal_ids.get(&predicate.to_string()).unwrap().clone()];
-                clauses.push(clause);
-            }
-            token => panic!("unexpected {:?}", token)
-        }
-        ((count - 1) as u32, clauses)
+    // None if the formula is too large to encode, or the SAT solver gives no answer for it
+    pub fn check_sat(&self, solver: &dyn SatSolver) -> Option<bool> {
+        let (var_count, clauses) = self.to_tseitin_clauses()?;
+        solver.solve(var_count, &clauses)
     }
 
+    // decided by the built-in solver, where formulas too large to encode are assumed to be satisfiable
     pub fn is_sat(&self) -> bool {
-        let cnf = self.to_cnf();
-        let (var_count, mut clauses) = cnf.to_clauses();
-        Robinson::parser::preproc_and_solve(clauses.as_mut(), var_count as usize)
+        self.check_sat(&Dpll).unwrap_or(true)
     }
 }
 
@@ -438,7 +392,7 @@ impl<'a> fmt::Display for Formula<'a> {
                 let vars_str = vars.iter().map(|var| format!("{}", var.name)).collect::<Vec<_>>().join(", ");
                 write!(f, "∀{}: {}", vars_str, inner)
             }
-            Formula::Equals(lhs, rhs) => write!(f, "{} = {}", lhs, rhs),
+            Formula::Equals(lhs, rhs) => write!(f, "{} = {}", lhs.name, rhs.name),
         }
     }
 }
@@ -546,6 +500,25 @@ mod tests {
         assert_eq!(cnf.is_sat(), true);
     }
 
+    #[test]
+    pub fn quantified_is_sat_test() {
+        let a = || Box::new(Formula::Atom(Predicate::new_dummy("a")));
+        let quantified = || Box::new(Formula::ForAll(vec![], a()));
+        // (a or forall: a) and (a -> forall: a) and not a
+        let formula = Formula::And(vec![
+            Box::new(Formula::Or(vec![a(), quantified()])),
+            Box::new(Formula::Imply(vec![a()], vec![quantified()])),
+            Box::new(Formula::Not(a())),
+        ]);
+        assert_eq!(formula.is_sat(), true);
+        // a and (a -> not a)
+        let formula = Formula::And(vec![
+            a(),
+            Box::new(Formula::Imply(vec![a()], vec![Box::new(Formula::Not(a()))])),
+        ]);
+        assert_eq!(formula.is_sat(), false);
+    }
+
     #[test]
     pub fn xor_simplification_test() {
         // (a xor b) = (a+b).(~a+~b)
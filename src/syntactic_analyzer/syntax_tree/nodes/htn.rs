@@ -29,8 +29,8 @@ pub struct Subtask<'a> {
 
 #[derive(Debug, Clone)]
 pub enum Constraint<'a> {
-    Equal(&'a str, &'a str),
-    NotEqual(&'a str, &'a str)
+    Equal(Symbol<'a>, Symbol<'a>),
+    NotEqual(Symbol<'a>, Symbol<'a>)
 }
 
 #[derive(Debug, Clone)]
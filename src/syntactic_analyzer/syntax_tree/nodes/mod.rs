@@ -5,6 +5,7 @@ mod htn;
 mod method;
 mod action;
 mod formula;
+mod tseitin;
 
 pub use predicate::Predicate;
 pub use task::Task;
@@ -12,4 +13,4 @@ pub use symbol::*;
 pub use htn::*;
 pub use method::*;
 pub use action::*;
-pub use formula::*;
\ No newline at end of file
+pub use formula::*;
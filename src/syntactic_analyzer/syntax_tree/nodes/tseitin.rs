@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+
+use super::*;
+
+// encodings beyond this many clauses are given up on, and the formula is left undecided
+const MAX_TSEITIN_CLAUSES: usize = 100_000;
+
+// Tseitin encoding, where every connective gets an auxiliary variable equivalent to it.
+// The clauses are linear in the size of the formula, and satisfiable iff the formula is.
+struct TseitinEncoder {
+    variables: HashMap<String, i32>,
+    count: i32,
+    clauses: Vec<Vec<i32>>,
+}
+
+impl TseitinEncoder {
+    fn new() -> TseitinEncoder {
+        TseitinEncoder {
+            variables: HashMap::new(),
+            count: 0,
+            clauses: vec![],
+        }
+    }
+
+    fn fresh(&mut self) -> i32 {
+        self.count += 1;
+        self.count
+    }
+
+    fn variable(&mut self, key: String) -> i32 {
+        if let Some(id) = self.variables.get(&key) {
+            return *id;
+        }
+        let id = self.fresh();
+        self.variables.insert(key, id);
+        id
+    }
+
+    fn add_clause(&mut self, clause: Vec<i32>) -> Option<()> {
+        self.clauses.push(clause);
+        match self.clauses.len() > MAX_TSEITIN_CLAUSES {
+            true => None,
+            false => Some(()),
+        }
+    }
+
+    // top level conjuncts and disjunctions are asserted without auxiliary variables
+    fn assert(&mut self, formula: &Formula) -> Option<()> {
+        match formula {
+            Formula::Empty => Some(()),
+            Formula::And(fs) => {
+                for f in fs {
+                    self.assert(f)?;
+                }
+                Some(())
+            }
+            Formula::Or(fs) => {
+                let mut clause = vec![];
+                for f in fs {
+                    clause.push(self.literal(f)?);
+                }
+                self.add_clause(clause)
+            }
+            _ => {
+                let literal = self.literal(formula)?;
+                self.add_clause(vec![literal])
+            }
+        }
+    }
+
+    // a literal that is equivalent to the formula
+    fn literal(&mut self, formula: &Formula) -> Option<i32> {
+        match formula {
+            Formula::Atom(predicate) => Some(self.variable(predicate.to_string())),
+            // equalities are independent of each other (i.e. no transitivity), so that
+            // distinct objects are never mistaken for a contradiction
+            Formula::Equals(a, b) => {
+                if a.name == b.name {
+                    return self.and(vec![]);
+                }
+                let (a, b) = if a.name < b.name { (a, b) } else { (b, a) };
+                Some(self.variable(format!("{} = {}", a.name, b.name)))
+            }
+            Formula::Not(f) => Some(-self.literal(f)?),
+            Formula::And(fs) => {
+                let literals = self.literals(fs)?;
+                self.and(literals)
+            }
+            Formula::Or(fs) => {
+                let literals = self.literals(fs)?;
+                self.or(literals)
+            }
+            Formula::Xor(fs) => {
+                let literals = self.literals(fs)?;
+                self.xor(literals)
+            }
+            Formula::Imply(ps, qs) => {
+                let ps = self.literals(ps)?;
+                let p = self.and(ps)?;
+                let qs = self.literals(qs)?;
+                let q = self.and(qs)?;
+                self.or(vec![-p, q])
+            }
+            Formula::Empty | Formula::ForAll(_, _) | Formula::Exists(_, _) => {
+                unreachable!("quantifiers are not dropped")
+            }
+        }
+    }
+
+    fn literals(&mut self, fs: &[Box<Formula>]) -> Option<Vec<i32>> {
+        fs.iter().map(|f| self.literal(f)).collect()
+    }
+
+    // x <-> l_1 ^ ... ^ l_n
+    fn and(&mut self, literals: Vec<i32>) -> Option<i32> {
+        let x = self.fresh();
+        let mut clause = vec![x];
+        for literal in literals {
+            self.add_clause(vec![-x, literal])?;
+            clause.push(-literal);
+        }
+        self.add_clause(clause)?;
+        Some(x)
+    }
+
+    // x <-> l_1 v ... v l_n
+    fn or(&mut self, literals: Vec<i32>) -> Option<i32> {
+        let x = self.fresh();
+        let mut clause = vec![-x];
+        for literal in literals {
+            self.add_clause(vec![x, -literal])?;
+            clause.push(literal);
+        }
+        self.add_clause(clause)?;
+        Some(x)
+    }
+
+    // x <-> exactly one of l_1, ..., l_n, i.e. at least one and pairwise at most one
+    fn xor(&mut self, literals: Vec<i32>) -> Option<i32> {
+        let mut conjuncts = vec![self.or(literals.clone())?];
+        for (i, a) in literals.iter().enumerate() {
+            for b in literals.iter().skip(i + 1) {
+                conjuncts.push(self.or(vec![-a, -b])?);
+            }
+        }
+        self.and(conjuncts)
+    }
+}
+
+impl<'a> Formula<'a> {
+    // equisatisfiable clauses of the formula (with quantified subformulas left unconstrained),
+    // or None if the encoding exceeds MAX_TSEITIN_CLAUSES
+    pub fn to_tseitin_clauses(&self) -> Option<(u32, Vec<Vec<i32>>)> {
+        let mut encoder = TseitinEncoder::new();
+        encoder.assert(&self.drop_quantifiers())?;
+        Some((encoder.count as u32, encoder.clauses))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexical_analyzer::TokenPosition;
+
+    fn atom(name: &str) -> Box<Formula<'_>> {
+        Box::new(Formula::Atom(Predicate::new_dummy(name)))
+    }
+
+    fn not(f: Box<Formula>) -> Box<Formula> {
+        Box::new(Formula::Not(f))
+    }
+
+    #[test]
+    pub fn tseitin_size_test() {
+        // (a_1 ^ b_1) v ... v (a_n ^ b_n) has 2^n clauses in the textbook CNF
+        let names: Vec<String> = (0..64).map(|i| format!("p{}", i)).collect();
+        let disjuncts = names
+            .chunks(2)
+            .map(|pair| Box::new(Formula::And(vec![atom(&pair[0]), atom(&pair[1])])))
+            .collect();
+        let formula = Formula::And(vec![
+            Box::new(Formula::Or(disjuncts)),
+            not(atom("p0")),
+        ]);
+        let (var_count, clauses) = formula.to_tseitin_clauses().unwrap();
+        assert_eq!(var_count, 64 + 32);
+        assert_eq!(clauses.len(), 32 * 3 + 2);
+        assert_eq!(formula.is_sat(), true);
+    }
+
+    #[test]
+    pub fn tseitin_xor_test() {
+        // oneof a b c with a, b and c
+        let formula = Formula::And(vec![
+            Box::new(Formula::Xor(vec![atom("a"), atom("b"), atom("c")])),
+            atom("a"),
+            atom("b"),
+            atom("c"),
+        ]);
+        assert_eq!(formula.is_sat(), false);
+        // oneof a b c with a, not b and not c
+        let formula = Formula::And(vec![
+            Box::new(Formula::Xor(vec![atom("a"), atom("b"), atom("c")])),
+            atom("a"),
+            not(atom("b")),
+            not(atom("c")),
+        ]);
+        assert_eq!(formula.is_sat(), true);
+        // oneof a b c without any of them
+        let formula = Formula::And(vec![
+            Box::new(Formula::Xor(vec![atom("a"), atom("b"), atom("c")])),
+            not(atom("a")),
+            not(atom("b")),
+            not(atom("c")),
+        ]);
+        assert_eq!(formula.is_sat(), false);
+        // oneof a b with a and b
+        let formula = Formula::And(vec![
+            Box::new(Formula::Xor(vec![atom("a"), atom("b")])),
+            atom("a"),
+            atom("b"),
+        ]);
+        assert_eq!(formula.is_sat(), false);
+        // not (oneof a b) with a and not b
+        let formula = Formula::And(vec![
+            not(Box::new(Formula::Xor(vec![atom("a"), atom("b")]))),
+            atom("a"),
+            not(atom("b")),
+        ]);
+        assert_eq!(formula.is_sat(), false);
+    }
+
+    #[test]
+    pub fn tseitin_equality_test() {
+        let symbol = |name| Symbol::new(name, TokenPosition::default(), None, None);
+        let equals = |a, b| Box::new(Formula::Equals(symbol(a), symbol(b)));
+        // x = y and x != y
+        let formula = Formula::And(vec![equals("x", "y"), not(equals("y", "x"))]);
+        assert_eq!(formula.is_sat(), false);
+        // x != x
+        assert_eq!(not(equals("x", "x")).is_sat(), false);
+        // pairwise distinct x, y and z
+        let formula = Formula::And(vec![
+            not(equals("x", "y")),
+            not(equals("y", "z")),
+            not(equals("x", "z")),
+        ]);
+        assert_eq!(formula.is_sat(), true);
+    }
+}
@@ -2,7 +2,10 @@ use super::*;
 use crate::TokenPosition;
 #[derive(Debug)]
 pub struct ProblemAST<'a> {
+    pub name: String,
+    pub domain_name: String,
     pub requirements: Vec<RequirementType>,
+    pub requirement_positions: Vec<TokenPosition>,
     pub init_tn: Option<InitialTaskNetwork<'a>>,
     pub init_state: Vec<Predicate<'a>>,
     pub goal: Option<Formula<'a>>,
@@ -10,9 +13,12 @@ pub struct ProblemAST<'a> {
 }
 
 impl <'a> ProblemAST<'a> {
-    pub fn new() -> ProblemAST<'a> {
+    pub fn new(name: String, domain_name: String) -> ProblemAST<'a> {
         ProblemAST {
+            name,
+            domain_name,
             requirements: vec![],
+            requirement_positions: vec![],
             init_tn: None,
             init_state: vec![],
             goal: None,
@@ -36,7 +42,8 @@ impl <'a> ProblemAST<'a> {
     pub fn add_goal(&mut self, goal: Formula<'a>) {
         self.goal = Some(goal);
     }
-    pub fn add_requirement(&mut self, req: RequirementType) {
+    pub fn add_requirement(&mut self, req: RequirementType, position: TokenPosition) {
         self.requirements.push(req);
+        self.requirement_positions.push(position);
     }
 }
\ No newline at end of file
@@ -293,9 +293,9 @@ mod tests {
                                 match neq {
                                     Formula::Not(equality) => {
                                         match **equality {
-                                            Formula::Equals(a, b) => {
-                                                assert_eq!(a, "l1");
-                                                assert_eq!(b, "l2");
+                                            Formula::Equals(ref a, ref b) => {
+                                                assert_eq!(a.name, "l1");
+                                                assert_eq!(b.name, "l2");
                                             }
                                             _ => { panic!("equality constraint not parsed successfully")}
                                         }
@@ -366,9 +366,9 @@ mod tests {
                                     _ => { panic!("wrong parameter type") }
                                 }
                                 match **exp {
-                                    Formula::Equals(a,b ) => {
-                                        assert_eq!(a, "l1");
-                                        assert_eq!(b, "l2");
+                                    Formula::Equals(ref a, ref b) => {
+                                        assert_eq!(a.name, "l1");
+                                        assert_eq!(b.name, "l2");
                                     }
                                     _ => {
                                         panic!("wrong expression parsing")
@@ -487,8 +487,11 @@ mod tests {
                     match tn.tn.constraints {
                         Some(constraint) => {
                             assert_eq!(constraint.len(), 1);
-                            match constraint[0] {
-                                Constraint::NotEqual("term1", "term2") => {},
+                            match &constraint[0] {
+                                Constraint::NotEqual(a, b) => {
+                                    assert_eq!(a.name, "term1");
+                                    assert_eq!(b.name, "term2");
+                                },
                                 _ => { panic!("constraint not parsed correctly")}
                             }
                         }
@@ -776,6 +779,47 @@ mod tests {
         }
     }
 
+    #[test]
+    pub fn conditional_effect_parsing_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:action a_1
+                 :parameters (?p_1)
+                 :precondition (imply (at ?p_1) (hold ?p_1))
+                 :effect (forall (?loc) (when (and (at ?loc) (hold ?loc)) (not (at ?loc))))
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(AbstractSyntaxTree::Domain(ast)) => {
+                let action = &ast.actions[0];
+                match action.preconditions.as_ref().unwrap() {
+                    Formula::Imply(antecedents, consequences) => {
+                        assert_eq!(antecedents.len(), 1);
+                        assert_eq!(consequences.len(), 1);
+                    }
+                    _ => panic!("wrong formula"),
+                }
+                match action.effects.as_ref().unwrap() {
+                    Formula::ForAll(q, e) => {
+                        assert_eq!(q[0].name, "loc");
+                        match e.as_ref() {
+                            Formula::Imply(antecedents, consequences) => {
+                                assert_eq!(antecedents[0].get_propositional_predicates().len(), 2);
+                                assert!(matches!(consequences[0].as_ref(), Formula::Not(_)));
+                            }
+                            _ => panic!("wrong formula"),
+                        }
+                    }
+                    _ => panic!("wrong formula"),
+                }
+            }
+            _ => panic!("parsing errors"),
+        }
+    }
+
     #[test]
     pub fn non_deterministic_action_parsing_test() {
         let program = String::from(
@@ -1007,4 +1051,59 @@ mod tests {
             _ => panic!()
         }
     }
+
+    #[test]
+    pub fn block_recovery_test() {
+        let program = String::from(
+            "(define (domain bal)
+                (:predicates (at ?a))
+                (:action a_1
+                 :parameters (?p_1)
+                 :precondition (at ?p_1
+                 :effect (not (at ?p_1))
+                )
+                (:task abs_1 :parameters (?a))
+                (:method m_1
+                 :parameters (?p_1)
+                 :task abs_1 ?p_1)
+                 :ordered-subtasks (and (t1 (a_1 ?p_1)))
+                )
+                (:action a_2
+                 :parameters (?p_1)
+                 :precondition (at ?p_1)
+                 :effect (not (at ?p_1))
+                )
+             ) ",
+        )
+        .into_bytes();
+        let lexer = LexicalAnalyzer::new(&program);
+        let parser = Parser::new(lexer);
+        match parser.parse_all() {
+            Ok(_) => panic!("errors are not caught"),
+            Err(errors) => {
+                assert_eq!(errors.len(), 2);
+                match &errors[0] {
+                    ParsingError::Syntactic(error) => {
+                        assert_eq!(error.position.line, 6);
+                    }
+                    token => panic!("{:?}", token)
+                }
+                match &errors[1] {
+                    ParsingError::Syntactic(error) => {
+                        assert_eq!(error.position.line, 11);
+                    }
+                    token => panic!("{:?}", token)
+                }
+            }
+        }
+        // without recovery, only the first error is reported
+        let lexer = LexicalAnalyzer::new(&program);
+        match Parser::new(lexer).parse() {
+            Ok(_) => panic!("errors are not caught"),
+            Err(ParsingError::Syntactic(error)) => {
+                assert_eq!(error.position.line, 6);
+            }
+            Err(token) => panic!("{:?}", token)
+        }
+    }
 }
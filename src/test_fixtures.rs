@@ -0,0 +1,94 @@
+// models shared by the tests of several modules
+
+use crate::lexical_analyzer::LexicalAnalyzer;
+use crate::syntactic_analyzer::*;
+
+// packages are moved along roads, directly or via other locations, and
+// marked as delivered where they are
+pub const DELIVERY_DOMAIN: &str = "(define (domain delivery)
+    (:types package location - object)
+    (:predicates
+        (at ?p - package ?l - location)
+        (road ?from ?to - location)
+        (delivered ?p - package)
+    )
+    (:task deliver :parameters (?p - package ?l - location))
+    (:task deliver_both :parameters (?a ?b - package ?l - location))
+    (:method m_direct
+     :parameters (?p - package ?from ?to - location)
+     :task (deliver ?p ?to)
+     :precondition (at ?p ?from)
+     :ordered-subtasks (and
+        (t1 (move ?p ?from ?to))
+     )
+    )
+    (:method m_via
+     :parameters (?p - package ?from ?via ?to - location)
+     :task (deliver ?p ?to)
+     :precondition (at ?p ?from)
+     :ordered-subtasks (and
+        (t1 (move ?p ?from ?via))
+        (t2 (deliver ?p ?to))
+     )
+    )
+    (:method m_already
+     :parameters (?p - package ?l - location)
+     :task (deliver ?p ?l)
+     :precondition (at ?p ?l)
+     :ordered-subtasks ()
+    )
+    (:method m_stay
+     :parameters (?p - package ?l - location ?other - location)
+     :task (deliver ?p ?l)
+     :subtasks (and (t1 (move ?p ?other ?l)))
+     :constraints (and (= ?l ?other) (not (= ?l ?other)))
+    )
+    (:method m_both
+     :parameters (?a ?b - package ?l - location)
+     :task (deliver_both ?a ?b ?l)
+     :subtasks (and
+        (t1 (deliver ?a ?l))
+        (t2 (deliver ?b ?l))
+     )
+     :ordering (and
+        (< t1 t2)
+     )
+    )
+    (:action move
+     :parameters (?p - package ?from ?to - location)
+     :precondition (and (at ?p ?from) (road ?from ?to))
+     :effect (and (not (at ?p ?from)) (at ?p ?to))
+    )
+    (:action mark
+     :parameters (?p - package ?l - location)
+     :precondition (at ?p ?l)
+     :effect (delivered ?p)
+    )
+)";
+
+// a problem of the delivery domain, where `htn` is the body of the initial task network
+pub fn delivery_problem(objects: &str, htn: &str, init: &str, goal: &str) -> String {
+    format!(
+        "(define (problem p) (:domain delivery)
+            (:objects {})
+            (:htn :parameters () {})
+            (:init {})
+            (:goal (and {}))
+        )",
+        objects, htn, init, goal
+    )
+}
+
+pub fn parse(program: &Vec<u8>) -> AbstractSyntaxTree<'_> {
+    Parser::new(LexicalAnalyzer::new(program)).parse().unwrap()
+}
+
+// parses the delivery domain and the problem, and passes them on
+pub fn with_delivery<T>(problem: &str, f: impl FnOnce(&DomainAST, &ProblemAST) -> T) -> T {
+    let domain = DELIVERY_DOMAIN.as_bytes().to_vec();
+    let problem = problem.as_bytes().to_vec();
+    match (parse(&domain), parse(&problem)) {
+        (AbstractSyntaxTree::Domain(d), AbstractSyntaxTree::Problem(p)) => f(&d, &p),
+        _ => panic!("expected a domain and a problem"),
+    }
+}
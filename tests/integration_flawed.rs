@@ -1,6 +1,6 @@
 extern crate hddl_analyzer;
 
-use hddl_analyzer::{HDDLAnalyzer, ParsingError, SemanticErrorType, WarningType};
+use hddl_analyzer::{HDDLAnalyzer, ParsingError, SatSolver, SemanticErrorType, Snippet, VerifyOptions, WarningType};
 use std::fs;
 
 #[test]
@@ -63,6 +63,50 @@ pub fn cyclic_type_validation_test() {
     }
 }
 
+#[test]
+pub fn duplicate_action_snippet_test() {
+    let domain = fs::read(
+        "tests/flawed_domains/duplicate-action-domain.hddl"
+    ).unwrap();
+    match HDDLAnalyzer::verify(&domain, None) {
+        Ok(_) => panic!("error not found"),
+        Err(err) => {
+            let snippet = Snippet::new(&domain, "domain.hddl");
+            let name = "move_seg_twe1_0_200_seg_twe2_0_50_south_south_medium";
+            let expected = format!(
+                "  --> domain.hddl:82:10\n   |\n63 | (:action {}\n   |          {} first defined here\n...\n82 | (:action {}\n   |          {} redefined here\n",
+                name, "-".repeat(name.len()), name, "^".repeat(name.len())
+            );
+            assert_eq!(snippet.render(&err.labels()), expected);
+        }
+    }
+}
+
+#[test]
+pub fn duplicate_action_structured_output_test() {
+    let domain = fs::read(
+        "tests/flawed_domains/duplicate-action-domain.hddl"
+    ).unwrap();
+    let diagnostics = HDDLAnalyzer::verify_all(&domain, None);
+    let json: serde_json::Value = serde_json::from_str(&diagnostics.to_json("domain.hddl", None, None)).unwrap();
+    assert_eq!(json["success"], false);
+    let diagnostic = &json["diagnostics"][0];
+    assert_eq!(diagnostic["kind"], "DuplicateActionDeclaration");
+    assert_eq!(diagnostic["severity"], "error");
+    assert_eq!(diagnostic["file"], "domain.hddl");
+    assert_eq!(diagnostic["position"]["line"], 82);
+    assert_eq!(diagnostic["position"]["column"], 10);
+    assert_eq!(diagnostic["related"][0]["position"]["line"], 63);
+    let sarif: serde_json::Value = serde_json::from_str(&diagnostics.to_sarif("domain.hddl", None, None)).unwrap();
+    assert_eq!(sarif["version"], "2.1.0");
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "DuplicateActionDeclaration");
+    assert_eq!(result["level"], "error");
+    let region = &result["locations"][0]["physicalLocation"]["region"];
+    assert_eq!(region["startLine"], 82);
+    assert_eq!(result["relatedLocations"][0]["physicalLocation"]["region"]["startLine"], 63);
+}
+
 #[test]
 pub fn duplicate_action_validation_test() {
     let domain = fs::read(
@@ -526,4 +570,176 @@ pub fn ignore_possibly_complementary_effects_validation_test() {
             panic!()
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+pub fn undeclared_type_all_errors_validation_test() {
+    let domain = fs::read(
+        "tests/flawed_domains/undefined-type-domain.hddl"
+    ).unwrap();
+    let diagnostics = HDDLAnalyzer::verify_all(&domain, None);
+    assert_eq!(diagnostics.errors.len(), 7);
+    for error in diagnostics.errors.iter() {
+        match error {
+            ParsingError::Semantic(SemanticErrorType::UndefinedType(x)) => {
+                assert_eq!(x.symbol, "airplane")
+            }
+            token => panic!("{:?}", token)
+        }
+    }
+}
+
+#[test]
+pub fn metadata_json_test() {
+    let domain = fs::read(
+        "tests/flawed_domains/abstract-task-without-refinement-domain.hddl"
+    ).unwrap();
+    let metadata = HDDLAnalyzer::get_metadata(&domain, None).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&metadata.to_json()).unwrap();
+    assert_eq!(json["domain"], "airport_fixed_structure");
+    assert_eq!(json["recursion"]["class"], "EmptyRecursion");
+    assert_eq!(json["recursion"]["cycle"][0]["task"], "AchieveSomeGoal");
+    assert_eq!(json["recursion"]["cycle"][0]["method"], "ParkAirplane");
+    assert_eq!(json["recursive_components"][0]["tasks"][0], "AchieveSomeGoal");
+    assert_eq!(json["recursive_components"][0]["class"], "EmptyRecursion");
+    assert_eq!(json["hierarchy_class"]["classes"], serde_json::json!(["Regular", "TailRecursive"]));
+    assert_eq!(json["hierarchy_class"]["violating_methods"]["Acyclic"], "ParkAirplane");
+    assert_eq!(json["hierarchy_class"]["violating_methods"]["Regular"], serde_json::Value::Null);
+    assert_eq!(json["hierarchy_class"]["totally_ordered"], true);
+    assert_eq!(json["task_bounds"].as_array().unwrap().len(), 0);
+    assert_eq!(json["nullables"].as_array().unwrap().len(), 0);
+    assert_eq!(json["counts"]["actions"], 2);
+    assert_eq!(json["counts"]["methods"], 1);
+}
+
+#[test]
+pub fn metadata_undefined_task_test() {
+    let domain = fs::read(
+        "tests/flawed_domains/undefined-task-domain.hddl"
+    ).unwrap();
+    match HDDLAnalyzer::get_metadata(&domain, None) {
+        Err(hddl_analyzer::ParsingError::Semantic(SemanticErrorType::UndefinedSubtask(t))) => {
+            assert_eq!(t.symbol, "undefined_task");
+            assert_eq!(t.position.line, 53);
+        }
+        _ => panic!("error not found"),
+    }
+}
+
+#[test]
+pub fn metadata_problem_test() {
+    let domain = fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = fs::read_to_string("tests/ipc/Transport/pfile01.hddl").unwrap();
+    let metadata = HDDLAnalyzer::get_metadata(&domain, Some(&problem.clone().into_bytes())).unwrap();
+    assert_eq!(metadata.domain_name, "domain_htn");
+    // the problem is checked as well
+    let flawed_problem = problem.replace("(at truck_0 city_loc_2)", "(at truck_0 city_loc_9)").into_bytes();
+    match HDDLAnalyzer::get_metadata(&domain, Some(&flawed_problem)) {
+        Err(hddl_analyzer::ParsingError::Semantic(SemanticErrorType::UndefinedObject(undefined))) => {
+            assert_eq!(undefined.symbol, "city_loc_9");
+            assert_eq!(undefined.position.line, 32);
+        }
+        result => panic!("expected an undefined object, found {:?}", result.err()),
+    }
+}
+
+#[test]
+pub fn swapped_files_verify_all_test() {
+    let domain = fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = fs::read("tests/ipc/Transport/pfile01.hddl").unwrap();
+    let diagnostics = HDDLAnalyzer::verify_all(&problem, None);
+    match &diagnostics.errors[..] {
+        [ParsingError::Syntactic(error)] => {
+            assert_eq!(error.expected, "a domain definition");
+            assert_eq!(error.found, "a problem definition");
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+    let diagnostics = HDDLAnalyzer::verify_all(&domain, Some(&domain));
+    assert!(diagnostics.errors.is_empty());
+    match &diagnostics.problem_errors[..] {
+        [ParsingError::Syntactic(error)] => {
+            assert_eq!(error.expected, "a problem definition");
+            assert_eq!(error.found, "a domain definition");
+        }
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+#[test]
+pub fn stray_problem_token_test() {
+    let domain = fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = b"(define (problem p) (:domain transport) stray)".to_vec();
+    let diagnostics = HDDLAnalyzer::verify_all(&domain, Some(&problem));
+    match &diagnostics.problem_errors[..] {
+        [ParsingError::Syntactic(error)] => assert_eq!(error.expected, "a block definition"),
+        errors => panic!("unexpected errors {:?}", errors),
+    }
+}
+
+#[test]
+pub fn smells_as_notes_test() {
+    let domain = fs::read("tests/ipc/Hiking/domain.hddl").unwrap();
+    let problem = fs::read("tests/ipc/Hiking/p01.hddl").unwrap();
+    // smells are only reported on request
+    let diagnostics = HDDLAnalyzer::verify_all(&domain, Some(&problem));
+    assert!(diagnostics.warnings.iter().all(|w| !w.is_smell()));
+    let options = VerifyOptions { smells: true, ..Default::default() };
+    let diagnostics = HDDLAnalyzer::verify_all_with(&domain, Some(&problem), &options);
+    assert!(diagnostics.warnings.iter().any(|w| matches!(w, WarningType::ImmutablePredicate(_))));
+    let json: serde_json::Value = serde_json::from_str(&diagnostics.to_json("domain.hddl", None, None)).unwrap();
+    for diagnostic in json["diagnostics"].as_array().unwrap() {
+        match diagnostic["kind"].as_str().unwrap() {
+            "ImmutablePredicate" | "UnusedType" | "UnusedPredicate" | "UnusedParameter" | "RedundantEffect"
+            | "UnusedRequirement" | "UndeclaredRequirement" | "UndeclaredProblemRequirement"
+            | "UndeclaredProblemFeature" => assert_eq!(diagnostic["severity"], "note"),
+            _ => assert_eq!(diagnostic["severity"], "warning"),
+        }
+    }
+    // types of objects and the tasks of the initial task network are used
+    let warnings = HDDLAnalyzer::verify_with(&domain, Some(&problem), &options).unwrap();
+    assert!(!warnings.iter().any(|w| matches!(w, WarningType::UnusedType(_))));
+    let unused_tasks: Vec<String> = warnings
+        .iter()
+        .filter(|w| matches!(w, WarningType::UnusedTask(_)))
+        .map(|w| w.to_string())
+        .collect();
+    assert_eq!(unused_tasks, vec!["line 32: compound task go_hiking is declared, but never used as a subtask"]);
+    // without a problem, types and tasks are not checked
+    let warnings = HDDLAnalyzer::verify_with(&domain, None, &options).unwrap();
+    assert!(!warnings.iter().any(|w| matches!(w, WarningType::UnusedType(_) | WarningType::UnusedTask(_))));
+}
+
+// answers every query with the given result
+struct FixedSolver(Option<bool>);
+
+impl SatSolver for FixedSolver {
+    fn solve(&self, _var_count: u32, _clauses: &[Vec<i32>]) -> Option<bool> {
+        self.0
+    }
+}
+
+#[test]
+pub fn sat_solver_option_test() {
+    let domain = fs::read("tests/ipc/Hiking/domain.hddl").unwrap();
+    let undecided = |warnings: &[WarningType]| {
+        warnings.iter().filter(|w| matches!(w, WarningType::UndecidedSatisfiability(_))).count()
+    };
+    // the built-in solver decides every formula
+    let warnings = HDDLAnalyzer::verify_with(&domain, None, &VerifyOptions::default()).unwrap();
+    assert_eq!(undecided(&warnings), 0);
+    // each call uses the solver of its own options
+    let options = VerifyOptions { sat_solver: Some(Box::new(FixedSolver(None))), ..Default::default() };
+    let warnings = HDDLAnalyzer::verify_with(&domain, None, &options).unwrap();
+    assert!(undecided(&warnings) > 0);
+    let diagnostics = HDDLAnalyzer::verify_all_with(&domain, None, &options);
+    assert_eq!(undecided(&diagnostics.warnings), undecided(&warnings));
+    let options = VerifyOptions { sat_solver: Some(Box::new(FixedSolver(Some(false)))), ..Default::default() };
+    match HDDLAnalyzer::verify_with(&domain, None, &options) {
+        Err(ParsingError::Semantic(
+            SemanticErrorType::ComplementaryActionPrecondition(_) | SemanticErrorType::ComplementaryActionEffect(_),
+        )) => {}
+        result => panic!("unexpected result {:?}", result),
+    }
+    assert!(HDDLAnalyzer::verify(&domain, None).is_ok());
+}
@@ -1,7 +1,8 @@
 extern crate hddl_analyzer;
 
-use hddl_analyzer::{HDDLAnalyzer, SemanticErrorType};
+use hddl_analyzer::{Formula, HDDLAnalyzer, ParsingError, PlanErrorType, SearchResult, SemanticErrorType, TaskOrdering};
 use std::fs;
+use std::time::{Duration, Instant};
 
 #[test]
 #[ignore = "takes too long to run"]
@@ -29,3 +30,96 @@ pub fn ipc_validation_test() {
         }
     }    
 }
+
+#[test]
+pub fn ipc_grounding_time_test() {
+    // problems whose methods used to be instantiated for every task before any pruning
+    for (folder, problem, methods) in [
+        ("Minecraft-Regular", "p-003-003-003-003.hddl", 196),
+        ("Minecraft-Player", "p-003-003-003-003.hddl", 177088),
+    ] {
+        let domain = fs::read(format!("tests/ipc/{}/domain.hddl", folder)).unwrap();
+        let problem = fs::read(format!("tests/ipc/{}/{}", folder, problem)).unwrap();
+        let domain_ast = HDDLAnalyzer::parse_domain(&domain).unwrap();
+        let problem_ast = HDDLAnalyzer::parse_problem(&problem).unwrap();
+        let start = Instant::now();
+        let statistics = HDDLAnalyzer::ground(&domain_ast, &problem_ast).unwrap().statistics;
+        assert!(start.elapsed() < Duration::from_secs(60), "grounding {} took {:?}", folder, start.elapsed());
+        assert_eq!(statistics.tdg_reachable.methods, methods);
+        assert!(statistics.relaxed_reachable.methods < 2 * methods);
+    }
+}
+
+#[test]
+pub fn transport_plan_verification_test() {
+    let domain = fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = fs::read("tests/ipc/Transport/pfile01.hddl").unwrap();
+    let plan = fs::read_to_string("tests/plans/transport-pfile01.plan").unwrap();
+    let (domain, problem) = (HDDLAnalyzer::parse_domain(&domain).unwrap(), HDDLAnalyzer::parse_problem(&problem).unwrap());
+    HDDLAnalyzer::verify_plan(&domain, &problem, &plan.clone().into_bytes()).unwrap();
+    // picking up the second package before driving to it
+    let flawed_plan = plan.replace(
+        "4 drive truck_0 city_loc_0 city_loc_1\n5 pick_up truck_0 city_loc_1 package_1 capacity_0 capacity_1",
+        "5 pick_up truck_0 city_loc_1 package_1 capacity_0 capacity_1\n4 drive truck_0 city_loc_0 city_loc_1",
+    );
+    match HDDLAnalyzer::verify_plan(&domain, &problem, &flawed_plan.into_bytes()) {
+        Err(ParsingError::Plan(error)) => {
+            assert!(matches!(error.error_type, PlanErrorType::ViolatedOrdering(14, 15)));
+            assert_eq!(error.step, Some(9));
+            assert_eq!(error.position.unwrap().line, 16);
+        }
+        result => panic!("expected an ordering violation, found {:?}", result),
+    }
+}
+
+#[test]
+pub fn transport_solve_test() {
+    let domain = fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = fs::read("tests/ipc/Transport/pfile01.hddl").unwrap();
+    let (domain, problem) = (HDDLAnalyzer::parse_domain(&domain).unwrap(), HDDLAnalyzer::parse_problem(&problem).unwrap());
+    match HDDLAnalyzer::solve(&domain, &problem, 20, 100000).unwrap() {
+        SearchResult::Solution(plan) => {
+            assert_eq!(plan.lines().filter(|line| line.contains(" drive ")).count(), 4);
+            HDDLAnalyzer::verify_plan(&domain, &problem, &plan.into_bytes()).unwrap();
+        }
+        result => panic!("expected a solution, found {:?}", result),
+    }
+    // two deliveries need at least 8 actions and 10 decompositions
+    assert_eq!(
+        HDDLAnalyzer::solve(&domain, &problem, 17, 100000).unwrap(),
+        SearchResult::NoSolutionUpToDepth(17)
+    );
+}
+
+#[test]
+pub fn transport_library_api_test() {
+    let domain = fs::read("tests/ipc/Transport/domain.hddl").unwrap();
+    let problem = fs::read("tests/ipc/Transport/pfile01.hddl").unwrap();
+    let domain_ast = HDDLAnalyzer::parse_domain(&domain).unwrap();
+    let problem_ast = HDDLAnalyzer::parse_problem(&problem).unwrap();
+    assert_eq!(problem_ast.domain_name, "domain_htn");
+    let initial_tn = &problem_ast.init_tn.as_ref().unwrap().tn;
+    assert_eq!(initial_tn.subtasks.len(), 2);
+    assert!(matches!(initial_tn.orderings, TaskOrdering::Partial(_)));
+
+    let analysis = HDDLAnalyzer::analyze(&domain_ast, Some(&problem_ast)).unwrap();
+    assert_eq!(analysis.symbol_table.actions.len(), domain_ast.actions.len());
+    assert!(analysis.symbol_table.tasks.iter().any(|task| task.name == "deliver"));
+    let drive = domain_ast.actions.iter().find(|action| action.name == "drive").unwrap();
+    assert!(matches!(drive.preconditions, Some(Formula::And(_))));
+
+    let tdg = &analysis.tdg;
+    assert_eq!(tdg.compound_tasks().len(), domain_ast.compound_tasks.len());
+    assert_eq!(tdg.methods_of("deliver"), vec!["m_deliver_ordering_0"]);
+    assert_eq!(tdg.subtasks_of("m_deliver_ordering_0"), vec!["get_to", "load", "unload"]);
+    assert!(tdg.reachable("deliver").unwrap().primitives.contains("drive"));
+
+    // every delivery picks up and drops a package, but driving can be avoided
+    let landmarks = HDDLAnalyzer::landmarks(&domain_ast, &problem_ast).unwrap().unwrap();
+    assert!(landmarks.compound_tasks.contains(&"deliver"));
+    assert!(landmarks.methods.contains(&"m_deliver_ordering_0"));
+    assert_eq!(landmarks.actions, vec!["pick_up", "drop"]);
+
+    // a problem is not a domain
+    assert!(matches!(HDDLAnalyzer::parse_domain(&problem), Err(ParsingError::Syntactic(_))));
+}